@@ -2,6 +2,7 @@ use std::ffi::OsString;
 use std::process::ExitCode;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::FetchMode;
 
 use crate::commands;
 use crate::context::CliSession;
@@ -43,8 +44,26 @@ where
         OutputFormat::Text
     };
 
+    // Error codes are documented independently of any workspace.
+    if let Some(("explain", sub)) = matches.subcommand() {
+        let result = commands::explain::run(sub).map_err(|err| err.with_json(verbosity.json));
+        return emit_result(result?, output);
+    }
+    // Printed schemas are the same in every workspace; only `--write` needs one.
+    if let Some(("schema", sub)) = matches.subcommand()
+        && !sub.get_flag("write")
+    {
+        return emit_result(commands::schema::print(sub), output);
+    }
+
     let workspace_override = matches.get_one::<String>("workspace").cloned();
-    let session = CliSession::bootstrap(workspace_override, verbosity)?;
+    let fetch_mode = if matches.get_flag("offline") {
+        FetchMode::Offline
+    } else {
+        FetchMode::Online
+    };
+    let session = CliSession::bootstrap(workspace_override, verbosity, fetch_mode)
+        .map_err(|err| err.with_json(verbosity.json))?;
     if session.verbosity.verbose {
         tracing::info!(
             workspace = %session.workspace_paths.root().display(),
@@ -55,8 +74,9 @@ where
         );
     }
 
-    let result = dispatch(&session, &matches)?;
-    emit_result(result, output)
+    let result = dispatch(&session, &matches).map_err(|err| err.with_json(verbosity.json));
+    session.finish_progress();
+    emit_result(result?, output)
 }
 
 fn init_tracing() {
@@ -89,12 +109,27 @@ fn build_cli() -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Emit additional logging about template locators, workspace paths, and adapters."),
         )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .action(ArgAction::SetTrue)
+                .help("Resolve https:// dependencies from .specman/cache/remote only, without network access."),
+        )
         .subcommand_required(true)
         .subcommand(commands::status::command())
         .subcommand(commands::spec::command())
         .subcommand(commands::implementation::command())
         .subcommand(commands::scratch::command())
         .subcommand(commands::templates::command())
+        .subcommand(commands::prompt::command())
+        .subcommand(commands::graph::command())
+        .subcommand(commands::impact::command())
+        .subcommand(commands::why::command())
+        .subcommand(commands::fetch::command())
+        .subcommand(commands::vendor::command())
+        .subcommand(commands::lint::command())
+        .subcommand(commands::explain::command())
+        .subcommand(commands::schema::command())
 }
 
 /// Delegates parsed subcommands to their respective modules, ensuring the Lifecycle
@@ -110,6 +145,14 @@ fn dispatch(
         Some(("impl", sub)) => commands::implementation::run(session, sub),
         Some(("scratch", sub)) => commands::scratch::run(session, sub),
         Some(("template", sub)) => commands::templates::run(session, sub),
+        Some(("prompt", sub)) => commands::prompt::run(session, sub),
+        Some(("graph", sub)) => commands::graph::run(session, sub),
+        Some(("impact", sub)) => commands::impact::run(session, sub),
+        Some(("why", sub)) => commands::why::run(session, sub),
+        Some(("fetch", sub)) => commands::fetch::run(session, sub),
+        Some(("vendor", sub)) => commands::vendor::run(session, sub),
+        Some(("lint", sub)) => commands::lint::run(session, sub),
+        Some(("schema", sub)) => commands::schema::run(session, sub),
         _ => Err(CliError::new("missing command", ExitStatus::Usage)),
     }
 }
@@ -1,5 +1,6 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use serde::Serialize;
+use specman::{ArtifactKind, DependencyQuery, ResourceHandle};
 
 use crate::error::{CliError, ExitStatus};
 
@@ -79,3 +80,32 @@ pub fn view_label(view: DependencyView) -> &'static str {
         DependencyView::All => "complete",
     }
 }
+
+/// Resolves the artifact slug for a `dependencies` command. Accepts a bare slug, a resource
+/// handle (`spec://name`), or the read-only `spec://name/dependencies` alias; handles for a
+/// different artifact kind are rejected.
+pub fn target_name(raw: &str, scope: DependencyScope) -> Result<String, CliError> {
+    let handle = match DependencyQuery::parse(raw).map_err(CliError::from)? {
+        Some(query) => Some(query.handle().clone()),
+        None => ResourceHandle::parse(raw).map_err(CliError::from)?,
+    };
+    let Some(handle) = handle else {
+        return Ok(raw.to_string());
+    };
+
+    if handle.kind() != scope_kind(scope) {
+        return Err(CliError::new(
+            format!("{raw} does not address a {}", scope_label(scope)),
+            ExitStatus::Usage,
+        ));
+    }
+    Ok(handle.slug().to_string())
+}
+
+fn scope_kind(scope: DependencyScope) -> ArtifactKind {
+    match scope {
+        DependencyScope::Specification => ArtifactKind::Specification,
+        DependencyScope::Implementation => ArtifactKind::Implementation,
+        DependencyScope::ScratchPad => ArtifactKind::ScratchPad,
+    }
+}
@@ -0,0 +1,37 @@
+use clap::{Arg, ArgMatches, Command};
+use specman::ErrorCode;
+
+use crate::commands::CommandResult;
+use crate::error::{CliError, ExitStatus};
+
+/// Defines `specman explain`, which describes an error code and how to resolve it. Without a
+/// code it lists every code.
+pub fn command() -> Command {
+    Command::new("explain")
+        .about("Describe an error code (e.g. SM-DEP-CYCLE) and how to fix it")
+        .arg(
+            Arg::new("code")
+                .value_name("CODE")
+                .help("Error code printed by a failed command; lists every code when omitted"),
+        )
+}
+
+/// Runs without a session so codes can be looked up outside any workspace.
+pub fn run(matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let codes = match matches.get_one::<String>("code") {
+        Some(raw) => vec![raw.parse::<ErrorCode>().map_err(|_| {
+            let known: Vec<&str> = ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+            CliError::new(
+                format!(
+                    "unknown error code '{raw}'; expected one of {}",
+                    known.join(", ")
+                ),
+                ExitStatus::Usage,
+            )
+        })?],
+        None => ErrorCode::ALL.to_vec(),
+    };
+    Ok(CommandResult::Explain {
+        codes: codes.into_iter().map(ErrorCode::explanation).collect(),
+    })
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use clap::{ArgMatches, Command};
+use specman::{
+    CachingContentFetcher, DependencyGraphServices, FetchMode, FilesystemWorkspaceLocator,
+};
+
+use crate::commands::CommandResult;
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+use crate::util;
+
+/// Defines `specman fetch`, which downloads every `https://` dependency into the remote
+/// cache so later runs can resolve them with `--offline`.
+pub fn command() -> Command {
+    Command::new("fetch")
+        .about("Download https:// dependencies into .specman/cache/remote for offline use")
+}
+
+pub fn run(session: &CliSession, _matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    if session.fetch_mode == FetchMode::Offline {
+        return Err(CliError::new(
+            "specman fetch needs network access; drop --offline",
+            ExitStatus::Usage,
+        ));
+    }
+
+    // Refresh mode reports unreachable origins instead of quietly serving stale copies.
+    let fetcher = CachingContentFetcher::new(&session.workspace_paths, FetchMode::Refresh);
+    let cache = util::workspace_relative(session.workspace_paths.root(), fetcher.root());
+    let graph = DependencyGraphServices::with_fetcher(
+        FilesystemWorkspaceLocator::new(session.workspace_paths.root()),
+        Arc::new(fetcher),
+    );
+    if let Some(progress) = &session.progress {
+        graph.set_progress_observer(progress.clone());
+    }
+
+    let artifacts = graph.fetch_remote_artifacts()?;
+    Ok(CommandResult::Fetch { cache, artifacts })
+}
@@ -0,0 +1,90 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::{DependencyRelation, GraphFormat, ResourceHandle};
+
+use crate::commands::CommandResult;
+use crate::commands::dependencies::{self, DependencyView};
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+
+/// Defines `specman graph`, which exports the workspace dependency graph for diagrams in
+/// reviews and documentation.
+pub fn command() -> Command {
+    dependencies::with_direction_flags(
+        Command::new("graph")
+            .about("Export the spec/impl/scratch dependency graph as DOT, Mermaid, or GraphML")
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_name("FORMAT")
+                    .default_value("dot")
+                    .value_parser(GraphFormat::ALL.map(GraphFormat::slug))
+                    .help("Output format"),
+            )
+            .arg(
+                Arg::new("order")
+                    .long("order")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("format")
+                    .help("Print the build order (dependency levels) and any cycles instead of a diagram"),
+            )
+            .arg(
+                Arg::new("root")
+                    .long("root")
+                    .value_name("HANDLE")
+                    .help("Limit the graph to this artifact's closure (spec://, impl://, or scratch:// handle)"),
+            ),
+    )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let format: GraphFormat = matches
+        .get_one::<String>("format")
+        .map(String::as_str)
+        .unwrap_or("dot")
+        .parse()?;
+    let view = dependencies::parse_view(matches)?;
+
+    let mut graph = session
+        .dependency_mapper
+        .dependency_graph()
+        .workspace_graph()?;
+
+    match matches.get_one::<String>("root") {
+        Some(raw) => {
+            let handle = ResourceHandle::parse(raw)?.ok_or_else(|| {
+                CliError::new(
+                    format!("--root expects a spec://, impl://, or scratch:// handle, got {raw}"),
+                    ExitStatus::Usage,
+                )
+            })?;
+            let direction = match view {
+                DependencyView::Downstream => Some(DependencyRelation::Downstream),
+                DependencyView::Upstream => Some(DependencyRelation::Upstream),
+                DependencyView::All => None,
+            };
+            graph = graph.closure(&handle.artifact_id(), direction)?;
+        }
+        None if ["downstream", "upstream", "all"]
+            .iter()
+            .any(|flag| matches.get_flag(flag)) =>
+        {
+            return Err(CliError::new(
+                "--downstream, --upstream, and --all require --root",
+                ExitStatus::Usage,
+            ));
+        }
+        None => {}
+    }
+
+    if matches.get_flag("order") {
+        return Ok(CommandResult::GraphOrder {
+            levels: graph.levels(),
+            cycles: graph.cycles(),
+        });
+    }
+
+    Ok(CommandResult::Graph {
+        format,
+        document: graph.render(format),
+    })
+}
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::ImpactReport;
+use specman::impact::artifact_for_path;
+
+use crate::commands::{CommandResult, artifact_locator};
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+
+/// Defines `specman impact`, which lists every artifact affected by changes to the given
+/// artifacts or to the files touched by a git diff range.
+pub fn command() -> Command {
+    Command::new("impact")
+        .about("List implementations and scratch pads affected by changed artifacts")
+        .arg(
+            Arg::new("locator")
+                .value_name("LOCATOR")
+                .action(ArgAction::Append)
+                .required_unless_present("git")
+                .help("Changed artifact: resource handle, workspace-relative path, or specification name"),
+        )
+        .arg(
+            Arg::new("git")
+                .long("git")
+                .value_name("RANGE")
+                .help("Treat artifacts with files changed in this git diff range (e.g. main...HEAD) as changed"),
+        )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let graph = session
+        .dependency_mapper
+        .dependency_graph()
+        .workspace_graph()?;
+
+    let mut changed = Vec::new();
+    for raw in matches.get_many::<String>("locator").into_iter().flatten() {
+        changed.push(artifact_locator(session, raw)?);
+    }
+    if let Some(range) = matches.get_one::<String>("git") {
+        // Artifacts deleted in the range are no longer in the graph and have nothing left to
+        // impact.
+        for path in git_changed_paths(session.workspace_paths.root(), range)? {
+            if let Some(id) = artifact_for_path(&session.workspace_paths, &path)
+                && graph.contains(&id)
+                && !changed.contains(&id)
+            {
+                changed.push(id);
+            }
+        }
+    }
+
+    let report = ImpactReport::analyze(&graph, &changed)?;
+    Ok(CommandResult::Impact { report })
+}
+
+fn git_changed_paths(root: &Path, range: &str) -> Result<Vec<PathBuf>, CliError> {
+    // A range such as `--output=file` would otherwise be parsed as a git option.
+    if range.starts_with('-') {
+        return Err(CliError::new(
+            format!("invalid git range {range}: ranges cannot start with '-'"),
+            ExitStatus::Usage,
+        ));
+    }
+    let output = process::Command::new("git")
+        .args(["diff", "--name-only", "--relative", range, "--"])
+        .current_dir(root)
+        .output()
+        .map_err(|err| CliError::new(format!("failed to run git: {err}"), ExitStatus::Io))?;
+    if !output.status.success() {
+        return Err(CliError::new(
+            format!(
+                "git diff {range} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            ExitStatus::Usage,
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
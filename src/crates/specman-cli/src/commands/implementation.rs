@@ -4,14 +4,16 @@ use std::path::{Path, PathBuf};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use serde::Serialize;
 use specman::dependency_tree::{
-    ArtifactId, ArtifactKind, ArtifactSummary, DependencyMapping, DependencyTree,
+    ArtifactId, ArtifactKind, ArtifactSummary, DependencyMapping, DependencyQuery, DependencyTree,
 };
 use specman::front_matter::{self, ImplementationFrontMatter, SpecificationFrontMatter};
 use specman::lifecycle::LifecycleController;
-use specman::template::{TemplateEngine, TokenMap};
+use specman::template::TemplateEngine;
 
-use crate::commands::CommandResult;
 use crate::commands::dependencies::{self, DependencyScope};
+use crate::commands::{
+    CommandResult, arguments_arg, declared_locator, dry_run_arg, template_tokens, warn_unaudited,
+};
 use crate::context::CliSession;
 use crate::error::{CliError, ExitStatus};
 use crate::frontmatter::update_impl_document;
@@ -105,11 +107,21 @@ fn create_impl(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             ExitStatus::Usage,
         ));
     }
-
-    let resolved = session.templates.descriptor(TemplateKind::Implementation)?;
+    let resolved_spec = declared_locator(session, &folder, &resolved_spec)?;
+
+    let resolved = session
+        .templates
+        .descriptor(TemplateKind::Implementation, matches.get_flag("dry-run"))?;
+    let tokens = template_tokens(
+        session,
+        matches,
+        ArtifactKind::Implementation,
+        &name,
+        vec![resolved_spec.clone()],
+    )?;
     let mut rendered = session
         .template_engine
-        .render(&resolved.descriptor, &TokenMap::new())
+        .render(&resolved.descriptor, &tokens)
         .map_err(CliError::from)?;
     rendered.provenance = Some(resolved.provenance);
     rendered.body =
@@ -119,10 +131,19 @@ fn create_impl(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
         kind: ArtifactKind::Implementation,
         name: name.clone(),
     };
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .persistence
+            .persist_dry_run(&artifact, &rendered)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
+
     let persisted = session
         .persistence
         .persist(&artifact, &rendered)
         .map_err(CliError::from)?;
+    warn_unaudited(persisted.audit_error.as_deref());
     session
         .record_dependency_tree(&artifact)
         .map_err(CliError::from)?;
@@ -139,6 +160,7 @@ fn delete_impl(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("implementation name required", ExitStatus::Usage))?;
+    DependencyQuery::ensure_mutable(&name).map_err(CliError::from)?;
     let forced = matches.get_flag("force");
     let artifact = ArtifactId {
         kind: ArtifactKind::Implementation,
@@ -166,6 +188,13 @@ fn delete_impl(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             ExitStatus::Data,
         ));
     }
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .lifecycle
+            .preview_deletion(artifact, Some(plan), session.persistence.as_ref(), forced)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
     let tree = plan.dependencies.clone();
 
     let removed = session
@@ -177,6 +206,7 @@ fn delete_impl(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             forced,
         )
         .map_err(CliError::from)?;
+    warn_unaudited(removed.audit_error.as_deref());
     let removed_path = util::workspace_relative(session.workspace_paths.root(), &removed.directory);
 
     let spec_locator = tree.root.metadata.get("spec").cloned();
@@ -233,6 +263,8 @@ fn new_command() -> Command {
                 .value_name("PATH")
                 .help("Relative path to the implementation code location"),
         )
+        .arg(arguments_arg())
+        .arg(dry_run_arg())
 }
 
 fn delete_command() -> Command {
@@ -250,6 +282,7 @@ fn delete_command() -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Override dependency blockers after printing the dependency tree."),
         )
+        .arg(dry_run_arg())
 }
 
 fn dependencies_command() -> Command {
@@ -260,7 +293,7 @@ fn dependencies_command() -> Command {
                 Arg::new("name")
                     .required(true)
                     .value_name("NAME")
-                    .help("Implementation slug (folder name)"),
+                    .help("Implementation slug, impl:// handle, or impl://{name}/dependencies"),
             ),
     )
 }
@@ -273,6 +306,7 @@ fn impl_dependencies(
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("implementation name required", ExitStatus::Usage))?;
+    let name = dependencies::target_name(&name, DependencyScope::Implementation)?;
     util::validate_slug(&name, "implementation")?;
 
     let impl_file = session
@@ -306,10 +340,9 @@ fn impl_dependencies(
 
 fn read_impl_summary(root: &Path, path: &Path) -> Result<ImplSummary, CliError> {
     let content = fs::read_to_string(path)?;
-    let split = front_matter::split_front_matter(&content)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
-    let fm: ImplementationFrontMatter = serde_yaml::from_str(split.yaml)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
+    let fm: ImplementationFrontMatter = front_matter::split_front_matter(&content)
+        .and_then(|split| split.deserialize())
+        .map_err(|err| err.in_file(path.display()))?;
     let language = fm
         .primary_language
         .as_ref()
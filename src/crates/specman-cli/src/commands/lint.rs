@@ -0,0 +1,36 @@
+use clap::{Arg, ArgMatches, Command};
+use specman::{Linter, Severity};
+
+use crate::commands::CommandResult;
+use crate::context::CliSession;
+use crate::error::CliError;
+
+/// Defines `specman lint`, which checks every artifact in the workspace against the SpecMan
+/// Data Model rules and cites the heading each violation breaks.
+pub fn command() -> Command {
+    Command::new("lint")
+        .about(
+            "Check specifications, implementations, and scratch pads against the data model rules",
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("text")
+                .value_parser(["text", "sarif"])
+                .help("Output format; sarif emits a SARIF 2.1.0 log for code-scanning tools"),
+        )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let linter = Linter::data_model();
+    let report = linter.run(&session.workspace_paths)?;
+
+    if matches.get_one::<String>("format").map(String::as_str) == Some("sarif") {
+        return Ok(CommandResult::LintSarif {
+            failed: report.count(Severity::Error) > 0,
+            log: linter.sarif(&report),
+        });
+    }
+    Ok(CommandResult::Lint { report })
+}
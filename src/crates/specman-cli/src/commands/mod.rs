@@ -1,14 +1,38 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use clap::{Arg, ArgAction, ArgMatches};
 use serde::Serialize;
-use specman::dependency_tree::DependencyTree;
+use specman::dependency_tree::{DependencyTree, validate_workspace_reference};
+use specman::impact::artifact_for_path;
+use specman::preview::MutationPreview;
+use specman::prompt_catalog::RenderedPrompt;
+use specman::template::TokenMap;
+use specman::{
+    ArtifactId, ArtifactKind, DependencyHop, ErrorExplanation, GraphFormat, ImpactReport,
+    LintReport, RemoteArtifact, ResourceHandle, Severity, TokenProvider, TokenRequest, VendorCheck,
+    VendorOutcome,
+};
 
-use crate::error::ExitStatus;
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+use crate::util;
 
 pub mod dependencies;
+pub mod explain;
+pub mod fetch;
+pub mod graph;
+pub mod impact;
 pub mod implementation;
+pub mod lint;
+pub mod prompt;
+pub mod schema;
 pub mod scratch;
 pub mod spec;
 pub mod status;
 pub mod templates;
+pub mod vendor;
+pub mod why;
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -65,6 +89,127 @@ pub enum CommandResult {
     TemplatePointer {
         report: templates::PointerReport,
     },
+    DryRun {
+        preview: MutationPreview,
+    },
+    Prompt {
+        prompt: RenderedPrompt,
+    },
+    Graph {
+        format: GraphFormat,
+        document: String,
+    },
+    GraphOrder {
+        levels: Vec<Vec<ArtifactId>>,
+        cycles: Vec<Vec<ArtifactId>>,
+    },
+    Impact {
+        report: ImpactReport,
+    },
+    Why {
+        from: ArtifactId,
+        to: ArtifactId,
+        paths: Vec<Vec<DependencyHop>>,
+    },
+    Fetch {
+        /// Workspace-relative cache directory.
+        cache: String,
+        artifacts: Vec<RemoteArtifact>,
+    },
+    Vendor {
+        outcome: VendorOutcome,
+    },
+    VendorCheck {
+        checks: Vec<VendorCheck>,
+    },
+    Lint {
+        report: LintReport,
+    },
+    LintSarif {
+        log: serde_json::Value,
+        #[serde(skip)]
+        failed: bool,
+    },
+    Explain {
+        codes: Vec<ErrorExplanation>,
+    },
+    Schema {
+        /// Schemas keyed by kind (`spec`, `impl`, `scratch`).
+        schemas: BTreeMap<String, serde_json::Value>,
+    },
+    SchemaWritten {
+        /// Workspace-relative paths of the written schema files.
+        paths: Vec<String>,
+    },
+}
+
+/// Shared `--dry-run` flag for lifecycle commands that would otherwise write or delete files.
+pub fn dry_run_arg() -> Arg {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .action(ArgAction::SetTrue)
+        .help("Preview the files that would change (with diffs) without touching the workspace.")
+}
+
+/// Shared `--arguments` option for create commands, surfaced to templates as `{{arguments}}`.
+pub fn arguments_arg() -> Arg {
+    Arg::new("arguments")
+        .long("arguments")
+        .value_name("TEXT")
+        .help("Free-form instructions passed verbatim to the template as {{arguments}}.")
+}
+
+/// Reports on stderr a change that landed but is missing from the audit log. The command
+/// still succeeds, since the workspace was already modified.
+pub fn warn_unaudited(audit_error: Option<&str>) {
+    if let Some(err) = audit_error {
+        eprintln!("warning: {err}");
+    }
+}
+
+/// Computes the Template Token Contract values for a create command.
+pub fn template_tokens(
+    session: &CliSession,
+    matches: &ArgMatches,
+    kind: ArtifactKind,
+    name: &str,
+    declared: Vec<String>,
+) -> Result<TokenMap, CliError> {
+    let mut request = TokenRequest::new(kind, name);
+    request.declared = declared;
+    if let Some(arguments) = matches.get_one::<String>("arguments") {
+        request.arguments = arguments.clone();
+    }
+    session.tokens.tokens(&request).map_err(CliError::from)
+}
+
+/// Rewrites a workspace-relative locator from the command line into the form the new artifact
+/// in `folder` declares, and checks that it resolves from there.
+pub fn declared_locator(
+    session: &CliSession,
+    folder: &Path,
+    locator: &str,
+) -> Result<String, CliError> {
+    let declared = util::document_relative(session.workspace_paths.root(), folder, locator);
+    validate_workspace_reference(&declared, folder, &session.workspace_paths)?;
+    Ok(declared)
+}
+
+/// Accepts resource handles, workspace-relative paths into an artifact folder, or bare
+/// specification names.
+pub fn artifact_locator(session: &CliSession, raw: &str) -> Result<ArtifactId, CliError> {
+    if let Some(handle) = ResourceHandle::parse(raw)? {
+        return Ok(handle.artifact_id());
+    }
+    if raw.contains('/') {
+        return artifact_for_path(&session.workspace_paths, Path::new(raw)).ok_or_else(|| {
+            CliError::new(
+                format!("{raw} is not inside a specification, implementation, or scratch pad"),
+                ExitStatus::Usage,
+            )
+        });
+    }
+    Ok(ResourceHandle::new(ArtifactKind::Specification, raw)?.artifact_id())
 }
 
 impl CommandResult {
@@ -86,6 +231,46 @@ impl CommandResult {
                     ExitStatus::Ok
                 }
             }
+            CommandResult::GraphOrder { cycles, .. } => {
+                if cycles.is_empty() {
+                    ExitStatus::Ok
+                } else {
+                    ExitStatus::Data
+                }
+            }
+            CommandResult::Fetch { artifacts, .. } => {
+                if artifacts.iter().all(|remote| remote.error.is_none()) {
+                    ExitStatus::Ok
+                } else {
+                    ExitStatus::TempFail
+                }
+            }
+            CommandResult::VendorCheck { checks } => {
+                if checks
+                    .iter()
+                    .any(|check| check.upstream_changed() || check.locally_modified())
+                {
+                    ExitStatus::Data
+                } else if checks.iter().any(|check| check.error.is_some()) {
+                    ExitStatus::TempFail
+                } else {
+                    ExitStatus::Ok
+                }
+            }
+            CommandResult::Lint { report } => {
+                if report.count(Severity::Error) > 0 {
+                    ExitStatus::Data
+                } else {
+                    ExitStatus::Ok
+                }
+            }
+            CommandResult::LintSarif { failed, .. } => {
+                if *failed {
+                    ExitStatus::Data
+                } else {
+                    ExitStatus::Ok
+                }
+            }
             _ => ExitStatus::Ok,
         }
     }
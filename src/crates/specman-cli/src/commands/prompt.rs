@@ -0,0 +1,103 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::{PromptCatalog, PromptKind, PromptRequest, ResourceHandle};
+
+use crate::commands::CommandResult;
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+
+/// Defines `specman prompt <kind>`, which renders a `templates/prompts` blueprint with
+/// context gathered from the workspace.
+pub fn command() -> Command {
+    Command::new("prompt")
+        .about("Render a ready-to-paste agent prompt for spec, impl, or scratch pad work")
+        .arg(
+            Arg::new("kind")
+                .value_name("KIND")
+                .help("Prompt to render")
+                .required(true)
+                .value_parser(PromptKind::ALL.map(PromptKind::slug)),
+        )
+        .arg(
+            Arg::new("target")
+                .long("target")
+                .value_name("HANDLE")
+                .help("Artifact the prompt works against (spec://, impl://, or a bare name); its dependencies fill {{context}}"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_name("NAME")
+                .help("Name of the artifact or scratch pad to create, if already decided"),
+        )
+        .arg(
+            Arg::new("branch")
+                .long("branch")
+                .value_name("BRANCH")
+                .help("Git branch to record instead of letting the agent derive one"),
+        )
+        .arg(
+            Arg::new("arguments")
+                .long("arguments")
+                .value_name("TEXT")
+                .help("User input inserted verbatim as {{arguments}}"),
+        )
+        .arg(
+            Arg::new("context")
+                .long("context")
+                .value_name("LOCATOR")
+                .action(ArgAction::Append)
+                .help("Additional path or URL to list under {{context}} (repeatable)"),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .value_name("KEY=VALUE")
+                .action(ArgAction::Append)
+                .help("Explicit value for any prompt token, e.g. objectives=... (repeatable)"),
+        )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let raw_kind = matches
+        .get_one::<String>("kind")
+        .ok_or_else(|| CliError::new("prompt kind is required", ExitStatus::Usage))?;
+    let kind: PromptKind = raw_kind.parse()?;
+
+    let mut request = PromptRequest::new(kind);
+    if let Some(raw) = matches.get_one::<String>("target") {
+        request.target = Some(parse_target(kind, raw)?);
+    }
+    request.name = matches.get_one::<String>("name").cloned();
+    request.branch = matches.get_one::<String>("branch").cloned();
+    if let Some(arguments) = matches.get_one::<String>("arguments") {
+        request.arguments = arguments.clone();
+    }
+    request.context = matches
+        .get_many::<String>("context")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+    for raw in matches.get_many::<String>("token").into_iter().flatten() {
+        let (key, value) = raw.split_once('=').ok_or_else(|| {
+            CliError::new(
+                format!("--token expects KEY=VALUE, got {raw}"),
+                ExitStatus::Usage,
+            )
+        })?;
+        request
+            .tokens
+            .insert(key.trim().to_string(), value.to_string());
+    }
+
+    let catalog = PromptCatalog::new(session.workspace_paths.clone());
+    let prompt = catalog.render(&request, session.dependency_mapper.as_ref())?;
+    Ok(CommandResult::Prompt { prompt })
+}
+
+/// Accepts full resource handles, or bare names interpreted as the kind the prompt targets.
+fn parse_target(kind: PromptKind, raw: &str) -> Result<ResourceHandle, CliError> {
+    let handle = match ResourceHandle::parse(raw)? {
+        Some(handle) => handle,
+        None => ResourceHandle::new(kind.target_kind(), raw)?,
+    };
+    Ok(handle)
+}
@@ -0,0 +1,65 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::front_matter::FrontMatterKind;
+use specman::schema::{SCHEMA_KINDS, front_matter_schema, schema_name, write_front_matter_schemas};
+
+use crate::commands::CommandResult;
+use crate::context::CliSession;
+use crate::error::CliError;
+
+/// Defines `specman schema`, which emits draft-07 JSON Schemas for artifact front matter so
+/// editors such as yaml-language-server can validate it while it is written.
+pub fn command() -> Command {
+    Command::new("schema")
+        .about("Emit JSON Schemas (draft-07) for specification, implementation, and scratch pad front matter")
+        .arg(
+            Arg::new("kind")
+                .value_name("KIND")
+                .default_value("all")
+                .value_parser(["spec", "impl", "scratch", "all"])
+                .help("Front matter type to emit"),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .action(ArgAction::SetTrue)
+                .help("Write {kind}.schema.json files under .specman/schemas/ instead of printing"),
+        )
+}
+
+/// Prints the schemas. Their content does not depend on the workspace, so this runs
+/// before a workspace is discovered.
+pub fn print(matches: &ArgMatches) -> CommandResult {
+    CommandResult::Schema {
+        schemas: kinds(matches)
+            .into_iter()
+            .map(|kind| (schema_name(kind).to_string(), front_matter_schema(kind)))
+            .collect(),
+    }
+}
+
+/// Handles `--write`, which needs the workspace's `.specman/` folder.
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    if !matches.get_flag("write") {
+        return Ok(print(matches));
+    }
+    let root = session.workspace_paths.root();
+    let paths = write_front_matter_schemas(&session.workspace_paths, &kinds(matches))?
+        .into_iter()
+        .map(|path| {
+            path.strip_prefix(root)
+                .unwrap_or(&path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    Ok(CommandResult::SchemaWritten { paths })
+}
+
+fn kinds(matches: &ArgMatches) -> Vec<FrontMatterKind> {
+    match matches.get_one::<String>("kind").map(String::as_str) {
+        Some("spec") => vec![FrontMatterKind::Specification],
+        Some("impl") => vec![FrontMatterKind::Implementation],
+        Some("scratch") => vec![FrontMatterKind::ScratchPad],
+        _ => SCHEMA_KINDS.to_vec(),
+    }
+}
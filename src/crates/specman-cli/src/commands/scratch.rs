@@ -3,13 +3,17 @@ use std::path::Path;
 
 use clap::{Arg, ArgAction, ArgMatches, Command, ValueEnum, builder::EnumValueParser};
 use serde::Serialize;
-use specman::dependency_tree::{ArtifactId, ArtifactKind, DependencyMapping, DependencyTree};
-use specman::front_matter::{self, ScratchFrontMatter};
+use specman::dependency_tree::{
+    ArtifactId, ArtifactKind, DependencyMapping, DependencyQuery, DependencyTree,
+    validate_scratch_target,
+};
+use specman::front_matter::{self, ScratchFrontMatter, ScratchWorkType};
 use specman::lifecycle::LifecycleController;
-use specman::template::{TemplateEngine, TokenMap};
+use specman::scratchpad;
+use specman::template::TemplateEngine;
 
-use crate::commands::CommandResult;
 use crate::commands::dependencies::{self, DependencyScope};
+use crate::commands::{CommandResult, arguments_arg, dry_run_arg, template_tokens, warn_unaudited};
 use crate::context::CliSession;
 use crate::error::{CliError, ExitStatus};
 use crate::frontmatter::update_scratch_document;
@@ -29,7 +33,9 @@ pub type DeletionTree = DependencyTree;
 
 #[derive(Clone, Debug, ValueEnum)]
 pub enum ScratchType {
+    Draft,
     Feat,
+    Fix,
     Ref,
     Revision,
 }
@@ -37,11 +43,24 @@ pub enum ScratchType {
 impl ScratchType {
     fn as_key(&self) -> &'static str {
         match self {
+            ScratchType::Draft => "draft",
             ScratchType::Feat => "feat",
+            ScratchType::Fix => "fix",
             ScratchType::Ref => "ref",
             ScratchType::Revision => "revision",
         }
     }
+
+    /// The work type `scratch new` writes: the kind alone, with no heading lists.
+    fn work_type(&self) -> ScratchWorkType {
+        match self {
+            ScratchType::Draft => ScratchWorkType::Draft(Default::default()),
+            ScratchType::Feat => ScratchWorkType::Feat(Default::default()),
+            ScratchType::Fix => ScratchWorkType::Fix(Default::default()),
+            ScratchType::Ref => ScratchWorkType::Refactor(Default::default()),
+            ScratchType::Revision => ScratchWorkType::Revision(Default::default()),
+        }
+    }
 }
 
 pub fn command() -> Command {
@@ -112,16 +131,33 @@ fn create_scratchpad(
         .get_one::<ScratchType>("type")
         .expect("clap ensures required option");
     let work_key = work_type.as_key();
+    let folder = session.workspace_paths.scratchpad_dir().join(&name);
+    validate_scratch_target(&target, &folder, &session.workspace_paths)?;
+    scratchpad::validate_work_type(
+        &work_type.work_type(),
+        &target,
+        &folder,
+        &session.workspace_paths,
+    )?;
 
     let branch = matches
         .get_one::<String>("branch")
         .cloned()
         .unwrap_or_else(|| default_branch(&target, work_key, &name));
 
-    let resolved = session.templates.descriptor(TemplateKind::Scratch)?;
+    let resolved = session
+        .templates
+        .descriptor(TemplateKind::Scratch, matches.get_flag("dry-run"))?;
+    let tokens = template_tokens(
+        session,
+        matches,
+        ArtifactKind::ScratchPad,
+        &name,
+        vec![target.clone()],
+    )?;
     let mut rendered = session
         .template_engine
-        .render(&resolved.descriptor, &TokenMap::new())
+        .render(&resolved.descriptor, &tokens)
         .map_err(CliError::from)?;
     rendered.provenance = Some(resolved.provenance);
     rendered.body = update_scratch_document(&rendered.body, &target, &branch, work_key)?;
@@ -130,10 +166,19 @@ fn create_scratchpad(
         kind: ArtifactKind::ScratchPad,
         name: name.clone(),
     };
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .persistence
+            .persist_dry_run(&artifact, &rendered)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
+
     let persisted = session
         .persistence
         .persist(&artifact, &rendered)
         .map_err(CliError::from)?;
+    warn_unaudited(persisted.audit_error.as_deref());
     session
         .record_dependency_tree(&artifact)
         .map_err(CliError::from)?;
@@ -153,6 +198,7 @@ fn delete_scratchpad(
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("scratch pad name required", ExitStatus::Usage))?;
+    DependencyQuery::ensure_mutable(&name).map_err(CliError::from)?;
     let forced = matches.get_flag("force");
     let artifact = ArtifactId {
         kind: ArtifactKind::ScratchPad,
@@ -181,6 +227,13 @@ fn delete_scratchpad(
             ExitStatus::Data,
         ));
     }
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .lifecycle
+            .preview_deletion(artifact, Some(plan), session.persistence.as_ref(), forced)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
     let tree = plan.dependencies.clone();
 
     let scratch_file = folder.join("scratch.md");
@@ -205,6 +258,7 @@ fn delete_scratchpad(
             forced,
         )
         .map_err(CliError::from)?;
+    warn_unaudited(removed.audit_error.as_deref());
     let removed_path = util::workspace_relative(session.workspace_paths.root(), &removed.directory);
 
     Ok(CommandResult::ScratchDeleted {
@@ -240,7 +294,7 @@ fn new_command() -> Command {
             Arg::new("type")
                 .required(true)
                 .long("type")
-                .value_name("feat|ref|revision")
+                .value_name("draft|feat|fix|ref|revision")
                 .value_parser(EnumValueParser::<ScratchType>::new())
                 .help("Scratch pad work type"),
         )
@@ -250,6 +304,8 @@ fn new_command() -> Command {
                 .value_name("BRANCH")
                 .help("Override the default {target}/{type}/{name} branch naming scheme"),
         )
+        .arg(arguments_arg())
+        .arg(dry_run_arg())
 }
 
 fn delete_command() -> Command {
@@ -267,6 +323,7 @@ fn delete_command() -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Override dependency blockers after printing the dependency tree."),
         )
+        .arg(dry_run_arg())
 }
 
 fn dependencies_command() -> Command {
@@ -277,7 +334,7 @@ fn dependencies_command() -> Command {
                 Arg::new("name")
                     .required(true)
                     .value_name("NAME")
-                    .help("Scratch pad slug (folder name)"),
+                    .help("Scratch pad slug, scratch:// handle, or scratch://{name}/dependencies"),
             ),
     )
 }
@@ -290,6 +347,7 @@ fn scratch_dependencies(
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("scratch pad name required", ExitStatus::Usage))?;
+    let name = dependencies::target_name(&name, DependencyScope::ScratchPad)?;
     util::validate_slug(&name, "scratch pad")?;
 
     let scratch_file = session
@@ -323,10 +381,9 @@ fn scratch_dependencies(
 
 fn read_scratch_summary(root: &Path, path: &Path) -> Result<ScratchSummary, CliError> {
     let content = fs::read_to_string(path)?;
-    let split = front_matter::split_front_matter(&content)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
-    let fm: ScratchFrontMatter = serde_yaml::from_str(split.yaml)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
+    let fm: ScratchFrontMatter = front_matter::split_front_matter(&content)
+        .and_then(|split| split.deserialize())
+        .map_err(|err| err.in_file(path.display()))?;
     let branch = fm.branch.clone();
     let work_type = fm
         .work_type
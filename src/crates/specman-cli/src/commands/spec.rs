@@ -4,14 +4,16 @@ use std::path::Path;
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use serde::Serialize;
 use specman::dependency_tree::{
-    ArtifactId, ArtifactKind, ArtifactSummary, DependencyMapping, DependencyTree,
+    ArtifactId, ArtifactKind, ArtifactSummary, DependencyMapping, DependencyQuery, DependencyTree,
 };
 use specman::front_matter::{self, SpecificationFrontMatter};
 use specman::lifecycle::LifecycleController;
-use specman::template::{TemplateEngine, TokenMap};
+use specman::template::TemplateEngine;
 
-use crate::commands::CommandResult;
 use crate::commands::dependencies::{self, DependencyScope};
+use crate::commands::{
+    CommandResult, arguments_arg, declared_locator, dry_run_arg, template_tokens, warn_unaudited,
+};
 use crate::context::CliSession;
 use crate::error::{CliError, ExitStatus};
 use crate::frontmatter::update_spec_document;
@@ -88,11 +90,24 @@ fn create_spec(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             ExitStatus::Usage,
         ));
     }
+    let dependencies = dependencies
+        .iter()
+        .map(|dep| declared_locator(session, &folder, dep))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let resolved = session.templates.descriptor(TemplateKind::Specification)?;
+    let resolved = session
+        .templates
+        .descriptor(TemplateKind::Specification, matches.get_flag("dry-run"))?;
+    let tokens = template_tokens(
+        session,
+        matches,
+        ArtifactKind::Specification,
+        &name,
+        dependencies.clone(),
+    )?;
     let mut rendered = session
         .template_engine
-        .render(&resolved.descriptor, &TokenMap::new())
+        .render(&resolved.descriptor, &tokens)
         .map_err(CliError::from)?;
     rendered.provenance = Some(resolved.provenance);
     rendered.body = update_spec_document(&rendered.body, &name, &version, &dependencies)?;
@@ -101,10 +116,19 @@ fn create_spec(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
         kind: ArtifactKind::Specification,
         name: name.clone(),
     };
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .persistence
+            .persist_dry_run(&artifact, &rendered)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
+
     let persisted = session
         .persistence
         .persist(&artifact, &rendered)
         .map_err(CliError::from)?;
+    warn_unaudited(persisted.audit_error.as_deref());
     session
         .record_dependency_tree(&artifact)
         .map_err(CliError::from)?;
@@ -121,6 +145,7 @@ fn delete_spec(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("spec name required", ExitStatus::Usage))?;
+    DependencyQuery::ensure_mutable(&name).map_err(CliError::from)?;
     let forced = matches.get_flag("force");
     let artifact = ArtifactId {
         kind: ArtifactKind::Specification,
@@ -149,6 +174,13 @@ fn delete_spec(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             ExitStatus::Data,
         ));
     }
+    if matches.get_flag("dry-run") {
+        let preview = session
+            .lifecycle
+            .preview_deletion(artifact, Some(plan), session.persistence.as_ref(), forced)
+            .map_err(CliError::from)?;
+        return Ok(CommandResult::DryRun { preview });
+    }
     let tree = plan.dependencies.clone();
 
     let removed = session
@@ -160,6 +192,7 @@ fn delete_spec(session: &CliSession, matches: &ArgMatches) -> Result<CommandResu
             forced,
         )
         .map_err(CliError::from)?;
+    warn_unaudited(removed.audit_error.as_deref());
     let removed_path = util::workspace_relative(session.workspace_paths.root(), &removed.directory);
 
     let summary = SpecSummary {
@@ -203,6 +236,8 @@ fn spec_new_command() -> Command {
                 .default_value("1.0.0")
                 .help("Version recorded in the front matter."),
         )
+        .arg(arguments_arg())
+        .arg(dry_run_arg())
 }
 
 fn spec_delete_command() -> Command {
@@ -220,6 +255,7 @@ fn spec_delete_command() -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Override dependency blockers after printing the dependency tree."),
         )
+        .arg(dry_run_arg())
 }
 
 fn spec_dependencies_command() -> Command {
@@ -230,7 +266,7 @@ fn spec_dependencies_command() -> Command {
                 Arg::new("name")
                     .required(true)
                     .value_name("NAME")
-                    .help("Specification slug (folder name)"),
+                    .help("Specification slug, spec:// handle, or spec://{name}/dependencies"),
             ),
     )
 }
@@ -243,6 +279,7 @@ fn spec_dependencies(
         .get_one::<String>("name")
         .cloned()
         .ok_or_else(|| CliError::new("spec name required", ExitStatus::Usage))?;
+    let name = dependencies::target_name(&name, DependencyScope::Specification)?;
     util::validate_slug(&name, "specification")?;
 
     let spec_file = session
@@ -291,10 +328,9 @@ fn parse_dependencies(raw: Option<&String>) -> Result<Vec<String>, CliError> {
 
 fn read_spec_summary(path: &Path) -> Result<SpecSummary, CliError> {
     let content = fs::read_to_string(path)?;
-    let split = front_matter::split_front_matter(&content)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
-    let fm: SpecificationFrontMatter = serde_yaml::from_str(split.yaml)
-        .map_err(|err| CliError::new(err.to_string(), ExitStatus::Config))?;
+    let fm: SpecificationFrontMatter = front_matter::split_front_matter(&content)
+        .and_then(|split| split.deserialize())
+        .map_err(|err| err.in_file(path.display()))?;
     let name = fm
         .identity
         .name
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use clap::Command;
 use serde::Serialize;
+use specman::SpecmanError;
 use specman::dependency_tree::{ArtifactId, ArtifactKind, DependencyMapping};
 
 use crate::commands::CommandResult;
@@ -17,6 +18,9 @@ pub struct StatusReport {
     pub path: String,
     pub ok: bool,
     pub message: Option<String>,
+    /// Ways to break a dependency cycle that stopped this artifact's tree from building.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remediation: Vec<String>,
 }
 
 pub fn command() -> Command {
@@ -29,13 +33,31 @@ pub fn run(session: &CliSession, _matches: &clap::ArgMatches) -> Result<CommandR
     for artifact in collect_artifacts(session) {
         let path = artifact_path(&session.workspace_paths, &artifact);
         match session.dependency_mapper.dependency_tree(&artifact) {
-            Ok(_) => reports.push(StatusReport {
-                name: artifact.name.clone(),
-                kind: artifact_kind(&artifact),
-                path,
-                ok: true,
-                message: None,
-            }),
+            Ok(tree) => {
+                // Only requirements this artifact declares count against it; an unsatisfied
+                // optional requirement is reported without failing the run.
+                let own: Vec<_> = tree
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.artifact == artifact)
+                    .collect();
+                let ok = own.iter().all(|diagnostic| diagnostic.optional);
+                healthy &= ok;
+                let message = (!own.is_empty()).then(|| {
+                    own.iter()
+                        .map(|diagnostic| diagnostic.message.as_str())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                });
+                reports.push(StatusReport {
+                    name: artifact.name.clone(),
+                    kind: artifact_kind(&artifact),
+                    path,
+                    ok,
+                    message,
+                    remediation: Vec::new(),
+                });
+            }
             Err(err) => {
                 healthy = false;
                 reports.push(StatusReport {
@@ -44,6 +66,10 @@ pub fn run(session: &CliSession, _matches: &clap::ArgMatches) -> Result<CommandR
                     path,
                     ok: false,
                     message: Some(err.to_string()),
+                    remediation: match &err {
+                        SpecmanError::DependencyCycle(cycle) => cycle.suggestions(),
+                        _ => Vec::new(),
+                    },
                 });
             }
         }
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::{CachingContentFetcher, FetchMode, FilesystemWorkspaceLocator, Vendorer};
+
+use crate::commands::{CommandResult, warn_unaudited};
+use crate::context::CliSession;
+use crate::error::{CliError, ExitStatus};
+
+/// Defines `specman vendor`, which copies a remote specification into `spec/` with a
+/// provenance record, or checks vendored copies against their upstream sources.
+pub fn command() -> Command {
+    Command::new("vendor")
+        .about("Copy a remote specification into spec/ and point references at the local copy")
+        .arg(
+            Arg::new("url")
+                .value_name("URL")
+                .required_unless_present("check")
+                .conflicts_with("check")
+                .help("HTTPS URL of the specification to vendor"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .value_name("NAME")
+                .conflicts_with("check")
+                .help("Specification name to vendor under; defaults to the document's front matter name"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .action(ArgAction::SetTrue)
+                .help("Report vendored specifications whose upstream or local content no longer matches the recorded hash"),
+        )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let check = matches.get_flag("check");
+    if check && session.fetch_mode == FetchMode::Offline {
+        return Err(CliError::new(
+            "specman vendor --check needs network access; drop --offline",
+            ExitStatus::Usage,
+        ));
+    }
+
+    // Vendoring offline copies the cached document; otherwise always go to the origin so the
+    // recorded hash matches what upstream serves today.
+    let mode = match session.fetch_mode {
+        FetchMode::Offline => FetchMode::Offline,
+        _ => FetchMode::Refresh,
+    };
+    let vendorer = Vendorer::new(
+        Arc::new(FilesystemWorkspaceLocator::new(
+            session.workspace_paths.root(),
+        )),
+        Arc::new(CachingContentFetcher::new(&session.workspace_paths, mode)),
+    );
+
+    if check {
+        return Ok(CommandResult::VendorCheck {
+            checks: vendorer.check()?,
+        });
+    }
+
+    let url = matches
+        .get_one::<String>("url")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let name = matches.get_one::<String>("name").map(String::as_str);
+    let outcome = vendorer.vendor(url, name)?;
+    warn_unaudited(outcome.audit_error.as_deref());
+    Ok(CommandResult::Vendor { outcome })
+}
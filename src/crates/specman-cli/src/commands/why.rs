@@ -0,0 +1,51 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use specman::PathSelection;
+
+use crate::commands::{CommandResult, artifact_locator};
+use crate::context::CliSession;
+use crate::error::CliError;
+
+/// Defines `specman why <from> <to>`, which explains how one artifact comes to depend on
+/// another.
+pub fn command() -> Command {
+    Command::new("why")
+        .about("Show the dependency paths that make one artifact depend on another")
+        .arg(Arg::new("from").value_name("FROM").required(true).help(
+            "Dependent artifact: resource handle, workspace-relative path, or specification name",
+        ))
+        .arg(
+            Arg::new("to")
+                .value_name("TO")
+                .required(true)
+                .help("Dependency to explain; pass FROM again to list the cycles through it"),
+        )
+        .arg(
+            Arg::new("shortest")
+                .long("shortest")
+                .action(ArgAction::SetTrue)
+                .help("Print only one shortest path instead of every simple path"),
+        )
+}
+
+pub fn run(session: &CliSession, matches: &ArgMatches) -> Result<CommandResult, CliError> {
+    let from = artifact_locator(session, required(matches, "from"))?;
+    let to = artifact_locator(session, required(matches, "to"))?;
+    let selection = if matches.get_flag("shortest") {
+        PathSelection::Shortest
+    } else {
+        PathSelection::All
+    };
+
+    let paths = session
+        .dependency_mapper
+        .dependency_graph()
+        .paths_between(&from, &to, selection)?;
+    Ok(CommandResult::Why { from, to, paths })
+}
+
+fn required<'a>(matches: &'a ArgMatches, name: &str) -> &'a str {
+    matches
+        .get_one::<String>(name)
+        .map(String::as_str)
+        .unwrap_or_default()
+}
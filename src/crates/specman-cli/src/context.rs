@@ -5,10 +5,14 @@ use specman::lifecycle::DefaultLifecycleController;
 use specman::persistence::WorkspacePersistence;
 use specman::template::MarkdownTemplateEngine;
 use specman::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator, WorkspacePaths};
-use specman::{DataModelAdapter, InMemoryAdapter, SpecmanError};
+use specman::{
+    CachingContentFetcher, ContractTokenProvider, DataModelAdapter, FetchMode, InMemoryAdapter,
+    SpecmanError,
+};
 use std::sync::Arc;
 
 use crate::error::CliError;
+use crate::progress::ProgressLine;
 use crate::templates::TemplateCatalog;
 use crate::util::Verbosity;
 
@@ -23,6 +27,8 @@ pub struct CliSession {
     pub persistence: Arc<WorkspacePersistence<Arc<FilesystemWorkspaceLocator>>>,
     pub template_engine: Arc<MarkdownTemplateEngine>,
     pub templates: TemplateCatalog,
+    /// Template Token Contract values for create commands.
+    pub tokens: ContractTokenProvider<Arc<FilesystemWorkspaceLocator>>,
     pub lifecycle: Arc<
         DefaultLifecycleController<
             Arc<FilesystemDependencyMapper<Arc<FilesystemWorkspaceLocator>>>,
@@ -30,16 +36,22 @@ pub struct CliSession {
         >,
     >, // Centralized lifecycle guard rails shared across commands.
     pub verbosity: Verbosity,
+    /// How HTTPS dependencies are fetched; `--offline` restricts them to the remote cache.
+    pub fetch_mode: FetchMode,
+    /// Traversal progress renderer, present only in `--verbose` mode.
+    pub progress: Option<Arc<ProgressLine>>,
 }
 
 impl CliSession {
     /// Creates a new session by resolving the workspace root (optionally honoring
     /// `--workspace`), instantiating the default adapter stack, and wiring lifecycle
     /// automation so downstream commands can satisfy the Workspace Context Resolution
-    /// and Data Model Activation concepts.
+    /// and Data Model Activation concepts. HTTPS dependencies go through the workspace's
+    /// remote cache in `fetch_mode`.
     pub fn bootstrap(
         workspace_override: Option<String>,
         verbosity: Verbosity,
+        fetch_mode: FetchMode,
     ) -> Result<Self, CliError> {
         let locator = match workspace_override {
             Some(path) => {
@@ -52,8 +64,16 @@ impl CliSession {
 
         let workspace_locator = Arc::new(locator);
         let workspace_paths = workspace_locator.workspace()?;
-        let dependency_mapper =
-            Arc::new(FilesystemDependencyMapper::new(workspace_locator.clone()));
+        let dependency_mapper = Arc::new(FilesystemDependencyMapper::with_fetcher(
+            workspace_locator.clone(),
+            Arc::new(CachingContentFetcher::new(&workspace_paths, fetch_mode)),
+        ));
+        let progress = verbosity.verbose.then(|| Arc::new(ProgressLine::stderr()));
+        if let Some(progress) = &progress {
+            dependency_mapper
+                .dependency_graph()
+                .set_progress_observer(progress.clone());
+        }
         let data_adapter: Arc<dyn DataModelAdapter> = Arc::new(InMemoryAdapter::new());
         let persistence = Arc::new(WorkspacePersistence::with_inventory_and_adapter(
             workspace_locator.clone(),
@@ -62,6 +82,7 @@ impl CliSession {
         ));
         let template_engine = Arc::new(MarkdownTemplateEngine::default());
         let templates = TemplateCatalog::new(workspace_paths.clone());
+        let tokens = ContractTokenProvider::new(dependency_mapper.graph_handle());
         let lifecycle = Arc::new(DefaultLifecycleController::new(
             dependency_mapper.clone(),
             template_engine.clone(),
@@ -73,8 +94,11 @@ impl CliSession {
             persistence,
             template_engine,
             templates,
+            tokens,
             lifecycle,
             verbosity,
+            fetch_mode,
+            progress,
         })
     }
 
@@ -84,4 +108,11 @@ impl CliSession {
         self.persistence.save_dependency_tree(artifact, &tree)?;
         Ok(())
     }
+
+    /// Ends the verbose progress line before results or errors are printed.
+    pub fn finish_progress(&self) {
+        if let Some(progress) = &self.progress {
+            progress.finish();
+        }
+    }
 }
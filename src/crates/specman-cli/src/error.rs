@@ -2,13 +2,16 @@ use std::fmt;
 use std::process::ExitCode;
 
 use clap::error::ErrorKind as ClapErrorKind;
-use specman::SpecmanError;
+use serde::Serialize;
+use specman::front_matter::FrontMatterError;
+use specman::{ErrorCode, ErrorDetails, SpecmanError};
 
 const EX_OK: u8 = 0;
 const EX_USAGE: u8 = 64;
 const EX_DATAERR: u8 = 65;
 const EX_SOFTWARE: u8 = 70;
 const EX_OSERR: u8 = 71;
+const EX_TEMPFAIL: u8 = 75;
 const EX_CONFIG: u8 = 78;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +20,7 @@ pub enum ExitStatus {
     Usage,
     Data,
     Io,
+    TempFail,
     Config,
     Software,
 }
@@ -28,6 +32,7 @@ impl ExitStatus {
             ExitStatus::Usage => EX_USAGE,
             ExitStatus::Data => EX_DATAERR,
             ExitStatus::Io => EX_OSERR,
+            ExitStatus::TempFail => EX_TEMPFAIL,
             ExitStatus::Config => EX_CONFIG,
             ExitStatus::Software => EX_SOFTWARE,
         }
@@ -38,6 +43,20 @@ impl ExitStatus {
 pub struct CliError {
     message: String,
     status: ExitStatus,
+    details: Option<Box<ErrorDetails>>,
+    span: Option<Box<ErrorSpan>>,
+    json: bool,
+}
+
+/// Where in a document an error was found, as reported in `--json` mode.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ErrorSpan {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
 }
 
 impl CliError {
@@ -45,29 +64,122 @@ impl CliError {
         Self {
             message: message.into(),
             status,
+            details: None,
+            span: None,
+            json: false,
         }
     }
 
+    /// Prints the error as a JSON object on stdout instead of text on stderr.
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    pub fn span(&self) -> Option<&ErrorSpan> {
+        self.span.as_deref()
+    }
+
+    /// Code and structured payload of the library error this came from, if any.
+    pub fn details(&self) -> Option<&ErrorDetails> {
+        self.details.as_deref()
+    }
+
     pub fn exit_code(&self) -> ExitCode {
         ExitCode::from(self.status.code())
     }
 
     pub fn print(&self) {
-        if !self.message.is_empty() {
+        if self.message.is_empty() {
+            return;
+        }
+        if self.json {
+            let mut payload = serde_json::json!({
+                "type": "error",
+                "message": self.message,
+                "exit_code": self.status.code(),
+                "span": self.span,
+            });
+            if let Some(details) = &self.details {
+                payload["code"] = serde_json::json!(details.code);
+                for (key, value) in [
+                    ("locator", serde_json::json!(details.locator)),
+                    ("artifact", serde_json::json!(details.artifact)),
+                    ("heading", serde_json::json!(details.heading)),
+                ] {
+                    if !value.is_null() {
+                        payload[key] = value;
+                    }
+                }
+            }
+            println!("{payload:#}");
+        } else {
             eprintln!("{}", self.message);
         }
     }
 }
 
+/// Exit status for each error code, following `sysexits`: bad artifact content is
+/// `EX_DATAERR`, bad locators and workspace setup are `EX_USAGE`.
+pub fn exit_status_for(code: ErrorCode) -> ExitStatus {
+    match code {
+        ErrorCode::DependencyCycle
+        | ErrorCode::DependencyUnresolved
+        | ErrorCode::FrontMatterInvalid
+        | ErrorCode::ScratchTarget
+        | ErrorCode::TemplateMissingToken
+        | ErrorCode::TemplateInvalid => ExitStatus::Data,
+        ErrorCode::WorkspaceNotFound
+        | ErrorCode::WorkspaceEscape
+        | ErrorCode::WorkspaceInvalid
+        | ErrorCode::ReadOnlyLocator => ExitStatus::Usage,
+        ErrorCode::Serialization => ExitStatus::Software,
+        ErrorCode::LockBusy => ExitStatus::TempFail,
+        ErrorCode::Io => ExitStatus::Io,
+    }
+}
+
+/// Renders the excerpt of a front matter error with line numbers and a caret under the
+/// reported column.
+fn code_frame(err: &FrontMatterError) -> Option<String> {
+    let last = err.excerpt.last()?;
+    let width = last.line.to_string().len();
+    let mut frame = format!("{:width$} |", "");
+    for source in &err.excerpt {
+        frame.push_str(&format!("\n{:>width$} | {}", source.line, source.text));
+        if Some(source.line) == err.line {
+            let column = err.column.unwrap_or(1).max(1);
+            frame.push_str(&format!("\n{:width$} | {:>column$}", "", "^"));
+        }
+    }
+    Some(frame)
+}
+
 impl From<SpecmanError> for CliError {
     fn from(err: SpecmanError) -> Self {
-        let status = match err {
-            SpecmanError::Template(_) | SpecmanError::Dependency(_) => ExitStatus::Data,
-            SpecmanError::Workspace(_) => ExitStatus::Usage,
-            SpecmanError::Serialization(_) => ExitStatus::Software,
-            SpecmanError::Io(_) => ExitStatus::Io,
-        };
-        CliError::new(err.to_string(), status)
+        let details = err.details();
+        let status = exit_status_for(details.code);
+        let mut message = format!("error[{}]: {err}", details.code);
+        if let SpecmanError::DependencyCycle(cycle) = &err {
+            message.push_str("\nTo break the cycle, change one of these entries:");
+            for suggestion in cycle.suggestions() {
+                message.push_str(&format!("\n  - {suggestion}"));
+            }
+        }
+        let mut error = CliError::new(message, status);
+        error.details = Some(Box::new(details));
+        if let SpecmanError::FrontMatter(front) = &err {
+            if let Some(frame) = code_frame(front) {
+                error.message.push('\n');
+                error.message.push_str(&frame);
+            }
+            error.span = Some(Box::new(ErrorSpan {
+                file: front.file.clone(),
+                line: front.line,
+                column: front.column,
+            }));
+        }
+        error
     }
 }
 
@@ -1,8 +1,12 @@
-use std::collections::BTreeMap;
 use std::process::ExitCode;
 
 use serde_json::json;
-use specman::dependency_tree::{ArtifactKind, ArtifactSummary, DependencyEdge, DependencyTree};
+use specman::dependency_tree::{
+    ArtifactId, ArtifactKind, ArtifactSummary, DependencyNode, DependencyRelation, DependencyTree,
+};
+use specman::impact::{ImpactReach, ImpactReport};
+use specman::lint::Severity;
+use specman::preview::{FileAction, MutationPreview};
 use specman::template::TemplateLocator;
 
 use crate::commands::CommandResult;
@@ -36,10 +40,18 @@ fn print_text(result: &CommandResult) {
             }
             for report in reports {
                 if report.ok {
-                    println!("  [OK] {} ({})", report.name, report.kind);
+                    match &report.message {
+                        Some(message) => {
+                            println!("  [OK] {} ({}): {message}", report.name, report.kind)
+                        }
+                        None => println!("  [OK] {} ({})", report.name, report.kind),
+                    }
                 } else if let Some(message) = &report.message {
                     println!("  [ERR] {} ({}): {message}", report.name, report.kind);
                 }
+                for suggestion in &report.remediation {
+                    println!("      fix: {suggestion}");
+                }
             }
         }
         CommandResult::SpecList { specs } => {
@@ -186,6 +198,176 @@ fn print_text(result: &CommandResult) {
                 println!("  Last-Modified: {}", last_modified);
             }
         }
+        CommandResult::DryRun { preview } => print_preview(preview),
+        // Prompts print bare so the output can be pasted straight into an agent.
+        CommandResult::Prompt { prompt } => print!("{}", prompt.body),
+        CommandResult::Graph { document, .. } => print!("{}", document),
+        CommandResult::Impact { report } => print_impact(report),
+        CommandResult::Why { from, to, paths } => {
+            if paths.is_empty() {
+                println!("{} does not depend on {}", id_label(from), id_label(to));
+            } else {
+                println!(
+                    "{} path(s) from {} to {}:",
+                    paths.len(),
+                    id_label(from),
+                    id_label(to)
+                );
+                for (index, path) in paths.iter().enumerate() {
+                    let mut line = id_label(from);
+                    for hop in path {
+                        let optional = if hop.optional { ", optional" } else { "" };
+                        line.push_str(&format!(
+                            " --[{}{}]--> {}",
+                            hop.field,
+                            optional,
+                            id_label(&hop.to)
+                        ));
+                    }
+                    println!("  {}. {}", index + 1, line);
+                }
+            }
+        }
+        CommandResult::Fetch { cache, artifacts } => {
+            let cached = artifacts
+                .iter()
+                .filter(|remote| remote.error.is_none())
+                .count();
+            println!(
+                "Cached {} of {} remote document(s) in {}",
+                cached,
+                artifacts.len(),
+                cache
+            );
+            for remote in artifacts {
+                match (&remote.artifact, &remote.error) {
+                    (_, Some(error)) => println!("  [ERR] {}: {}", remote.url, error),
+                    (Some(id), None) => println!("  [OK] {} ({})", remote.url, id_label(id)),
+                    (None, None) => println!("  [OK] {}", remote.url),
+                }
+            }
+        }
+        CommandResult::Vendor { outcome } => {
+            let vendored = &outcome.vendored;
+            println!(
+                "Vendored {} as {} (sha256 {})",
+                vendored.source, vendored.path, vendored.sha256
+            );
+            for id in &outcome.rewritten {
+                println!("  Repointed {}", id_label(id));
+            }
+        }
+        CommandResult::VendorCheck { checks } => {
+            println!("Checked {} vendored specification(s)", checks.len());
+            for check in checks {
+                let label = id_label(&check.vendored.artifact);
+                if check.locally_modified() {
+                    println!(
+                        "  [MODIFIED] {}: {} no longer matches the vendored hash",
+                        label, check.vendored.path
+                    );
+                }
+                if check.upstream_changed() {
+                    println!(
+                        "  [DRIFT] {}: {} changed since {}",
+                        label, check.vendored.source, check.vendored.fetched_at
+                    );
+                } else if let Some(error) = &check.error {
+                    println!("  [ERR] {}: {}", label, error);
+                } else if !check.locally_modified() {
+                    println!("  [OK] {}: matches {}", label, check.vendored.source);
+                }
+            }
+        }
+        CommandResult::Lint { report } => {
+            println!(
+                "Linted {} artifact(s): {} error(s), {} warning(s)",
+                report.artifacts,
+                report.count(Severity::Error),
+                report.count(Severity::Warning)
+            );
+            for diagnostic in &report.diagnostics {
+                let label = match diagnostic.severity {
+                    Severity::Error => "ERROR",
+                    Severity::Warning => "WARN",
+                };
+                let location = match (diagnostic.line, diagnostic.column) {
+                    (Some(line), Some(column)) => format!("{}:{line}:{column}", diagnostic.path),
+                    (Some(line), None) => format!("{}:{line}", diagnostic.path),
+                    _ => diagnostic.path.clone(),
+                };
+                println!(
+                    "  [{}] {} {}: {}",
+                    label, location, diagnostic.rule, diagnostic.message
+                );
+                println!("      see {}", diagnostic.heading);
+            }
+        }
+        // SARIF consumers read the log itself, so it prints unwrapped.
+        CommandResult::LintSarif { log, .. } => println!("{:#}", log),
+        CommandResult::Schema { schemas } => {
+            // A single schema prints bare so it can be redirected straight into a file.
+            if let [(_, schema)] = schemas.iter().collect::<Vec<_>>().as_slice() {
+                println!("{:#}", schema);
+            } else {
+                println!("{:#}", serde_json::json!(schemas));
+            }
+        }
+        CommandResult::SchemaWritten { paths } => {
+            println!("Wrote {} schema(s):", paths.len());
+            for path in paths {
+                println!("  {path}");
+            }
+        }
+        CommandResult::Explain { codes } => {
+            for (index, explanation) in codes.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                println!("{}: {}", explanation.code, explanation.summary);
+                println!("  fix: {}", explanation.remediation);
+                if let Some(heading) = &explanation.heading {
+                    println!("  see {heading}");
+                }
+            }
+        }
+        CommandResult::GraphOrder { levels, cycles } => {
+            println!("Build order ({} level(s)):", levels.len());
+            for (depth, level) in levels.iter().enumerate() {
+                let members: Vec<String> = level.iter().map(id_label).collect();
+                println!("  Level {}: {}", depth, members.join(", "));
+            }
+            if !cycles.is_empty() {
+                println!("Cycles ({}):", cycles.len());
+                for cycle in cycles {
+                    let members: Vec<String> = cycle.iter().map(id_label).collect();
+                    println!("  {}", members.join(" <-> "));
+                }
+            }
+        }
+    }
+}
+
+fn print_preview(preview: &MutationPreview) {
+    println!(
+        "Dry run for {} '{}' ({} file(s); workspace unchanged)",
+        kind_label(preview.artifact.kind),
+        preview.artifact.name,
+        preview.changes.len()
+    );
+    for change in &preview.changes {
+        let verb = match change.action {
+            FileAction::Create => "create",
+            FileAction::Update => "update",
+            FileAction::Delete => "delete",
+        };
+        println!("  would {verb}: {}", change.path.display());
+    }
+    for change in &preview.changes {
+        print!("{}", change.diff);
+    }
+    if let Some(tree) = &preview.dependencies {
+        print_dependency_tree(tree);
     }
 }
 
@@ -195,6 +377,41 @@ fn print_json(result: &CommandResult) -> Result<(), CliError> {
     Ok(())
 }
 
+fn print_impact(report: &ImpactReport) {
+    if report.changed.is_empty() {
+        println!("No changed artifacts.");
+        return;
+    }
+    let changed: Vec<String> = report.changed.iter().map(id_label).collect();
+    println!(
+        "Impact of {}: {} artifact(s)",
+        changed.join(", "),
+        report.impacted.len()
+    );
+    if report.impacted.is_empty() {
+        println!("  (none)");
+    }
+    for item in &report.impacted {
+        let reach = match item.reach {
+            ImpactReach::Direct => "direct",
+            ImpactReach::Transitive => "transitive",
+        };
+        let requirement = if item.required {
+            "required"
+        } else {
+            "optional"
+        };
+        println!(
+            "  {} ({}, {})",
+            artifact_label(&item.artifact),
+            reach,
+            requirement
+        );
+        let path: Vec<String> = item.path.iter().map(id_label).collect();
+        println!("    path: {}", path.join(" -> "));
+    }
+}
+
 fn print_dependency_tree(tree: &DependencyTree) {
     render_direction_section(
         "Downstream",
@@ -222,6 +439,18 @@ fn print_dependency_view(scope: DependencyScope, view: DependencyView, tree: &De
             render_direction_section(&title, tree, other, false);
         }
     }
+
+    if !tree.diagnostics.is_empty() {
+        println!("  Version requirements not met: {}", tree.diagnostics.len());
+        for diagnostic in &tree.diagnostics {
+            let note = if diagnostic.optional {
+                " (optional)"
+            } else {
+                ""
+            };
+            println!("    {}{}", diagnostic.message, note);
+        }
+    }
 }
 
 fn render_direction_section(
@@ -250,84 +479,55 @@ fn render_direction_section(
         return;
     }
 
-    let children = build_children_map(edges, view);
-    let mut stack = Vec::new();
-    render_children(&tree.root, &children, format!("{prefix}"), &mut stack);
+    let relation = match view {
+        DependencyView::Upstream => DependencyRelation::Upstream,
+        _ => DependencyRelation::Downstream,
+    };
+    render_children(&tree.hierarchy(relation), prefix);
 }
 
-fn build_children_map(
-    edges: &[DependencyEdge],
-    view: DependencyView,
-) -> BTreeMap<String, Vec<ArtifactSummary>> {
-    let mut map: BTreeMap<String, Vec<ArtifactSummary>> = BTreeMap::new();
-
-    for edge in edges {
-        let (parent, child) = match view {
-            DependencyView::Downstream => (&edge.to, &edge.from),
-            DependencyView::Upstream => (&edge.from, &edge.to),
-            DependencyView::All => unreachable!("all view handled earlier"),
-        };
-        map.entry(parent.id.name.clone())
-            .or_default()
-            .push(child.clone());
-    }
-
-    for children in map.values_mut() {
-        children.sort_by(|a, b| a.id.name.cmp(&b.id.name));
+fn render_children(node: &DependencyNode, prefix: &str) {
+    for (index, child) in node.children.iter().enumerate() {
+        let last = index + 1 == node.children.len();
+        let connector = if last { "└──" } else { "├──" };
+        let mut label = artifact_label(&child.artifact);
+        if child.optional {
+            label.push_str(" (optional)");
+        }
+        if child.seen_earlier {
+            label.push_str(" (seen earlier)");
+        }
+        println!("{prefix}{connector} {label}");
+        let next_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+        render_children(child, &next_prefix);
     }
-
-    map
 }
 
-fn render_children(
-    parent: &ArtifactSummary,
-    children_map: &BTreeMap<String, Vec<ArtifactSummary>>,
-    prefix: String,
-    stack: &mut Vec<String>,
-) {
-    if let Some(children) = children_map.get(&parent.id.name) {
-        stack.push(parent.id.name.clone());
-        for (index, child) in children.iter().enumerate() {
-            let connector = if index + 1 == children.len() {
-                "└──"
-            } else {
-                "├──"
-            };
-            let cycle = stack.contains(&child.id.name);
-            if cycle {
-                println!(
-                    "{}{} {} (cycle detected)",
-                    prefix,
-                    connector,
-                    artifact_label(child)
-                );
-                continue;
-            }
-            println!("{}{} {}", prefix, connector, artifact_label(child));
-            let next_prefix = format!(
-                "{}{}",
-                prefix,
-                if index + 1 == children.len() {
-                    "    "
-                } else {
-                    "│   "
-                }
-            );
-            render_children(child, children_map, next_prefix, stack);
-        }
-        stack.pop();
+fn artifact_label(summary: &ArtifactSummary) -> String {
+    let mut label = id_label(&summary.id);
+    if let Some(version) = &summary.version {
+        label.push_str(&format!("@{version}"));
+    }
+    if let Some(fragment) = &summary.fragment {
+        label.push_str(&format!("#{fragment}"));
     }
+    label
 }
 
-fn artifact_label(summary: &ArtifactSummary) -> String {
-    let kind = match summary.id.kind {
+fn id_label(id: &ArtifactId) -> String {
+    let kind = match id.kind {
         ArtifactKind::Specification => "spec",
         ArtifactKind::Implementation => "impl",
         ArtifactKind::ScratchPad => "scratch",
     };
-    match summary.version.as_ref() {
-        Some(version) => format!("{} {}@{}", kind, summary.id.name, version),
-        None => format!("{} {}", kind, summary.id.name),
+    format!("{} {}", kind, id.name)
+}
+
+fn kind_label(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Specification => "specification",
+        ArtifactKind::Implementation => "implementation",
+        ArtifactKind::ScratchPad => "scratch pad",
     }
 }
 
@@ -343,5 +543,6 @@ fn describe_template_locator(locator: &TemplateLocator) -> String {
     match locator {
         TemplateLocator::FilePath(path) => path.display().to_string(),
         TemplateLocator::Url(url) => url.clone(),
+        TemplateLocator::Embedded(key) => format!("embedded://{key}"),
     }
 }
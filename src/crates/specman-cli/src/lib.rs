@@ -4,6 +4,7 @@ mod context;
 mod error;
 mod formatter;
 mod frontmatter;
+mod progress;
 mod templates;
 mod util;
 
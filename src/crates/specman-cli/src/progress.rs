@@ -0,0 +1,63 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+
+use specman::{ProgressEvent, ProgressObserver};
+
+/// Renders dependency traversal progress on STDERR for `--verbose` runs. Terminals get a
+/// single line that is redrawn in place; redirected output gets one line per event so logs
+/// stay readable.
+pub struct ProgressLine {
+    interactive: bool,
+    state: Mutex<ProgressCounts>,
+}
+
+#[derive(Default)]
+struct ProgressCounts {
+    artifacts: usize,
+    fetches_started: usize,
+    fetches_finished: usize,
+    edges: usize,
+    drawn: bool,
+}
+
+impl ProgressLine {
+    pub fn stderr() -> Self {
+        Self {
+            interactive: io::stderr().is_terminal(),
+            state: Mutex::new(ProgressCounts::default()),
+        }
+    }
+
+    /// Terminates an in-place line so regular output starts on a fresh row.
+    pub fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        if self.interactive && state.drawn {
+            eprintln!();
+        }
+        state.drawn = false;
+    }
+}
+
+impl ProgressObserver for ProgressLine {
+    fn on_progress(&self, event: &ProgressEvent) {
+        let mut state = self.state.lock().unwrap();
+        match event {
+            ProgressEvent::ArtifactVisited { .. } => state.artifacts += 1,
+            ProgressEvent::FetchStarted { .. } => state.fetches_started += 1,
+            ProgressEvent::FetchFinished { .. } => state.fetches_finished += 1,
+            ProgressEvent::EdgeDiscovered { .. } => state.edges += 1,
+        }
+        let line = format!(
+            "progress: {} artifact(s), {}/{} fetch(es), {} edge(s) - {event}",
+            state.artifacts, state.fetches_finished, state.fetches_started, state.edges
+        );
+        let mut stderr = io::stderr().lock();
+        if self.interactive {
+            let _ = write!(stderr, "\r\x1b[2K{line}");
+            let _ = stderr.flush();
+            state.drawn = true;
+        } else {
+            let _ = writeln!(stderr, "{line}");
+        }
+    }
+}
@@ -15,19 +15,29 @@ pub enum TemplateKind {
 
 pub struct TemplateCatalog {
     catalog: LibraryTemplateCatalog,
+    preview: LibraryTemplateCatalog,
 }
 
 impl TemplateCatalog {
     pub fn new(workspace: WorkspacePaths) -> Self {
         Self {
-            catalog: LibraryTemplateCatalog::new(workspace),
+            catalog: LibraryTemplateCatalog::new(workspace.clone()),
+            preview: LibraryTemplateCatalog::new(workspace).without_cache_writes(),
         }
     }
 
-    pub fn descriptor(&self, kind: TemplateKind) -> Result<ResolvedTemplate, CliError> {
-        self.catalog
-            .resolve(kind.scenario())
-            .map_err(CliError::from)
+    /// Resolves the template for `kind`; dry runs skip refreshing the embedded template cache.
+    pub fn descriptor(
+        &self,
+        kind: TemplateKind,
+        dry_run: bool,
+    ) -> Result<ResolvedTemplate, CliError> {
+        let catalog = if dry_run {
+            &self.preview
+        } else {
+            &self.catalog
+        };
+        catalog.resolve(kind.scenario()).map_err(CliError::from)
     }
 
     pub fn set_pointer(
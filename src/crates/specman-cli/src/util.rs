@@ -1,4 +1,6 @@
-use std::path::{Component, Path};
+use std::path::{Component, Path, PathBuf};
+
+use specman::DependencyQuery;
 
 use crate::error::{CliError, ExitStatus};
 
@@ -21,6 +23,8 @@ pub fn validate_locator(value: &str, field: &str) -> Result<(), CliError> {
         return Ok(());
     }
 
+    DependencyQuery::ensure_mutable(trimmed).map_err(CliError::from)?;
+
     if trimmed.contains("://") {
         return Err(CliError::new(
             format!("{field} locator '{trimmed}' must be an HTTPS URL or workspace-relative path"),
@@ -84,6 +88,25 @@ pub fn validate_slug(name: &str, kind: &str) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Rewrites a workspace-relative `locator` so it resolves from `document_dir`, the folder of
+/// the document that will declare it. Front matter paths are relative to their document.
+pub fn document_relative(root: &Path, document_dir: &Path, locator: &str) -> String {
+    if locator.starts_with("https://") {
+        return locator.to_string();
+    }
+    let from: Vec<_> = document_dir
+        .strip_prefix(root)
+        .unwrap_or(document_dir)
+        .components()
+        .collect();
+    let to: Vec<_> = Path::new(locator).components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let relative: PathBuf = std::iter::repeat_n(Component::ParentDir, from.len() - common)
+        .chain(to[common..].iter().copied())
+        .collect();
+    relative.to_string_lossy().replace('\\', "/")
+}
+
 pub fn workspace_relative(root: &Path, path: &Path) -> String {
     path.strip_prefix(root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
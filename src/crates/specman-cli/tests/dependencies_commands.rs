@@ -3,6 +3,7 @@ use std::io::Write;
 use std::path::Path;
 
 use assert_cmd::Command;
+use predicates::prelude::*;
 use predicates::str::contains;
 use tempfile::TempDir;
 
@@ -44,6 +45,80 @@ fn impl_dependencies_honors_upstream_flag() -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+#[test]
+fn impl_dependencies_nest_transitive_upstream() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "impl",
+        "dependencies",
+        "alpha-spec-rust",
+        "--upstream",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("  └── spec alpha-spec"))
+        .stdout(contains("      └── spec beta-spec"));
+    Ok(())
+}
+
+#[test]
+fn impl_dependencies_show_heading_fragments() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/alpha-spec/spec.md"),
+        r#"---
+name: alpha-spec
+version: "1.0.0"
+---
+# Alpha
+
+## Concept: Alpha Widgets
+"#,
+    )?;
+    write_file(
+        workspace.path().join("impl/alpha-spec-rust/impl.md"),
+        r#"---
+name: alpha-spec-rust
+version: "0.1.0"
+spec: ../../spec/alpha-spec/spec.md#concept-alpha-widgets
+primary_language:
+    language: rust@1.0
+---
+Implementation body.
+"#,
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "impl",
+        "dependencies",
+        "alpha-spec-rust",
+        "--upstream",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(contains("└── spec alpha-spec@1.0.0#concept-alpha-widgets"));
+
+    write_file(
+        workspace.path().join("spec/alpha-spec/spec.md"),
+        "---\nname: alpha-spec\nversion: \"1.0.0\"\n---\n# Alpha\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "impl",
+        "dependencies",
+        "alpha-spec-rust",
+        "--upstream",
+    ]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains("heading #concept-alpha-widgets not found"));
+    Ok(())
+}
+
 #[test]
 fn spec_dependencies_rejects_conflicting_flags() -> Result<(), Box<dyn std::error::Error>> {
     let workspace = scaffold_workspace()?;
@@ -83,10 +158,448 @@ fn scratch_dependencies_renders_all_sections() -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[test]
+fn graph_exports_workspace_as_dot() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).arg("graph");
+
+    cmd.assert()
+        .success()
+        .stdout(contains("digraph specman {"))
+        .stdout(contains("spec_alpha_spec -> spec_beta_spec;"))
+        .stdout(contains("impl_alpha_spec_rust -> spec_alpha_spec;"))
+        .stdout(contains(
+            "scratch_alpha_notes [label=\"alpha-notes\", shape=note",
+        ));
+    Ok(())
+}
+
+#[test]
+fn graph_limits_mermaid_output_to_root_closure() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "graph",
+        "--format",
+        "mermaid",
+        "--root",
+        "impl://alpha-spec-rust",
+        "--upstream",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("flowchart LR"))
+        .stdout(contains("impl_alpha_spec_rust --> spec_alpha_spec"))
+        .stdout(contains("spec_alpha_spec --> spec_beta_spec"))
+        .stdout(contains("scratch_alpha_notes").not());
+    Ok(())
+}
+
+#[test]
+fn graph_direction_flags_require_root() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["graph", "--upstream"]);
+
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("require --root"));
+    Ok(())
+}
+
+#[test]
+fn graph_order_lists_dependency_levels() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["graph", "--order"]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("Build order (3 level(s)):"))
+        .stdout(contains("  Level 0: spec beta-spec\n"))
+        .stdout(contains("  Level 1: spec alpha-spec\n"))
+        .stdout(contains(
+            "  Level 2: impl alpha-spec-rust, scratch alpha-notes\n",
+        ))
+        .stdout(contains("Cycles").not());
+    Ok(())
+}
+
+#[test]
+fn graph_order_reports_every_cycle() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/beta-spec/spec.md"),
+        r#"---
+name: beta-spec
+version: "1.0.0"
+dependencies:
+  - ../alpha-spec/spec.md
+---
+Beta specification.
+"#,
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--json", "graph", "--order"]);
+
+    let output = cmd.assert().failure().code(65).get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "graph_order");
+    assert_eq!(
+        payload["cycles"],
+        serde_json::json!([[
+            {"kind": "Specification", "name": "alpha-spec"},
+            {"kind": "Specification", "name": "beta-spec"}
+        ]])
+    );
+    Ok(())
+}
+
+#[test]
+fn unmet_version_requirements_are_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/alpha-spec/spec.md"),
+        r#"---
+name: alpha-spec
+version: "1.0.0"
+dependencies:
+  - ref: ../beta-spec/spec.md
+    version: "^2.0"
+---
+Alpha specification.
+"#,
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "dependencies",
+        "alpha-spec",
+        "--upstream",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(contains("Version requirements not met: 1"))
+        .stdout(contains(
+            "spec://alpha-spec requires spec://beta-spec ^2.0 via `dependencies`, found 1.0.0",
+        ));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).arg("status");
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stdout(contains("[ERR] alpha-spec (spec)"))
+        .stdout(contains("[OK] beta-spec (spec)"));
+    Ok(())
+}
+
+#[test]
+fn offline_mode_only_resolves_cached_remote_dependencies() -> Result<(), Box<dyn std::error::Error>>
+{
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/beta-spec/spec.md"),
+        r#"---
+name: beta-spec
+version: "1.0.0"
+dependencies:
+  - https://127.0.0.1:1/remote/spec.md
+---
+Beta specification.
+"#,
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--offline",
+        "spec",
+        "dependencies",
+        "beta-spec",
+        "--upstream",
+    ]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains("network access is disabled"));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--offline", "fetch"]);
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("drop --offline"));
+
+    // Nothing listens on port 1, so the fetch is reported as failed.
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--json", "fetch"]);
+    let output = cmd.assert().failure().code(75).get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "fetch");
+    assert_eq!(payload["cache"], ".specman/cache/remote");
+    assert_eq!(
+        payload["artifacts"][0]["url"],
+        "https://127.0.0.1:1/remote/spec.md"
+    );
+    assert!(payload["artifacts"][0]["error"].is_string());
+    Ok(())
+}
+
+#[test]
+fn dependency_cycles_suggest_entries_to_change() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/loop-a/spec.md"),
+        "---\nname: loop-a\nversion: \"1.0.0\"\ndependencies:\n  - ../loop-b/spec.md\n---\n# Loop A\n",
+    )?;
+    write_file(
+        workspace.path().join("spec/loop-b/spec.md"),
+        "---\nname: loop-b\nversion: \"1.0.0\"\ndependencies:\n  - spec://loop-a\n---\n# Loop B\n",
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "dependencies",
+        "loop-a",
+        "--upstream",
+    ]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains(
+            "dependency cycle detected: spec://loop-a -> spec://loop-b -> spec://loop-a",
+        ))
+        .stderr(contains("To break the cycle, change one of these entries:"))
+        .stderr(contains(
+            "  - remove `spec://loop-a` in spec://loop-b's `dependencies`",
+        ));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).arg("status");
+    cmd.assert().failure().code(65).stdout(contains(
+        "fix: remove `../loop-b/spec.md` in spec://loop-a's `dependencies`",
+    ));
+    Ok(())
+}
+
+#[test]
+fn vendor_check_reports_modified_and_unreachable_copies() -> Result<(), Box<dyn std::error::Error>>
+{
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join(".specman/vendor.json"),
+        r#"{
+  "artifacts": [
+    {
+      "artifact": { "kind": "Specification", "name": "beta-spec" },
+      "path": "spec/beta-spec/spec.md",
+      "source": "https://127.0.0.1:1/remote/spec.md",
+      "sha256": "0000",
+      "fetched_at": "2026-01-01T00:00:00.000Z"
+    }
+  ]
+}
+"#,
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--offline", "vendor", "--check"]);
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("drop --offline"));
+
+    // The recorded hash does not match the local file, and nothing listens on port 1.
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["vendor", "--check"]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stdout(contains("Checked 1 vendored specification(s)"))
+        .stdout(contains("[MODIFIED] spec beta-spec"))
+        .stdout(contains("[ERR] spec beta-spec"));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--offline",
+        "vendor",
+        "http://example.com/spec.md",
+    ]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains("use https"));
+    Ok(())
+}
+
+#[test]
+fn lint_cites_data_model_headings() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).arg("lint");
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stdout(contains("Linted 4 artifact(s): 2 error(s), 2 warning(s)"))
+        .stdout(contains(
+            "[ERROR] .specman/scratchpad/alpha-notes/scratch.md scratch-work-type-target: `feat` scratch pads must target an implementation",
+        ))
+        .stdout(contains(
+            "[ERROR] .specman/scratchpad/alpha-notes/scratch.md scratch-notes-section",
+        ))
+        .stdout(contains(
+            "see spec/specman-data-model/spec.md#scratch-pad-content",
+        ))
+        .stdout(contains(
+            "[WARN] spec/beta-spec/spec.md spec-terminology-heading",
+        ));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["lint", "--format", "sarif"]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stdout(contains(r#""version": "2.1.0""#))
+        .stdout(contains(r#""ruleId": "scratch-notes-section""#));
+
+    // Warnings alone do not fail the run.
+    write_file(
+        workspace
+            .path()
+            .join(".specman/scratchpad/alpha-notes/scratch.md"),
+        r#"---
+target: impl/alpha-spec-rust/impl.md
+work_type:
+  feat: {}
+---
+## Notes
+"#,
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--json", "lint"]);
+    cmd.assert()
+        .success()
+        .stdout(contains(r#""type":"lint""#))
+        .stdout(contains(r#""severity":"warning""#))
+        .stdout(contains("scratch-notes-section").not());
+    Ok(())
+}
+
 fn cli() -> Command {
     Command::new(env!("CARGO_BIN_EXE_specman"))
 }
 
+#[test]
+fn dependencies_accept_read_only_handles() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "dependencies",
+        "spec://alpha-spec/dependencies",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains(
+            "Dependency tree (downstream) for specification 'alpha-spec'",
+        ))
+        .stdout(contains("impl alpha-spec-rust"));
+    Ok(())
+}
+
+#[test]
+fn dependencies_reject_handles_for_other_kinds() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "dependencies",
+        "impl://alpha-spec-rust/dependencies",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("does not address a specification"));
+    Ok(())
+}
+
+#[test]
+fn delete_rejects_dependency_handles() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "scratch",
+        "delete",
+        "scratch://alpha-notes/dependencies",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("read-only locator"));
+    assert!(
+        workspace
+            .path()
+            .join(".specman/scratchpad/alpha-notes/scratch.md")
+            .is_file()
+    );
+    Ok(())
+}
+
+#[test]
+fn verbose_mode_reports_traversal_progress() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut verbose = cli();
+    verbose.arg("--workspace").arg(workspace.path()).args([
+        "--verbose",
+        "spec",
+        "dependencies",
+        "alpha-spec",
+    ]);
+    verbose
+        .assert()
+        .success()
+        .stderr(contains(
+            "progress: 1 artifact(s), 0/0 fetch(es), 0 edge(s) - visited spec alpha-spec",
+        ))
+        .stderr(contains("found edge alpha-spec -> beta-spec"))
+        .stdout(contains("Dependency tree (downstream)"));
+
+    let mut quiet = cli();
+    quiet
+        .arg("--workspace")
+        .arg(workspace.path())
+        .args(["spec", "dependencies", "alpha-spec"]);
+    quiet.assert().success().stderr(contains("progress:").not());
+    Ok(())
+}
+
 fn scaffold_workspace() -> Result<TempDir, Box<dyn std::error::Error>> {
     let temp = TempDir::new()?;
     let root = temp.path();
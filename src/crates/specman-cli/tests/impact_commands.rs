@@ -0,0 +1,242 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn impact_lists_transitive_dependents_with_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["impact", "spec://beta-spec"]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("Impact of spec beta-spec: 4 artifact(s)"))
+        .stdout(contains("  spec alpha-spec@1.0.0 (direct, required)"))
+        .stdout(contains("  spec gamma-spec@1.0.0 (transitive, optional)"))
+        .stdout(contains("  scratch alpha-notes (transitive, required)"))
+        .stdout(contains(
+            "    path: spec beta-spec -> spec alpha-spec -> impl alpha-spec-rust",
+        ));
+    Ok(())
+}
+
+#[test]
+fn impact_json_accepts_paths_and_names() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--json",
+        "impact",
+        "spec/alpha-spec/spec.md",
+        "beta-spec",
+    ]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "impact");
+    assert_eq!(payload["report"]["changed"].as_array().unwrap().len(), 2);
+    let impacted = payload["report"]["impacted"].as_array().unwrap();
+    assert_eq!(impacted.len(), 3);
+    assert_eq!(impacted[0]["artifact"]["id"]["name"], "gamma-spec");
+    assert_eq!(impacted[0]["reach"], "direct");
+    assert_eq!(impacted[0]["required"], false);
+    assert_eq!(impacted[1]["artifact"]["id"]["name"], "alpha-spec-rust");
+    assert_eq!(impacted[1]["required"], true);
+    Ok(())
+}
+
+#[test]
+fn impact_reads_changed_artifacts_from_git_range() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let root = workspace.path();
+    git(root, &["init", "--quiet"])?;
+    git(root, &["add", "."])?;
+    git(root, &["commit", "--quiet", "-m", "baseline"])?;
+    write_file(
+        root.join("impl/alpha-spec-rust/impl.md"),
+        "---\nname: alpha-spec-rust\nversion: \"0.2.0\"\nspec: ../../spec/alpha-spec/spec.md\nprimary_language:\n    language: rust@1.0\n---\nChanged.\n",
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(root)
+        .args(["impact", "--git", "HEAD"]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("Impact of impl alpha-spec-rust: 1 artifact(s)"))
+        .stdout(contains("  scratch alpha-notes (direct, required)"));
+    Ok(())
+}
+
+#[test]
+fn impact_rejects_git_ranges_that_look_like_options() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let root = workspace.path();
+    git(root, &["init", "--quiet"])?;
+    let target = root.join("clobbered.txt");
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(root)
+        .arg("impact")
+        .arg(format!("--git=--output={}", target.display()));
+
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("ranges cannot start with '-'"));
+    assert!(!target.exists());
+    Ok(())
+}
+
+#[test]
+fn why_lists_every_path_with_front_matter_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "why",
+        "scratch://alpha-notes",
+        "spec://beta-spec",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains(
+            "2 path(s) from scratch alpha-notes to spec beta-spec:",
+        ))
+        .stdout(contains(
+            "  1. scratch alpha-notes --[dependencies]--> impl alpha-spec-rust --[spec]--> spec alpha-spec",
+        ))
+        .stdout(contains(
+            "  2. scratch alpha-notes --[target]--> impl alpha-spec-rust --[spec]--> spec alpha-spec --[dependencies]--> spec beta-spec",
+        ));
+    Ok(())
+}
+
+#[test]
+fn why_reports_unrelated_artifacts() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--json",
+        "why",
+        "beta-spec",
+        "impl/alpha-spec-rust/impl.md",
+        "--shortest",
+    ]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "why");
+    assert_eq!(payload["to"]["kind"], "Implementation");
+    assert_eq!(payload["paths"], serde_json::json!([]));
+    Ok(())
+}
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_specman"))
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let status = process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=SpecMan",
+            "-c",
+            "user.email=specman@example.com",
+        ])
+        .args(args)
+        .current_dir(root)
+        .status()?;
+    assert!(status.success(), "git {args:?} failed");
+    Ok(())
+}
+
+fn scaffold_workspace() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    let root = temp.path();
+
+    fs::create_dir_all(root.join(".specman/scratchpad"))?;
+
+    write_file(
+        root.join("spec/beta-spec/spec.md"),
+        r#"---
+name: beta-spec
+version: "1.0.0"
+dependencies: []
+---
+Beta specification.
+"#,
+    )?;
+
+    write_file(
+        root.join("spec/alpha-spec/spec.md"),
+        r#"---
+name: alpha-spec
+version: "1.0.0"
+dependencies:
+  - ../beta-spec/spec.md
+---
+Alpha specification.
+"#,
+    )?;
+
+    write_file(
+        root.join("impl/alpha-spec-rust/impl.md"),
+        r#"---
+name: alpha-spec-rust
+version: "0.1.0"
+spec: ../../spec/alpha-spec/spec.md
+primary_language:
+    language: rust@1.0
+---
+Implementation body.
+"#,
+    )?;
+
+    write_file(
+        root.join(".specman/scratchpad/alpha-notes/scratch.md"),
+        r#"---
+name: alpha-notes
+target: impl/alpha-spec-rust/impl.md
+branch: alpha-spec-rust/feat/alpha-notes
+work_type:
+  feat: {}
+dependencies:
+  - impl/alpha-spec-rust/impl.md
+---
+Scratch content.
+"#,
+    )?;
+
+    write_file(
+        root.join("spec/gamma-spec/spec.md"),
+        r#"---
+name: gamma-spec
+version: "1.0.0"
+dependencies:
+  - ref: ../alpha-spec/spec.md
+    optional: true
+---
+Gamma specification.
+"#,
+    )?;
+
+    Ok(temp)
+}
+
+fn write_file(path: impl AsRef<Path>, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
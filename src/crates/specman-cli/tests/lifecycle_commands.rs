@@ -0,0 +1,453 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+#[test]
+fn spec_new_dry_run_previews_without_writing() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    seed_remote_cache(
+        workspace.path(),
+        DATA_MODEL_URL,
+        "---\nname: specman-data-model\nversion: \"1.0.0\"\n---\n# Data model\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--offline",
+        "spec",
+        "new",
+        "gamma-spec",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("Dry run for specification 'gamma-spec'"))
+        .stdout(contains("would create: spec/gamma-spec/spec.md"))
+        .stdout(contains("--- /dev/null"))
+        .stdout(contains("+name: gamma-spec"));
+    assert!(!workspace.path().join("spec/gamma-spec").exists());
+    assert!(
+        !workspace.path().join(".specman/cache/templates").exists(),
+        "dry runs must not refresh the embedded template cache"
+    );
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--offline",
+        "--json",
+        "spec",
+        "new",
+        "gamma-spec",
+        "--dry-run",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    let tree = &payload["preview"]["dependencies"];
+    assert_eq!(tree["root"]["id"]["name"], "gamma-spec");
+    assert_eq!(
+        tree["upstream"][0]["to"]["id"]["name"],
+        "specman-data-model"
+    );
+    let diff = payload["preview"]["changes"][0]["diff"]
+        .as_str()
+        .ok_or("preview diff missing")?;
+    let previewed: String = diff
+        .lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .skip(1)
+        .map(|line| format!("{}\n", line.strip_prefix('+').unwrap_or(line)))
+        .collect();
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--offline", "spec", "new", "gamma-spec"]);
+    cmd.assert().success();
+    let written = fs::read_to_string(workspace.path().join("spec/gamma-spec/spec.md"))?;
+    assert_eq!(previewed, written);
+    Ok(())
+}
+
+#[test]
+fn impl_delete_dry_run_lists_files_and_keeps_them() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "impl",
+        "delete",
+        "alpha-spec-rust",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("would delete: impl/alpha-spec-rust/impl.md"))
+        .stdout(contains("-Implementation body."))
+        .stdout(contains("Downstream"));
+    assert!(
+        workspace
+            .path()
+            .join("impl/alpha-spec-rust/impl.md")
+            .is_file()
+    );
+    Ok(())
+}
+
+#[test]
+fn spec_delete_dry_run_honors_dependency_guard() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut blocked = cli();
+    blocked.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "delete",
+        "alpha-spec",
+        "--dry-run",
+    ]);
+    blocked
+        .assert()
+        .failure()
+        .code(65)
+        .stderr(contains("downstream artifacts detected"));
+
+    let mut forced = cli();
+    forced.arg("--workspace").arg(workspace.path()).args([
+        "--json",
+        "spec",
+        "delete",
+        "alpha-spec",
+        "--dry-run",
+        "--force",
+    ]);
+    let output = forced.assert().success().get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "dry_run");
+    assert_eq!(payload["preview"]["dry_run"], true);
+    assert_eq!(payload["preview"]["changes"][0]["action"], "delete");
+    assert_eq!(
+        payload["preview"]["dependencies"]["root"]["id"]["name"],
+        "alpha-spec"
+    );
+    assert!(workspace.path().join("spec/alpha-spec/spec.md").is_file());
+    assert!(!workspace.path().join(".specman/audit").exists());
+    Ok(())
+}
+
+#[test]
+fn spec_new_expands_template_token_contract() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join(".specman/templates/spec.md"),
+        "---\nname: {{output_name}}\nversion: \"1.0.0\"\ndependencies: {{dependencies}}\n---\n# {{output_name}}\n\nRead first:\n{{context}}\n\n<!-- {{arguments}} -->\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "new",
+        "gamma-spec",
+        "--dependencies",
+        "spec/alpha-spec/spec.md",
+        "--arguments",
+        "Cover retries only.",
+        "--dry-run",
+    ]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let preview = String::from_utf8(output)?;
+    assert!(preview.contains("+# gamma-spec\n"));
+    assert!(preview.contains("+Read first:\n+- `../alpha-spec/spec.md`\n"));
+    assert!(preview.contains("+<!-- Cover retries only. -->"));
+    // The post-render front matter rewrite must not duplicate the expanded dependency.
+    assert_eq!(preview.matches("+- ../alpha-spec/spec.md\n").count(), 1);
+    Ok(())
+}
+
+#[test]
+fn create_commands_declare_locators_relative_to_the_new_document()
+-> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join(".specman/templates/spec.md"),
+        "---\nname: {{output_name}}\nversion: \"1.0.0\"\ndependencies: []\n---\n# {{output_name}}\n",
+    )?;
+    write_file(
+        workspace.path().join(".specman/templates/impl.md"),
+        "---\nname: {{output_name}}\nversion: \"0.1.0\"\n---\n# {{output_name}}\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "new",
+        "gamma-spec",
+        "--dependencies",
+        "spec/alpha-spec/spec.md",
+    ]);
+    cmd.assert().success();
+    let spec = fs::read_to_string(workspace.path().join("spec/gamma-spec/spec.md"))?;
+    assert!(spec.contains("- ../alpha-spec/spec.md\n"), "{spec}");
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "impl",
+        "new",
+        "gamma-spec-rust",
+        "--spec",
+        "gamma-spec",
+        "--language",
+        "rust@1.91",
+    ]);
+    cmd.assert().success();
+    let implementation = fs::read_to_string(workspace.path().join("impl/gamma-spec-rust/impl.md"))?;
+    assert!(
+        implementation.contains("spec: ../../spec/gamma-spec/spec.md"),
+        "{implementation}"
+    );
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "spec",
+        "new",
+        "delta-spec",
+        "--dependencies",
+        "spec/missing-spec/spec.md",
+    ]);
+    cmd.assert().failure();
+    assert!(!workspace.path().join("spec/delta-spec").exists());
+    Ok(())
+}
+
+#[test]
+fn scratch_new_expands_target_dependency_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join(".specman/templates/scratch.md"),
+        "---\ntarget: placeholder\nwork_type:\n  feat: {}\n---\n# {{output_name}}\n\n{{context}}\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "scratch",
+        "new",
+        "--name",
+        "alpha-tuning",
+        "--target",
+        "impl/alpha-spec-rust/impl.md",
+        "--type",
+        "feat",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains("+# alpha-tuning"))
+        .stdout(contains(
+            "+- `impl/alpha-spec-rust/impl.md`\n+- `spec/alpha-spec/spec.md`",
+        ));
+    Ok(())
+}
+
+#[test]
+fn scratch_new_rejects_target_outside_work_type() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "scratch",
+        "new",
+        "--name",
+        "alpha-fix",
+        "--target",
+        "spec/alpha-spec/spec.md",
+        "--type",
+        "fix",
+    ]);
+    cmd.assert().failure().code(65).stderr(contains(
+        "`fix` scratch pads must target an implementation, but spec/alpha-spec/spec.md is a specification",
+    ));
+    assert!(
+        !workspace
+            .path()
+            .join(".specman/scratchpad/alpha-fix")
+            .exists()
+    );
+
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "scratch",
+        "new",
+        "--name",
+        "alpha-fix",
+        "--target",
+        "impl/alpha-spec-rust/impl.md",
+        "--type",
+        "fix",
+        "--dry-run",
+    ]);
+    cmd.assert().success().stdout(contains("+  fix: {}"));
+    Ok(())
+}
+
+#[test]
+fn front_matter_errors_render_code_frame_and_json_span() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/broken-spec/spec.md"),
+        "---\nname: broken-spec\nversion: \"1.0.0\"\ndependencies: nope\n---\nBroken.\n",
+    )?;
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["spec", "ls"]);
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains(
+            "spec/broken-spec/spec.md:4:15: dependencies: invalid type",
+        ))
+        .stderr(contains("4 | dependencies: nope\n  |               ^"));
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["--json", "spec", "ls"]);
+    let output = cmd.assert().failure().code(65).get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "error");
+    assert_eq!(payload["code"], "SM-FM-INVALID");
+    assert_eq!(payload["exit_code"], 65);
+    assert_eq!(payload["span"]["line"], 4);
+    assert_eq!(payload["span"]["column"], 15);
+    assert!(
+        payload["span"]["file"]
+            .as_str()
+            .is_some_and(|file| file.ends_with("spec/broken-spec/spec.md"))
+    );
+    Ok(())
+}
+
+#[test]
+fn explain_describes_error_codes_outside_a_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let outside = TempDir::new()?;
+    let mut cmd = cli();
+    cmd.current_dir(outside.path())
+        .args(["explain", "sm-dep-cycle"]);
+    cmd.assert()
+        .success()
+        .stdout(contains("SM-DEP-CYCLE: Dependencies loop back"))
+        .stdout(contains("optional: true"))
+        .stdout(contains("see spec/specman-data-model/spec.md#dependencies"));
+
+    let mut cmd = cli();
+    cmd.current_dir(outside.path()).args(["explain", "SM-NOPE"]);
+    cmd.assert()
+        .failure()
+        .code(64)
+        .stderr(contains("SM-WS-ESCAPE"));
+
+    let mut cmd = cli();
+    cmd.current_dir(outside.path()).args(["--json", "status"]);
+    let output = cmd.assert().failure().code(64).get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["code"], "SM-WS-NOT-FOUND");
+    Ok(())
+}
+
+#[test]
+fn schema_emits_and_writes_front_matter_schemas() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["schema", "impl"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let schema: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    assert_eq!(schema["title"], "SpecMan implementation front matter");
+    assert!(schema["properties"]["primary_language"].is_object());
+
+    let outside = TempDir::new()?;
+    let mut cmd = cli();
+    cmd.current_dir(outside.path()).args(["schema", "impl"]);
+    let printed = cmd.assert().success().get_output().stdout.clone();
+    assert_eq!(printed, output);
+
+    let mut cmd = cli();
+    cmd.arg("--workspace")
+        .arg(workspace.path())
+        .args(["schema", "--write"]);
+    cmd.assert()
+        .success()
+        .stdout(contains(".specman/schemas/scratch.schema.json"));
+    let scratch: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        workspace
+            .path()
+            .join(".specman/schemas/scratch.schema.json"),
+    )?)?;
+    assert!(scratch["definitions"]["ScratchWorkType"]["anyOf"].is_array());
+    for name in ["spec", "impl"] {
+        assert!(
+            workspace
+                .path()
+                .join(format!(".specman/schemas/{name}.schema.json"))
+                .is_file()
+        );
+    }
+    Ok(())
+}
+
+const DATA_MODEL_URL: &str = "https://raw.githubusercontent.com/jbrickley-tcs/specman/refs/heads/main/spec/specman-data-model/spec.md";
+
+/// Stores `body` where the remote cache keeps `url`, so `--offline` runs resolve it.
+fn seed_remote_cache(root: &Path, url: &str, body: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let key = hex::encode(Sha256::digest(url.as_bytes()));
+    write_file(root.join(format!(".specman/cache/remote/{key}.md")), body)
+}
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_specman"))
+}
+
+fn scaffold_workspace() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    let root = temp.path();
+
+    fs::create_dir_all(root.join(".specman/scratchpad"))?;
+
+    write_file(
+        root.join("spec/alpha-spec/spec.md"),
+        r#"---
+name: alpha-spec
+version: "1.0.0"
+dependencies: []
+---
+Alpha specification.
+"#,
+    )?;
+
+    write_file(
+        root.join("impl/alpha-spec-rust/impl.md"),
+        r#"---
+name: alpha-spec-rust
+version: "0.1.0"
+spec: ../../spec/alpha-spec/spec.md
+primary_language:
+    language: rust@1.0
+---
+Implementation body.
+"#,
+    )?;
+
+    Ok(temp)
+}
+
+fn write_file(path: impl AsRef<Path>, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
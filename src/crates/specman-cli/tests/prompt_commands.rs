@@ -0,0 +1,138 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn prompt_renders_context_for_target_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "prompt",
+        "impl",
+        "--target",
+        "beta-spec",
+        "--arguments",
+        "Port the parser to Rust.",
+        "--token",
+        "reference_items=- src/parser.rs",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(contains(
+            "- `spec/beta-spec/spec.md`\n- `spec/alpha-spec/spec.md`",
+        ))
+        .stdout(contains(
+            "realizes the specification at spec/beta-spec/spec.md",
+        ))
+        .stdout(contains("Port the parser to Rust."))
+        .stdout(contains(
+            "replacing the references list with - src/parser.rs",
+        ))
+        .stdout(contains("{{implementation_name}}"));
+    Ok(())
+}
+
+#[test]
+fn prompt_json_reports_provenance_and_workspace_override() -> Result<(), Box<dyn std::error::Error>>
+{
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace
+            .path()
+            .join(".specman/templates/prompts/scratch-revision.md"),
+        "Revise {{target_spec_path}} as {{scratch_name}}.\n",
+    )?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "--json",
+        "prompt",
+        "scratch-revision",
+        "--target",
+        "spec://alpha-spec",
+        "--name",
+        "alpha-wording",
+    ]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let payload: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(payload["type"], "prompt");
+    assert_eq!(
+        payload["prompt"]["body"],
+        "Revise spec/alpha-spec/spec.md as alpha-wording.\n"
+    );
+    assert_eq!(payload["prompt"]["provenance"]["tier"], "WorkspaceOverride");
+    assert_eq!(payload["prompt"]["work_type"], "Revision");
+    assert_eq!(
+        payload["prompt"]["unresolved_tokens"],
+        serde_json::json!([])
+    );
+    Ok(())
+}
+
+#[test]
+fn prompt_rejects_targets_of_the_wrong_kind() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut cmd = cli();
+    cmd.arg("--workspace").arg(workspace.path()).args([
+        "prompt",
+        "scratch-feat",
+        "--target",
+        "spec://alpha-spec",
+    ]);
+
+    cmd.assert()
+        .failure()
+        .code(65)
+        .stderr(contains("scratch-feat prompts target impl:// handles"));
+    Ok(())
+}
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_specman"))
+}
+
+fn scaffold_workspace() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+    let root = temp.path();
+
+    fs::create_dir_all(root.join(".specman/scratchpad"))?;
+
+    write_file(
+        root.join("spec/alpha-spec/spec.md"),
+        r#"---
+name: alpha-spec
+version: "1.0.0"
+dependencies: []
+---
+Alpha specification.
+"#,
+    )?;
+
+    write_file(
+        root.join("spec/beta-spec/spec.md"),
+        r#"---
+name: beta-spec
+version: "1.0.0"
+dependencies:
+  - ../alpha-spec/spec.md
+---
+Beta specification.
+"#,
+    )?;
+
+    Ok(temp)
+}
+
+fn write_file(path: impl AsRef<Path>, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
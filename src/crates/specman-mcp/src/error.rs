@@ -0,0 +1,87 @@
+use serde_json::{Value, json};
+use specman::{ErrorCode, SpecmanError};
+use thiserror::Error;
+
+use crate::protocol::{
+    INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, JsonRpcError, METHOD_NOT_FOUND,
+    SERVER_NOT_INITIALIZED,
+};
+
+/// Protocol-level failures surfaced to MCP clients as JSON-RPC error objects.
+#[derive(Debug, Error)]
+pub enum McpError {
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("method not found: {0}")]
+    MethodNotFound(String),
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+    #[error("server not initialized")]
+    NotInitialized,
+    #[error(transparent)]
+    Specman(#[from] SpecmanError),
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl McpError {
+    pub fn code(&self) -> i64 {
+        match self {
+            McpError::InvalidRequest(_) => INVALID_REQUEST,
+            McpError::MethodNotFound(_) => METHOD_NOT_FOUND,
+            McpError::InvalidParams(_) => INVALID_PARAMS,
+            McpError::NotInitialized => SERVER_NOT_INITIALIZED,
+            McpError::Specman(_) | McpError::Internal(_) => INTERNAL_ERROR,
+        }
+    }
+
+    pub fn to_json_rpc(&self) -> JsonRpcError {
+        JsonRpcError {
+            code: self.code(),
+            message: self.to_string(),
+            data: self.data(),
+        }
+    }
+
+    fn data(&self) -> Option<Value> {
+        match self {
+            McpError::Specman(err) => Some(specman_error_data(err)),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for McpError {
+    fn from(err: serde_json::Error) -> Self {
+        McpError::Internal(err.to_string())
+    }
+}
+
+/// Structured payload describing a SpecMan error so clients keep the originating
+/// category and stable error code instead of parsing message text.
+pub fn specman_error_data(err: &SpecmanError) -> Value {
+    let kind = match err.code() {
+        ErrorCode::TemplateMissingToken | ErrorCode::TemplateInvalid | ErrorCode::ScratchTarget => {
+            "template"
+        }
+        ErrorCode::DependencyUnresolved => "dependency",
+        ErrorCode::DependencyCycle => "dependency_cycle",
+        ErrorCode::FrontMatterInvalid => "front_matter",
+        ErrorCode::WorkspaceNotFound | ErrorCode::WorkspaceEscape | ErrorCode::WorkspaceInvalid => {
+            "workspace"
+        }
+        ErrorCode::Serialization => "serialization",
+        ErrorCode::ReadOnlyLocator => "read_only_locator",
+        ErrorCode::LockBusy => "lock",
+        ErrorCode::Io => "io",
+    };
+    let mut data = serde_json::to_value(err.details()).unwrap_or_default();
+    data["kind"] = json!(kind);
+    if let SpecmanError::DependencyCycle(cycle) = err {
+        data["cycle"] = serde_json::to_value(cycle).unwrap_or_default();
+    }
+    if let SpecmanError::FrontMatter(front) = err {
+        data["span"] = json!({ "file": front.file, "line": front.line, "column": front.column });
+    }
+    data
+}
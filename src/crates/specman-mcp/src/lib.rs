@@ -0,0 +1,82 @@
+mod error;
+mod progress;
+mod protocol;
+mod resources;
+mod server;
+mod session;
+mod tools;
+
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use specman::{FetchMode, FilesystemWorkspaceLocator};
+
+pub use error::McpError;
+pub use protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, SUPPORTED_PROTOCOL_VERSIONS};
+pub use server::{McpServer, SERVER_NAME, SERVER_VERSION};
+pub use session::{ClientInfo, McpWorkspaceSession, SpecmanServices};
+pub use tools::{ToolDefinition, capability_registry, tool_definitions};
+
+/// Entry point for the `specman-mcp` binary: discovers the workspace (honoring
+/// `--workspace <path>`) and serves MCP over STDIN/STDOUT until the client disconnects.
+/// `--offline` resolves `https://` dependencies from the remote cache only.
+pub fn run() -> ExitCode {
+    let arguments = match server_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => return fail(message),
+    };
+    let locator = match arguments.workspace {
+        Some(path) => FilesystemWorkspaceLocator::new(path),
+        None => match FilesystemWorkspaceLocator::from_current_dir() {
+            Ok(locator) => locator,
+            Err(err) => return fail(err),
+        },
+    };
+
+    let services = match SpecmanServices::with_fetch_mode(locator, arguments.fetch_mode) {
+        Ok(services) => services,
+        Err(err) => return fail(err),
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    match McpServer::new(services).serve(stdin.lock(), stdout.lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => fail(err),
+    }
+}
+
+struct ServerArguments {
+    workspace: Option<PathBuf>,
+    fetch_mode: FetchMode,
+}
+
+fn server_arguments(mut args: impl Iterator<Item = String>) -> Result<ServerArguments, String> {
+    let mut arguments = ServerArguments {
+        workspace: None,
+        fetch_mode: FetchMode::Online,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--workspace" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--workspace requires a path".to_string())?;
+                arguments.workspace = Some(PathBuf::from(value));
+            }
+            "--offline" => arguments.fetch_mode = FetchMode::Offline,
+            other => match other.strip_prefix("--workspace=") {
+                Some(value) => arguments.workspace = Some(PathBuf::from(value)),
+                None => return Err(format!("unexpected argument: {other}")),
+            },
+        }
+    }
+    Ok(arguments)
+}
+
+fn fail(err: impl std::fmt::Display) -> ExitCode {
+    // STDOUT belongs to the protocol stream, so diagnostics always go to STDERR.
+    eprintln!("specman-mcp: {err}");
+    ExitCode::FAILURE
+}
@@ -0,0 +1,74 @@
+use std::sync::Mutex;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use serde_json::{Value, json};
+use specman::{ProgressEvent, ProgressObserver};
+
+use crate::protocol::{JSONRPC_VERSION, JsonRpcResponse};
+
+/// Messages written to the client while `serve` waits on a request.
+pub(crate) enum Outbound {
+    Notification(Value),
+    Finished(thread::Result<Option<JsonRpcResponse>>),
+}
+
+/// Forwards graph progress as `notifications/progress` for the tool call that supplied a
+/// `_meta.progressToken`. Events raised outside such a call, or before `serve` attaches an
+/// output channel, are dropped.
+#[derive(Default)]
+pub(crate) struct ProgressRelay {
+    state: Mutex<RelayState>,
+}
+
+#[derive(Default)]
+struct RelayState {
+    sink: Option<Sender<Outbound>>,
+    token: Option<Value>,
+    progress: u64,
+}
+
+impl ProgressRelay {
+    pub(crate) fn attach(&self, sink: Sender<Outbound>) {
+        self.state.lock().unwrap().sink = Some(sink);
+    }
+
+    /// Starts reporting against `token` until the returned guard is dropped.
+    pub(crate) fn begin(&self, token: Option<Value>) -> ProgressScope<'_> {
+        let mut state = self.state.lock().unwrap();
+        state.token = token;
+        state.progress = 0;
+        ProgressScope { relay: self }
+    }
+}
+
+impl ProgressObserver for ProgressRelay {
+    fn on_progress(&self, event: &ProgressEvent) {
+        let mut state = self.state.lock().unwrap();
+        let (Some(token), Some(sink)) = (state.token.clone(), state.sink.clone()) else {
+            return;
+        };
+        state.progress += 1;
+        let notification = json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "method": "notifications/progress",
+            "params": {
+                "progressToken": token,
+                "progress": state.progress,
+                "message": event.to_string(),
+            },
+        });
+        // The receiver only disappears when the server is shutting down.
+        let _ = sink.send(Outbound::Notification(notification));
+    }
+}
+
+pub(crate) struct ProgressScope<'a> {
+    relay: &'a ProgressRelay,
+}
+
+impl Drop for ProgressScope<'_> {
+    fn drop(&mut self) {
+        self.relay.state.lock().unwrap().token = None;
+    }
+}
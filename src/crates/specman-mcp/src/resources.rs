@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use specman::{ArtifactKind, DependencyQuery, ResourceHandle};
+
+use crate::error::McpError;
+use crate::session::SpecmanServices;
+
+const JSON_MIME: &str = "application/json";
+
+#[derive(Deserialize)]
+struct ReadParams {
+    uri: String,
+}
+
+/// Answers `resources/list` with every spec, implementation, and scratch pad handle.
+pub fn list_resources(services: &SpecmanServices) -> Result<Value, McpError> {
+    let workspace = services.workspace_paths()?;
+    let resources: Vec<Value> = ResourceHandle::enumerate_all(&workspace)?
+        .into_iter()
+        .map(|handle| {
+            json!({
+                "uri": handle.to_string(),
+                "name": handle.slug(),
+                "description": format!("{} front matter", kind_label(handle.kind())),
+                "mimeType": JSON_MIME,
+            })
+        })
+        .collect();
+    Ok(json!({ "resources": resources }))
+}
+
+/// Answers `resources/templates/list` with the read-only `/dependencies` aliases.
+pub fn list_resource_templates() -> Value {
+    let templates: Vec<Value> = [
+        ArtifactKind::Specification,
+        ArtifactKind::Implementation,
+        ArtifactKind::ScratchPad,
+    ]
+    .into_iter()
+    .map(|kind| {
+        let scheme = match kind {
+            ArtifactKind::Specification => "spec",
+            ArtifactKind::Implementation => "impl",
+            ArtifactKind::ScratchPad => "scratch",
+        };
+        json!({
+            "uriTemplate": format!("{scheme}://{{artifact}}{}", DependencyQuery::SUFFIX),
+            "name": format!("{scheme}-dependencies"),
+            "description": format!(
+                "Read-only upstream/downstream dependency tree for a {}",
+                kind_label(kind)
+            ),
+            "mimeType": JSON_MIME,
+        })
+    })
+    .collect();
+    json!({ "resourceTemplates": templates })
+}
+
+/// Answers `resources/read` with the handle's front matter serialized per the data model.
+pub fn read_resource(services: &SpecmanServices, params: &Value) -> Result<Value, McpError> {
+    let params: ReadParams = serde_json::from_value(params.clone())
+        .map_err(|err| McpError::InvalidParams(err.to_string()))?;
+    if let Some(query) = DependencyQuery::parse(&params.uri)
+        .map_err(|err| McpError::InvalidParams(err.to_string()))?
+    {
+        let tree = services.graph.dependency_tree_for_query(&query)?;
+        return Ok(json!({
+            "contents": [{
+                "uri": query.to_string(),
+                "mimeType": JSON_MIME,
+                "text": serde_json::to_string_pretty(&tree)?,
+            }]
+        }));
+    }
+    let handle = parse_handle(&params.uri, None)?;
+    let description = describe(services, &handle)?;
+    Ok(json!({
+        "contents": [{
+            "uri": handle.to_string(),
+            "mimeType": JSON_MIME,
+            "text": serde_json::to_string_pretty(&description)?,
+        }]
+    }))
+}
+
+/// Lists handles for a single artifact class.
+pub fn list(services: &SpecmanServices, kind: ArtifactKind) -> Result<Value, McpError> {
+    let workspace = services.workspace_paths()?;
+    let handles: Vec<Value> = ResourceHandle::enumerate(&workspace, kind)?
+        .into_iter()
+        .map(|handle| {
+            let path = handle.to_path(&workspace);
+            json!({
+                "handle": handle.to_string(),
+                "name": handle.slug(),
+                "path": workspace_relative(workspace.root(), &path),
+            })
+        })
+        .collect();
+    Ok(json!({ "handles": handles }))
+}
+
+/// Resolves a handle to its canonical path and parsed front matter.
+pub fn describe(services: &SpecmanServices, handle: &ResourceHandle) -> Result<Value, McpError> {
+    let workspace = services.workspace_paths()?;
+    let resolved = handle.resolve(&workspace)?;
+    Ok(json!({
+        "handle": resolved.handle,
+        "path": workspace_relative(workspace.root(), &resolved.path),
+        "front_matter": serde_json::to_value(&resolved.front_matter)?,
+    }))
+}
+
+/// Parses a client-supplied handle. When `expected` is set, bare names are accepted and
+/// handles for other artifact classes are rejected.
+pub fn parse_handle(raw: &str, expected: Option<ArtifactKind>) -> Result<ResourceHandle, McpError> {
+    let invalid = |err: specman::SpecmanError| McpError::InvalidParams(err.to_string());
+    let handle = match (ResourceHandle::parse(raw).map_err(invalid)?, expected) {
+        (Some(handle), _) => handle,
+        (None, Some(kind)) => ResourceHandle::new(kind, raw).map_err(invalid)?,
+        (None, None) => {
+            return Err(McpError::InvalidParams(format!(
+                "{raw} is not a spec://, impl://, or scratch:// handle"
+            )));
+        }
+    };
+
+    if let Some(kind) = expected
+        && handle.kind() != kind
+    {
+        return Err(McpError::InvalidParams(format!(
+            "{handle} is not a {} handle",
+            kind_label(kind)
+        )));
+    }
+    Ok(handle)
+}
+
+fn kind_label(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Specification => "specification",
+        ArtifactKind::Implementation => "implementation",
+        ArtifactKind::ScratchPad => "scratch pad",
+    }
+}
+
+fn workspace_relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
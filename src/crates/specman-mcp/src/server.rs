@@ -0,0 +1,298 @@
+use std::any::Any;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::McpError;
+use crate::progress::Outbound;
+use crate::protocol::{
+    INTERNAL_ERROR, INVALID_REQUEST, JSONRPC_VERSION, JsonRpcError, JsonRpcRequest,
+    JsonRpcResponse, PARSE_ERROR, negotiate_protocol_version,
+};
+use crate::session::{ClientInfo, McpWorkspaceSession, SpecmanServices};
+use crate::{resources, tools};
+
+pub const SERVER_NAME: &str = "specman-mcp";
+pub const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// STDIO MCP server. Messages are newline-delimited JSON-RPC objects; the loop ends when
+/// the client closes the input stream or after a `shutdown` request has been answered.
+pub struct McpServer {
+    services: SpecmanServices,
+    session: Option<McpWorkspaceSession>,
+    shutdown_requested: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeParams {
+    #[serde(default)]
+    protocol_version: Option<String>,
+    #[serde(default)]
+    client_info: Option<ClientInfo>,
+}
+
+#[derive(Deserialize)]
+struct ToolCallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+    #[serde(default, rename = "_meta")]
+    meta: RequestMeta,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestMeta {
+    #[serde(default)]
+    progress_token: Option<Value>,
+}
+
+impl McpServer {
+    pub fn new(services: SpecmanServices) -> Self {
+        Self {
+            services,
+            session: None,
+            shutdown_requested: false,
+        }
+    }
+
+    /// Returns the negotiated session once `initialize` has completed.
+    pub fn session(&self) -> Option<&McpWorkspaceSession> {
+        self.session.as_ref()
+    }
+
+    /// Reads requests from `reader` and writes one response line per request to `writer`
+    /// until EOF or shutdown. Each request runs on a scoped worker thread so progress
+    /// notifications can be written while it is still in flight.
+    pub fn serve<R: BufRead, W: Write>(&mut self, reader: R, mut writer: W) -> io::Result<()> {
+        let (sender, receiver) = mpsc::channel();
+        self.services.progress.attach(sender.clone());
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = thread::scope(|scope| -> io::Result<Option<JsonRpcResponse>> {
+                let sender = sender.clone();
+                let server = &mut *self;
+                let raw = line.as_str();
+                scope.spawn(move || {
+                    let outcome =
+                        panic::catch_unwind(AssertUnwindSafe(|| server.handle_message(raw)));
+                    let _ = sender.send(Outbound::Finished(outcome));
+                });
+                loop {
+                    match receiver.recv().map_err(io::Error::other)? {
+                        Outbound::Notification(notification) => {
+                            write_line(&mut writer, &notification)?
+                        }
+                        Outbound::Finished(outcome) => {
+                            return Ok(outcome.unwrap_or_else(|payload| {
+                                panic_response(&line, payload.as_ref())
+                            }));
+                        }
+                    }
+                }
+            })?;
+            if let Some(response) = response {
+                write_line(&mut writer, &response)?;
+            }
+            if self.shutdown_requested {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a single raw JSON-RPC message, returning the response to emit (if any).
+    pub fn handle_message(&mut self, raw: &str) -> Option<JsonRpcResponse> {
+        let value: Value = match serde_json::from_str(raw) {
+            Ok(value) => value,
+            Err(err) => {
+                return Some(JsonRpcResponse::failure(
+                    Value::Null,
+                    JsonRpcError {
+                        code: PARSE_ERROR,
+                        message: format!("parse error: {err}"),
+                        data: None,
+                    },
+                ));
+            }
+        };
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(JsonRpcResponse::failure(
+                    id,
+                    JsonRpcError {
+                        code: INVALID_REQUEST,
+                        message: format!("invalid request: {err}"),
+                        data: None,
+                    },
+                ));
+            }
+        };
+
+        if request.jsonrpc != JSONRPC_VERSION {
+            let err = McpError::InvalidRequest(format!(
+                "unsupported jsonrpc version {}",
+                request.jsonrpc
+            ));
+            return (!request.is_notification())
+                .then(|| JsonRpcResponse::failure(id, err.to_json_rpc()));
+        }
+
+        if request.is_notification() {
+            self.handle_notification(&request);
+            return None;
+        }
+
+        Some(match self.dispatch(&request) {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(err) => JsonRpcResponse::failure(id, err.to_json_rpc()),
+        })
+    }
+
+    fn handle_notification(&mut self, request: &JsonRpcRequest) {
+        // `notifications/initialized` and `notifications/cancelled` need no action because
+        // every request is processed synchronously; unknown notifications are ignored per
+        // JSON-RPC.
+        if request.method == "exit" {
+            self.shutdown_requested = true;
+        }
+    }
+
+    fn dispatch(&mut self, request: &JsonRpcRequest) -> Result<Value, McpError> {
+        match request.method.as_str() {
+            "initialize" => self.initialize(&request.params),
+            "ping" => Ok(json!({})),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(json!({}))
+            }
+            method => {
+                if self.session.is_none() {
+                    return Err(McpError::NotInitialized);
+                }
+                match method {
+                    "tools/list" => Ok(json!({ "tools": tools::tool_definitions() })),
+                    "tools/call" => self.call_tool(&request.params),
+                    "resources/list" => resources::list_resources(&self.services),
+                    "resources/templates/list" => Ok(resources::list_resource_templates()),
+                    "resources/read" => resources::read_resource(&self.services, &request.params),
+                    other => Err(McpError::MethodNotFound(other.to_string())),
+                }
+            }
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) -> Result<Value, McpError> {
+        let params: InitializeParams = serde_json::from_value(params.clone())
+            .map_err(|err| McpError::InvalidParams(err.to_string()))?;
+        let protocol_version = negotiate_protocol_version(params.protocol_version.as_deref());
+        let workspace = self.services.workspace_paths()?;
+        self.session = Some(McpWorkspaceSession {
+            protocol_version: protocol_version.to_string(),
+            client: params.client_info,
+            workspace_root: workspace.root().to_path_buf(),
+        });
+
+        Ok(json!({
+            "protocolVersion": protocol_version,
+            "capabilities": {
+                "tools": { "listChanged": false },
+                "resources": { "subscribe": false, "listChanged": false },
+            },
+            "serverInfo": {
+                "name": SERVER_NAME,
+                "version": SERVER_VERSION,
+            },
+            "instructions": format!(
+                "SpecMan workspace at {}. Paths are workspace-relative.",
+                workspace.root().display()
+            ),
+        }))
+    }
+
+    /// Runs a tool and wraps the outcome in an MCP `CallToolResult`. Library failures are
+    /// reported as tool errors (`isError: true`) so clients can show them to the model;
+    /// malformed calls stay JSON-RPC errors.
+    fn call_tool(&mut self, params: &Value) -> Result<Value, McpError> {
+        let params: ToolCallParams = serde_json::from_value(params.clone())
+            .map_err(|err| McpError::InvalidParams(err.to_string()))?;
+        let _progress = self.services.progress.begin(params.meta.progress_token);
+        match tools::call_tool(&self.services, &params.name, params.arguments) {
+            Ok(structured) => Ok(json!({
+                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&structured)? }],
+                "structuredContent": structured,
+                "isError": false,
+            })),
+            Err(McpError::Specman(err)) => Ok(json!({
+                "content": [{ "type": "text", "text": err.to_string() }],
+                "structuredContent": { "error": crate::error::specman_error_data(&err) },
+                "isError": true,
+            })),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn write_line<W: Write>(writer: &mut W, message: &impl Serialize) -> io::Result<()> {
+    let encoded = serde_json::to_string(message).map_err(io::Error::other)?;
+    writeln!(writer, "{encoded}")?;
+    writer.flush()
+}
+
+/// Answers a request whose handler panicked with an `INTERNAL_ERROR`, so one bad request
+/// does not take the whole server down. Notifications still get no response.
+fn panic_response(raw: &str, payload: &(dyn Any + Send)) -> Option<JsonRpcResponse> {
+    let id = serde_json::from_str::<Value>(raw)
+        .ok()
+        .and_then(|value| value.get("id").cloned())?;
+    let detail = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    Some(JsonRpcResponse::failure(
+        id,
+        JsonRpcError {
+            code: INTERNAL_ERROR,
+            message: format!("internal error: request handler panicked: {detail}"),
+            data: None,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handler_panics_become_internal_errors_for_that_request() {
+        let payload = panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        let response = panic_response(
+            r#"{"jsonrpc":"2.0","id":7,"method":"tools/call"}"#,
+            payload.as_ref(),
+        )
+        .expect("requests get a response");
+        assert_eq!(response.id, json!(7));
+        let error = response.error.unwrap();
+        assert_eq!(error.code, INTERNAL_ERROR);
+        assert!(error.message.contains("boom"), "{}", error.message);
+
+        assert!(
+            panic_response(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                payload.as_ref()
+            )
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use specman::dependency_tree::DependencyInventory;
+use specman::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator, WorkspacePaths};
+use specman::{
+    CachingContentFetcher, DefaultLifecycleController, DependencyGraphServices, FetchMode,
+    MarkdownTemplateEngine, MetadataMutator, SpecmanError, TemplateCatalog, WorkspacePersistence,
+};
+
+use crate::progress::ProgressRelay;
+
+pub type WorkspaceHandle = Arc<FilesystemWorkspaceLocator>;
+pub type GraphServices = DependencyGraphServices<WorkspaceHandle>;
+
+/// Library services shared by every tool call in a session. The MCP layer only
+/// translates requests; all behavior lives behind these handles (see
+/// spec/specman-mcp/spec.md#concept-specman-capability-parity).
+pub struct SpecmanServices {
+    pub workspace: WorkspaceHandle,
+    pub graph: Arc<GraphServices>,
+    pub persistence: Arc<WorkspacePersistence<WorkspaceHandle>>,
+    pub lifecycle: DefaultLifecycleController<Arc<GraphServices>, MarkdownTemplateEngine>,
+    pub metadata: MetadataMutator<WorkspaceHandle>,
+    pub(crate) progress: Arc<ProgressRelay>,
+}
+
+impl SpecmanServices {
+    /// Wires the default service stack for the workspace discovered from `locator`,
+    /// failing fast when no `.specman` folder can be found.
+    pub fn new(locator: FilesystemWorkspaceLocator) -> Result<Self, SpecmanError> {
+        Self::with_fetch_mode(locator, FetchMode::Online)
+    }
+
+    /// Like [`SpecmanServices::new`], but resolves `https://` dependencies through the
+    /// workspace's remote cache in `fetch_mode`, matching the CLI.
+    pub fn with_fetch_mode(
+        locator: FilesystemWorkspaceLocator,
+        fetch_mode: FetchMode,
+    ) -> Result<Self, SpecmanError> {
+        let workspace = Arc::new(locator);
+        let paths = workspace.workspace()?;
+        let graph = Arc::new(DependencyGraphServices::with_fetcher(
+            workspace.clone(),
+            Arc::new(CachingContentFetcher::new(&paths, fetch_mode)),
+        ));
+        Ok(Self::from_parts(workspace, graph))
+    }
+
+    /// Builds the service stack around an existing graph service, which lets callers
+    /// supply a custom content fetcher. The graph's progress observer is replaced so
+    /// traversals can be reported to MCP clients.
+    pub fn from_parts(workspace: WorkspaceHandle, graph: Arc<GraphServices>) -> Self {
+        let progress = Arc::new(ProgressRelay::default());
+        graph.set_progress_observer(progress.clone());
+        let persistence = Arc::new(WorkspacePersistence::with_inventory(
+            workspace.clone(),
+            graph.clone() as Arc<dyn DependencyInventory>,
+        ));
+        let lifecycle = DefaultLifecycleController::new(graph.clone(), MarkdownTemplateEngine);
+        let metadata = MetadataMutator::new(workspace.clone());
+        Self {
+            workspace,
+            graph,
+            persistence,
+            lifecycle,
+            metadata,
+            progress,
+        }
+    }
+
+    pub fn workspace_paths(&self) -> Result<WorkspacePaths, SpecmanError> {
+        self.workspace.workspace()
+    }
+
+    /// Template catalogs are cheap to build and re-read the workspace on every
+    /// resolution, so each call gets a fresh one bound to the current paths.
+    pub fn templates(&self) -> Result<TemplateCatalog, SpecmanError> {
+        Ok(TemplateCatalog::new(self.workspace_paths()?))
+    }
+}
+
+/// Client identity reported during `initialize`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Negotiated MCP session bound to a single SpecMan workspace
+/// (spec/specman-mcp/spec.md#entity-mcpworkspacesession).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct McpWorkspaceSession {
+    pub protocol_version: String,
+    pub client: Option<ClientInfo>,
+    pub workspace_root: PathBuf,
+}
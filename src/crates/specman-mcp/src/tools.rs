@@ -0,0 +1,560 @@
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use specman::capability::{
+    self, ArtifactPersistOutput, ArtifactRemovalOutput, ArtifactTargetInput, DeletionInput,
+    DependencyAdditionInput, DependencyTreeInput, MetadataMutationOutput, NoInput,
+    ReferenceAdditionInput, TemplatePointerInput, TemplateScenarioInput, WorkspaceDiscoveryOutput,
+};
+use specman::dependency_tree::{validate_scratch_target, validate_workspace_reference};
+use specman::front_matter::{self, ScratchWorkType};
+use specman::scratchpad;
+use specman::template_tokens::{ContractTokenProvider, TokenProvider, TokenRequest};
+use specman::{
+    ArtifactId, ArtifactKind, CapabilityDescriptor, CapabilityRegistry, LifecycleController,
+    MarkdownTemplateEngine, MetadataMutationRequest, PromptCatalog, PromptRequest,
+    ReferenceAddition, RenderedTemplate, ResourceHandle, TemplateEngine, TemplateScenario,
+};
+
+use crate::error::McpError;
+use crate::resources;
+use crate::session::SpecmanServices;
+
+/// Tool metadata advertised through `tools/list`. Schemas come from the capability
+/// registry rather than being maintained here.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDefinition {
+    pub name: &'static str,
+    pub description: String,
+    pub input_schema: Value,
+    #[serde(rename = "_meta")]
+    pub meta: Value,
+}
+
+const MCP_SPEC: &str = "spec/specman-mcp/spec.md";
+
+/// Tool names paired with the capability each one invokes.
+const TOOLS: &[(&str, &str)] = &[
+    ("workspace_discover", capability::WORKSPACE_DISCOVER),
+    ("list_specifications", LIST_SPECIFICATIONS),
+    ("list_implementations", LIST_IMPLEMENTATIONS),
+    ("list_scratch_pads", LIST_SCRATCH_PADS),
+    ("describe_specification", DESCRIBE_SPECIFICATION),
+    ("describe_implementation", DESCRIBE_IMPLEMENTATION),
+    ("describe_scratch_pad", DESCRIBE_SCRATCH_PAD),
+    ("dependency_tree", capability::DEPENDENCY_TREE),
+    ("template_resolve", capability::TEMPLATE_RESOLVE),
+    ("template_set_pointer", capability::TEMPLATE_SET_POINTER),
+    (
+        "template_remove_pointer",
+        capability::TEMPLATE_REMOVE_POINTER,
+    ),
+    ("prompt_render", capability::PROMPT_RENDER),
+    ("lifecycle_create_specification", CREATE_SPECIFICATION),
+    ("lifecycle_create_implementation", CREATE_IMPLEMENTATION),
+    ("lifecycle_create_scratch_pad", CREATE_SCRATCH_PAD),
+    (
+        "lifecycle_plan_deletion",
+        capability::LIFECYCLE_PLAN_DELETION,
+    ),
+    ("lifecycle_delete", capability::LIFECYCLE_DELETE),
+    (
+        "metadata_add_dependency",
+        capability::METADATA_ADD_DEPENDENCY,
+    ),
+    ("metadata_add_reference", capability::METADATA_ADD_REFERENCE),
+];
+
+const LIST_SPECIFICATIONS: &str = "specman.mcp.resources.list_specifications";
+const LIST_IMPLEMENTATIONS: &str = "specman.mcp.resources.list_implementations";
+const LIST_SCRATCH_PADS: &str = "specman.mcp.resources.list_scratch_pads";
+const DESCRIBE_SPECIFICATION: &str = "specman.mcp.resources.describe_specification";
+const DESCRIBE_IMPLEMENTATION: &str = "specman.mcp.resources.describe_implementation";
+const DESCRIBE_SCRATCH_PAD: &str = "specman.mcp.resources.describe_scratch_pad";
+const CREATE_SPECIFICATION: &str = "specman.mcp.lifecycle.create_specification";
+const CREATE_IMPLEMENTATION: &str = "specman.mcp.lifecycle.create_implementation";
+const CREATE_SCRATCH_PAD: &str = "specman.mcp.lifecycle.create_scratch_pad";
+
+/// Core descriptors plus the resource-handle extensions this adapter contributes.
+pub fn capability_registry() -> CapabilityRegistry {
+    let mut registry = CapabilityRegistry::core();
+    let concept_ref = format!("{MCP_SPEC}#concept-specman-capability-parity");
+    for (id, summary) in [
+        (
+            LIST_SPECIFICATIONS,
+            "List specifications as spec:// resource handles.",
+        ),
+        (
+            LIST_IMPLEMENTATIONS,
+            "List implementations as impl:// resource handles.",
+        ),
+        (
+            LIST_SCRATCH_PADS,
+            "List scratch pads as scratch:// resource handles.",
+        ),
+    ] {
+        registry.register(CapabilityDescriptor::extension::<NoInput, Value>(
+            id,
+            concept_ref.clone(),
+            MCP_SPEC,
+            summary,
+        ));
+    }
+    for (id, summary) in [
+        (
+            DESCRIBE_SPECIFICATION,
+            "Return a specification's front matter by spec:// handle or name.",
+        ),
+        (
+            DESCRIBE_IMPLEMENTATION,
+            "Return an implementation's front matter by impl:// handle or name.",
+        ),
+        (
+            DESCRIBE_SCRATCH_PAD,
+            "Return a scratch pad's front matter by scratch:// handle or name.",
+        ),
+    ] {
+        registry.register(CapabilityDescriptor::extension::<HandleArgs, Value>(
+            id,
+            concept_ref.clone(),
+            MCP_SPEC,
+            summary,
+        ));
+    }
+    registry.register(CapabilityDescriptor::extension::<
+        CreateSpecificationArgs,
+        ArtifactPersistOutput,
+    >(
+        CREATE_SPECIFICATION,
+        concept_ref.clone(),
+        MCP_SPEC,
+        "Create a specification from the resolved specification template.",
+    ));
+    registry.register(CapabilityDescriptor::extension::<
+        CreateImplementationArgs,
+        ArtifactPersistOutput,
+    >(
+        CREATE_IMPLEMENTATION,
+        concept_ref.clone(),
+        MCP_SPEC,
+        "Create an implementation of a specification from the resolved implementation template.",
+    ));
+    registry.register(CapabilityDescriptor::extension::<
+        CreateScratchPadArgs,
+        ArtifactPersistOutput,
+    >(
+        CREATE_SCRATCH_PAD,
+        concept_ref,
+        MCP_SPEC,
+        "Create a scratch pad for a target artifact from the resolved scratch pad template.",
+    ));
+    registry
+}
+
+/// Enumerates every tool this adapter exposes, one or more per SpecMan Core concept.
+pub fn tool_definitions() -> Vec<ToolDefinition> {
+    let registry = capability_registry();
+    TOOLS
+        .iter()
+        .filter_map(|(name, id)| {
+            registry.get(id).map(|descriptor| ToolDefinition {
+                name,
+                description: descriptor.summary.clone(),
+                input_schema: descriptor.input_schema.clone(),
+                meta: json!({
+                    "specman/capability": descriptor.id,
+                    "specman/conceptRef": descriptor.concept_ref,
+                    "specman/coreVersion": descriptor.specman_core_version,
+                }),
+            })
+        })
+        .collect()
+}
+
+/// Executes a tool by name. Argument problems surface as `InvalidParams`; failures
+/// raised by the library surface as `McpError::Specman` so the server can report
+/// them as tool errors.
+pub fn call_tool(
+    services: &SpecmanServices,
+    name: &str,
+    arguments: Value,
+) -> Result<Value, McpError> {
+    match name {
+        "workspace_discover" => workspace_discover(services),
+        "list_specifications" => resources::list(services, ArtifactKind::Specification),
+        "list_implementations" => resources::list(services, ArtifactKind::Implementation),
+        "list_scratch_pads" => resources::list(services, ArtifactKind::ScratchPad),
+        "describe_specification" => describe(
+            services,
+            parse_args(arguments)?,
+            ArtifactKind::Specification,
+        ),
+        "describe_implementation" => describe(
+            services,
+            parse_args(arguments)?,
+            ArtifactKind::Implementation,
+        ),
+        "describe_scratch_pad" => {
+            describe(services, parse_args(arguments)?, ArtifactKind::ScratchPad)
+        }
+        "dependency_tree" => dependency_tree(services, parse_args(arguments)?),
+        "template_resolve" => template_resolve(services, parse_args(arguments)?),
+        "template_set_pointer" => template_set_pointer(services, parse_args(arguments)?),
+        "template_remove_pointer" => template_remove_pointer(services, parse_args(arguments)?),
+        "prompt_render" => prompt_render(services, parse_args(arguments)?),
+        "lifecycle_create_specification" => create_specification(services, parse_args(arguments)?),
+        "lifecycle_create_implementation" => {
+            create_implementation(services, parse_args(arguments)?)
+        }
+        "lifecycle_create_scratch_pad" => create_scratch_pad(services, parse_args(arguments)?),
+        "lifecycle_plan_deletion" => lifecycle_plan_deletion(services, parse_args(arguments)?),
+        "lifecycle_delete" => lifecycle_delete(services, parse_args(arguments)?),
+        "metadata_add_dependency" => metadata_add_dependency(services, parse_args(arguments)?),
+        "metadata_add_reference" => metadata_add_reference(services, parse_args(arguments)?),
+        other => Err(McpError::InvalidParams(format!("unknown tool: {other}"))),
+    }
+}
+
+/// Input for the describe tools: a resource handle or bare artifact name.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct HandleArgs {
+    handle: String,
+}
+
+/// Input for `lifecycle_create_specification`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CreateSpecificationArgs {
+    /// Specification slug; the document is written to `spec/{name}/spec.md`.
+    name: String,
+    #[serde(default = "default_version")]
+    version: String,
+    /// Dependency locators added to the front matter.
+    #[serde(default)]
+    dependencies: Vec<String>,
+    /// Instructions for the template's `{{arguments}}` token.
+    #[serde(default)]
+    arguments: String,
+}
+
+/// Input for `lifecycle_create_implementation`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CreateImplementationArgs {
+    /// Implementation slug; the document is written to `impl/{name}/impl.md`.
+    name: String,
+    /// Implemented specification, relative to the implementation document or an HTTPS URL.
+    spec: String,
+    /// Primary language identifier, e.g. `rust@1.91`.
+    language: String,
+    /// Source location; defaults to `src/{name}`.
+    #[serde(default)]
+    location: Option<String>,
+    /// Instructions for the template's `{{arguments}}` token.
+    #[serde(default)]
+    arguments: String,
+}
+
+/// Input for `lifecycle_create_scratch_pad`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CreateScratchPadArgs {
+    /// Scratch pad slug; the document is written to `.specman/scratchpad/{name}/scratch.md`.
+    name: String,
+    /// Artifact the work targets, as a workspace-relative path or HTTPS URL.
+    target: String,
+    work_type: ScratchWorkType,
+    branch: String,
+    /// Instructions for the template's `{{arguments}}` token.
+    #[serde(default)]
+    arguments: String,
+}
+
+fn default_version() -> String {
+    "1.0.0".to_string()
+}
+
+fn parse_args<T: DeserializeOwned>(arguments: Value) -> Result<T, McpError> {
+    let arguments = if arguments.is_null() {
+        json!({})
+    } else {
+        arguments
+    };
+    serde_json::from_value(arguments).map_err(|err| McpError::InvalidParams(err.to_string()))
+}
+
+fn workspace_discover(services: &SpecmanServices) -> Result<Value, McpError> {
+    let paths = services.workspace_paths()?;
+    Ok(serde_json::to_value(WorkspaceDiscoveryOutput::from(
+        &paths,
+    ))?)
+}
+
+fn describe(
+    services: &SpecmanServices,
+    args: HandleArgs,
+    kind: ArtifactKind,
+) -> Result<Value, McpError> {
+    let handle = resources::parse_handle(&args.handle, Some(kind))?;
+    resources::describe(services, &handle)
+}
+
+fn dependency_tree(
+    services: &SpecmanServices,
+    args: DependencyTreeInput,
+) -> Result<Value, McpError> {
+    let tree = services.graph.dependency_tree_from_locator(&args.locator)?;
+    Ok(serde_json::to_value(tree)?)
+}
+
+fn template_resolve(
+    services: &SpecmanServices,
+    args: TemplateScenarioInput,
+) -> Result<Value, McpError> {
+    let resolved = services.templates()?.resolve(args.scenario)?;
+    Ok(serde_json::to_value(resolved)?)
+}
+
+fn template_set_pointer(
+    services: &SpecmanServices,
+    args: TemplatePointerInput,
+) -> Result<Value, McpError> {
+    let resolved = services
+        .templates()?
+        .set_pointer(args.scenario, &args.locator)?;
+    Ok(serde_json::to_value(resolved)?)
+}
+
+fn template_remove_pointer(
+    services: &SpecmanServices,
+    args: TemplateScenarioInput,
+) -> Result<Value, McpError> {
+    let resolved = services.templates()?.remove_pointer(args.scenario)?;
+    Ok(serde_json::to_value(resolved)?)
+}
+
+fn prompt_render(services: &SpecmanServices, args: PromptRequest) -> Result<Value, McpError> {
+    let prompt =
+        PromptCatalog::new(services.workspace_paths()?).render(&args, services.graph.as_ref())?;
+    Ok(serde_json::to_value(prompt)?)
+}
+
+fn create_specification(
+    services: &SpecmanServices,
+    args: CreateSpecificationArgs,
+) -> Result<Value, McpError> {
+    let (artifact, folder) = new_artifact(services, ArtifactKind::Specification, &args.name)?;
+    validate_locators(services, &folder, &args.dependencies)?;
+    let mut rendered = render_template(
+        services,
+        TemplateScenario::Specification,
+        &artifact,
+        args.dependencies.clone(),
+        args.arguments,
+    )?;
+    rendered.body = front_matter::update_spec_document(
+        &rendered.body,
+        &artifact.name,
+        &args.version,
+        &args.dependencies,
+    )?;
+    persist_artifact(services, &artifact, &rendered)
+}
+
+fn create_implementation(
+    services: &SpecmanServices,
+    args: CreateImplementationArgs,
+) -> Result<Value, McpError> {
+    let (artifact, folder) = new_artifact(services, ArtifactKind::Implementation, &args.name)?;
+    validate_locators(services, &folder, std::slice::from_ref(&args.spec))?;
+    let location = args
+        .location
+        .unwrap_or_else(|| format!("src/{}", artifact.name));
+    let mut rendered = render_template(
+        services,
+        TemplateScenario::Implementation,
+        &artifact,
+        vec![args.spec.clone()],
+        args.arguments,
+    )?;
+    rendered.body = front_matter::update_impl_document(
+        &rendered.body,
+        &artifact.name,
+        &args.spec,
+        &args.language,
+        &location,
+    )?;
+    persist_artifact(services, &artifact, &rendered)
+}
+
+fn create_scratch_pad(
+    services: &SpecmanServices,
+    args: CreateScratchPadArgs,
+) -> Result<Value, McpError> {
+    let (artifact, folder) = new_artifact(services, ArtifactKind::ScratchPad, &args.name)?;
+    let workspace = services.workspace_paths()?;
+    validate_scratch_target(&args.target, &folder, &workspace)?;
+    scratchpad::validate_work_type(&args.work_type, &args.target, &folder, &workspace)?;
+    let mut rendered = render_template(
+        services,
+        TemplateScenario::ScratchPad,
+        &artifact,
+        vec![args.target.clone()],
+        args.arguments,
+    )?;
+    rendered.body = front_matter::update_scratch_document(
+        &rendered.body,
+        &args.target,
+        &args.branch,
+        args.work_type.kind().as_str(),
+    )?;
+    persist_artifact(services, &artifact, &rendered)
+}
+
+/// Validates the slug and refuses to overwrite an artifact that already exists. Returns the
+/// folder the artifact will be written to.
+fn new_artifact(
+    services: &SpecmanServices,
+    kind: ArtifactKind,
+    name: &str,
+) -> Result<(ArtifactId, PathBuf), McpError> {
+    let artifact = ResourceHandle::new(kind, name)?.artifact_id();
+    let workspace = services.workspace_paths()?;
+    let folder = match kind {
+        ArtifactKind::Specification => workspace.spec_dir(),
+        ArtifactKind::Implementation => workspace.impl_dir(),
+        ArtifactKind::ScratchPad => workspace.scratchpad_dir(),
+    }
+    .join(&artifact.name);
+    if folder.exists() {
+        return Err(McpError::InvalidParams(format!(
+            "{} already exists",
+            folder
+                .strip_prefix(workspace.root())
+                .unwrap_or(&folder)
+                .display()
+        )));
+    }
+    Ok((artifact, folder))
+}
+
+/// Rejects locators a new artifact in `folder` could not resolve, before anything is written.
+fn validate_locators(
+    services: &SpecmanServices,
+    folder: &Path,
+    locators: &[String],
+) -> Result<(), McpError> {
+    let workspace = services.workspace_paths()?;
+    for locator in locators {
+        validate_workspace_reference(locator, folder, &workspace)?;
+    }
+    Ok(())
+}
+
+/// Renders the scenario's resolved template with the contract tokens for `artifact`.
+fn render_template(
+    services: &SpecmanServices,
+    scenario: TemplateScenario,
+    artifact: &ArtifactId,
+    declared: Vec<String>,
+    arguments: String,
+) -> Result<RenderedTemplate, McpError> {
+    let resolved = services.templates()?.resolve(scenario)?;
+    let mut request = TokenRequest::new(artifact.kind, &artifact.name);
+    request.declared = declared;
+    request.arguments = arguments;
+    let tokens = ContractTokenProvider::new(services.graph.clone()).tokens(&request)?;
+    let mut rendered = MarkdownTemplateEngine.render(&resolved.descriptor, &tokens)?;
+    rendered.provenance = Some(resolved.provenance);
+    Ok(rendered)
+}
+
+fn persist_artifact(
+    services: &SpecmanServices,
+    artifact: &ArtifactId,
+    rendered: &RenderedTemplate,
+) -> Result<Value, McpError> {
+    let persisted = services.persistence.persist(artifact, rendered)?;
+    Ok(serde_json::to_value(ArtifactPersistOutput::from(
+        &persisted,
+    ))?)
+}
+
+fn lifecycle_plan_deletion(
+    services: &SpecmanServices,
+    args: ArtifactTargetInput,
+) -> Result<Value, McpError> {
+    let plan = services.lifecycle.plan_deletion(args.target)?;
+    Ok(serde_json::to_value(plan)?)
+}
+
+fn lifecycle_delete(services: &SpecmanServices, args: DeletionInput) -> Result<Value, McpError> {
+    let removed = services.lifecycle.execute_deletion(
+        args.target,
+        None,
+        services.persistence.as_ref(),
+        args.force,
+    )?;
+    Ok(serde_json::to_value(ArtifactRemovalOutput::from(&removed))?)
+}
+
+fn metadata_add_dependency(
+    services: &SpecmanServices,
+    args: DependencyAdditionInput,
+) -> Result<Value, McpError> {
+    let mut request = MetadataMutationRequest::new(resolve_workspace_file(services, &args.path)?)
+        .persist(args.persist);
+    request.add_dependencies.push(args.locator);
+    mutate_metadata(services, request)
+}
+
+fn metadata_add_reference(
+    services: &SpecmanServices,
+    args: ReferenceAdditionInput,
+) -> Result<Value, McpError> {
+    let mut reference = ReferenceAddition::new(args.locator);
+    if let Some(reference_type) = args.reference_type {
+        reference = reference.reference_type(reference_type);
+    }
+    if let Some(optional) = args.optional {
+        reference = reference.optional(optional);
+    }
+    let mut request = MetadataMutationRequest::new(resolve_workspace_file(services, &args.path)?)
+        .persist(args.persist);
+    request.add_references.push(reference);
+    mutate_metadata(services, request)
+}
+
+fn mutate_metadata(
+    services: &SpecmanServices,
+    request: MetadataMutationRequest,
+) -> Result<Value, McpError> {
+    let result = services.metadata.mutate(request)?;
+    if result.persisted.is_some() {
+        // Front matter edits change the graph, so later tree queries must not reuse the
+        // cached inventory.
+        services.graph.invalidate_inventory();
+    }
+    Ok(serde_json::to_value(MetadataMutationOutput::from(&result))?)
+}
+
+/// Resolves a client-supplied path against the workspace root. Resource handles pass through
+/// untouched so the library can resolve them (and reject read-only `/dependencies` queries);
+/// absolute paths are rejected so clients cannot address files outside the active workspace.
+fn resolve_workspace_file(services: &SpecmanServices, raw: &str) -> Result<PathBuf, McpError> {
+    if raw.contains("://") {
+        return Ok(PathBuf::from(raw));
+    }
+    let candidate = Path::new(raw);
+    if candidate.is_absolute() {
+        return Err(McpError::InvalidParams(format!(
+            "path must be workspace-relative: {raw}"
+        )));
+    }
+    Ok(services.workspace_paths()?.root().join(candidate))
+}
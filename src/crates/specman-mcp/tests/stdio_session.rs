@@ -0,0 +1,650 @@
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+
+use serde_json::{Value, json};
+use specman::FilesystemWorkspaceLocator;
+use specman_mcp::{McpServer, SUPPORTED_PROTOCOL_VERSIONS, SpecmanServices};
+use tempfile::TempDir;
+
+#[test]
+fn initialize_negotiates_protocol_and_lists_tools() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, "2025-03-26"),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        ],
+    )?;
+
+    assert_eq!(responses.len(), 2, "notifications must not be answered");
+    assert_eq!(responses[0]["id"], json!(1));
+    assert_eq!(
+        responses[0]["result"]["protocolVersion"],
+        json!("2025-03-26")
+    );
+    assert_eq!(
+        responses[0]["result"]["serverInfo"]["name"],
+        json!("specman-mcp")
+    );
+
+    let tools = responses[1]["result"]["tools"].as_array().expect("tools");
+    let names: Vec<&str> = tools
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    for expected in [
+        "workspace_discover",
+        "dependency_tree",
+        "template_resolve",
+        "prompt_render",
+        "lifecycle_create_specification",
+        "lifecycle_create_implementation",
+        "lifecycle_create_scratch_pad",
+        "lifecycle_delete",
+        "metadata_add_dependency",
+    ] {
+        assert!(names.contains(&expected), "missing tool {expected}");
+    }
+    assert!(
+        tools
+            .iter()
+            .all(|tool| tool["inputSchema"]["type"] == "object")
+    );
+
+    let registry = specman_mcp::capability_registry();
+    for tool in tools {
+        let id = tool["_meta"]["specman/capability"].as_str().unwrap();
+        let descriptor = registry.get(id).expect("tool backed by a descriptor");
+        assert_eq!(tool["inputSchema"], descriptor.input_schema);
+    }
+    let delete = tools
+        .iter()
+        .find(|tool| tool["name"] == "lifecycle_delete")
+        .unwrap();
+    assert_eq!(
+        delete["_meta"]["specman/capability"],
+        json!("specman.core.lifecycle_automation.delete")
+    );
+    assert_eq!(delete["inputSchema"]["required"], json!(["target"]));
+    Ok(())
+}
+
+#[test]
+fn initialize_offers_latest_revision_for_unknown_versions() -> Result<(), Box<dyn std::error::Error>>
+{
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(workspace.path(), &[initialize_request(1, "1999-01-01")])?;
+
+    assert_eq!(
+        responses[0]["result"]["protocolVersion"],
+        json!(SUPPORTED_PROTOCOL_VERSIONS[0])
+    );
+    Ok(())
+}
+
+#[test]
+fn requests_before_initialize_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[json!({ "jsonrpc": "2.0", "id": 7, "method": "tools/list" })],
+    )?;
+
+    assert_eq!(responses[0]["id"], json!(7));
+    assert_eq!(responses[0]["error"]["code"], json!(-32002));
+    Ok(())
+}
+
+#[test]
+fn dependency_tree_tool_delegates_to_graph_services() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(2, "dependency_tree", json!({ "locator": "spec://alpha" })),
+        ],
+    )?;
+
+    let result = &responses[1]["result"];
+    assert_eq!(result["isError"], json!(false));
+    let tree = &result["structuredContent"];
+    assert_eq!(tree["root"]["id"]["name"], json!("alpha"));
+    let downstream = tree["downstream"].as_array().expect("downstream edges");
+    assert!(
+        downstream
+            .iter()
+            .any(|edge| edge["from"]["id"]["name"] == "alpha-rust")
+    );
+    Ok(())
+}
+
+#[test]
+fn prompt_render_tool_fills_context_from_the_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(
+                2,
+                "prompt_render",
+                json!({
+                    "kind": "scratch-fix",
+                    "target": "impl://alpha-rust",
+                    "arguments": "Saving drops the last line.",
+                }),
+            ),
+        ],
+    )?;
+
+    let result = &responses[1]["result"];
+    assert_eq!(result["isError"], json!(false));
+    let prompt = &result["structuredContent"];
+    assert_eq!(prompt["template"], json!("templates/scratch/fix.md"));
+    assert_eq!(prompt["work_type"], json!("Fix"));
+    assert_eq!(prompt["provenance"]["tier"], json!("EmbeddedDefault"));
+    let body = prompt["body"].as_str().expect("prompt body");
+    assert!(body.contains("- `impl/alpha-rust/impl.md`\n- `spec/alpha/spec.md`"));
+    assert!(body.contains("Saving drops the last line."));
+    assert!(
+        prompt["unresolved_tokens"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("scratch_name"))
+    );
+    Ok(())
+}
+
+#[test]
+fn tool_calls_with_progress_tokens_stream_progress() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut call = tool_call(
+        2,
+        "dependency_tree",
+        json!({ "locator": "impl://alpha-rust" }),
+    );
+    call["params"]["_meta"] = json!({ "progressToken": "tree-1" });
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            call,
+            tool_call(3, "dependency_tree", json!({ "locator": "spec://alpha" })),
+        ],
+    )?;
+
+    let progress: Vec<&Value> = responses
+        .iter()
+        .filter(|message| message["method"] == "notifications/progress")
+        .collect();
+    assert!(!progress.is_empty(), "expected progress notifications");
+    assert!(
+        progress
+            .iter()
+            .all(|note| note["params"]["progressToken"] == "tree-1")
+    );
+    let counters: Vec<u64> = progress
+        .iter()
+        .map(|note| note["params"]["progress"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counters, (1..=counters.len() as u64).collect::<Vec<_>>());
+    assert!(
+        progress
+            .iter()
+            .any(|note| { note["params"]["message"] == "found edge alpha-rust -> alpha" })
+    );
+
+    let response_index = |id: u64| responses.iter().position(|message| message["id"] == id);
+    let last_progress = responses
+        .iter()
+        .rposition(|message| message["method"] == "notifications/progress")
+        .unwrap();
+    assert!(last_progress < response_index(2).unwrap());
+    // Calls without a token stay silent.
+    assert_eq!(response_index(3), Some(responses.len() - 1));
+    assert_eq!(last_progress + 2, responses.len() - 1);
+    Ok(())
+}
+
+#[test]
+fn library_failures_surface_as_tool_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(
+                2,
+                "lifecycle_delete",
+                json!({ "target": { "kind": "Specification", "name": "alpha" } }),
+            ),
+            tool_call(3, "lifecycle_delete", json!({ "target": "alpha" })),
+        ],
+    )?;
+
+    let blocked = &responses[1]["result"];
+    assert_eq!(blocked["isError"], json!(true));
+    assert_eq!(
+        blocked["structuredContent"]["error"]["kind"],
+        json!("dependency")
+    );
+    assert_eq!(
+        blocked["structuredContent"]["error"]["code"],
+        json!("SM-DEP-UNRESOLVED")
+    );
+    assert!(workspace.path().join("spec/alpha/spec.md").is_file());
+
+    assert_eq!(responses[2]["error"]["code"], json!(-32602));
+    Ok(())
+}
+
+#[test]
+fn metadata_tool_persists_front_matter_updates() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    write_file(
+        workspace.path().join("spec/beta/spec.md"),
+        "---\nname: beta\nversion: \"1.0.0\"\n---\n# Beta\n",
+    )?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(
+                2,
+                "metadata_add_dependency",
+                json!({ "path": "spec/beta/spec.md", "locator": "../alpha/spec.md" }),
+            ),
+            tool_call(3, "dependency_tree", json!({ "locator": "spec://alpha" })),
+        ],
+    )?;
+
+    assert_eq!(
+        responses[1]["result"]["structuredContent"]["persisted_path"],
+        json!("spec/beta/spec.md")
+    );
+    let contents = fs::read_to_string(workspace.path().join("spec/beta/spec.md"))?;
+    assert!(contents.contains("../alpha/spec.md"));
+
+    let envelope = &responses[1]["result"]["structuredContent"]["envelope"];
+    assert_eq!(
+        envelope["capability"],
+        json!("specman.core.metadata_mutation.mutate")
+    );
+    assert_eq!(envelope["updated"], json!(["spec/beta/spec.md"]));
+    let audit_log = fs::read_to_string(workspace.path().join(".specman/audit/operations.ndjson"))?;
+    let logged: Value = serde_json::from_str(audit_log.lines().next().expect("audit entry"))?;
+    assert_eq!(&logged, envelope);
+
+    let downstream = responses[2]["result"]["structuredContent"]["downstream"]
+        .as_array()
+        .expect("downstream edges");
+    assert!(
+        downstream
+            .iter()
+            .any(|edge| edge["from"]["id"]["name"] == "beta")
+    );
+    Ok(())
+}
+
+#[test]
+fn lifecycle_create_tools_write_each_artifact_class() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(
+                2,
+                "lifecycle_create_specification",
+                json!({ "name": "gamma", "dependencies": ["../alpha/spec.md"] }),
+            ),
+            tool_call(
+                3,
+                "lifecycle_create_implementation",
+                json!({ "name": "gamma-rust", "spec": "../../spec/gamma/spec.md", "language": "rust@1.91" }),
+            ),
+            tool_call(
+                4,
+                "lifecycle_create_scratch_pad",
+                json!({
+                    "name": "gamma-feature",
+                    "target": "impl/alpha-rust/impl.md",
+                    "work_type": { "feat": {} },
+                    "branch": "gamma/feat",
+                }),
+            ),
+            tool_call(
+                5,
+                "lifecycle_create_specification",
+                json!({ "name": "gamma" }),
+            ),
+        ],
+    )?;
+
+    for (response, path) in responses[1..4].iter().zip([
+        "spec/gamma/spec.md",
+        "impl/gamma-rust/impl.md",
+        ".specman/scratchpad/gamma-feature/scratch.md",
+    ]) {
+        let output = &response["result"]["structuredContent"];
+        assert_eq!(output["path"], json!(path), "{response}");
+        assert_eq!(output["envelope"]["created"], json!([path]));
+    }
+
+    let spec = fs::read_to_string(workspace.path().join("spec/gamma/spec.md"))?;
+    assert!(spec.contains("name: gamma\n"), "{spec}");
+    assert!(spec.contains("../alpha/spec.md"), "{spec}");
+    let implementation = fs::read_to_string(workspace.path().join("impl/gamma-rust/impl.md"))?;
+    assert!(implementation.contains("spec: ../../spec/gamma/spec.md"));
+    assert!(implementation.contains("language: rust@1.91"));
+    let scratch = fs::read_to_string(
+        workspace
+            .path()
+            .join(".specman/scratchpad/gamma-feature/scratch.md"),
+    )?;
+    assert!(scratch.contains("target: impl/alpha-rust/impl.md"));
+    assert!(scratch.contains("branch: gamma/feat"));
+
+    let duplicate = &responses[4];
+    assert_eq!(duplicate["error"]["code"], json!(-32602));
+    assert!(
+        duplicate["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("spec/gamma already exists")
+    );
+    Ok(())
+}
+
+#[test]
+fn lifecycle_create_tools_reject_unresolvable_locators() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let bad = [
+        "spec://alpha/dependencies",
+        "http://evil.example/x.md",
+        "../../../../etc/passwd",
+    ];
+    let mut script = vec![initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0])];
+    for (id, locator) in (2..).zip(bad) {
+        script.push(tool_call(
+            id,
+            "lifecycle_create_specification",
+            json!({ "name": "gamma", "dependencies": ["../alpha/spec.md", locator] }),
+        ));
+    }
+    script.push(tool_call(
+        5,
+        "lifecycle_create_implementation",
+        json!({ "name": "gamma-rust", "spec": "http://evil.example/x.md", "language": "rust@1.91" }),
+    ));
+    script.push(tool_call(
+        6,
+        "lifecycle_create_scratch_pad",
+        json!({
+            "name": "gamma-feature",
+            "target": "../../../../etc/passwd",
+            "work_type": { "feat": {} },
+            "branch": "gamma/feat",
+        }),
+    ));
+    let responses = run_script(workspace.path(), &script)?;
+
+    for response in &responses[1..] {
+        assert_eq!(response["result"]["isError"], json!(true), "{response}");
+    }
+    for escaped in [&responses[3], &responses[5]] {
+        assert_eq!(
+            escaped["result"]["structuredContent"]["error"]["code"],
+            json!("SM-WS-ESCAPE")
+        );
+    }
+    assert!(!workspace.path().join("spec/gamma").exists());
+    assert!(!workspace.path().join("impl/gamma-rust").exists());
+    assert!(
+        !workspace
+            .path()
+            .join(".specman/scratchpad/gamma-feature")
+            .exists()
+    );
+    Ok(())
+}
+
+#[test]
+fn shutdown_stops_processing_further_input() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "ping" }),
+        ],
+    )?;
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[1]["id"], json!(2));
+    Ok(())
+}
+
+#[test]
+fn malformed_json_yields_parse_error() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let mut server = server_for(workspace.path())?;
+    let mut output = Vec::new();
+    server.serve(Cursor::new("{not json\n"), &mut output)?;
+
+    let response: Value = serde_json::from_slice(&output)?;
+    assert_eq!(response["error"]["code"], json!(-32700));
+    assert_eq!(response["id"], Value::Null);
+    Ok(())
+}
+
+#[test]
+fn resources_enumerate_and_read_handles() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "resources/read",
+                "params": { "uri": "impl://alpha-rust" },
+            }),
+        ],
+    )?;
+
+    assert!(responses[0]["result"]["capabilities"]["resources"].is_object());
+    let uris: Vec<&str> = responses[1]["result"]["resources"]
+        .as_array()
+        .expect("resources")
+        .iter()
+        .map(|resource| resource["uri"].as_str().unwrap())
+        .collect();
+    assert_eq!(uris, vec!["spec://alpha", "impl://alpha-rust"]);
+
+    let contents = &responses[2]["result"]["contents"][0];
+    assert_eq!(contents["uri"], json!("impl://alpha-rust"));
+    let body: Value = serde_json::from_str(contents["text"].as_str().unwrap())?;
+    assert_eq!(
+        body["front_matter"]["spec"],
+        json!("../../spec/alpha/spec.md")
+    );
+    assert_eq!(body["path"], json!("impl/alpha-rust/impl.md"));
+    Ok(())
+}
+
+#[test]
+fn list_and_describe_tools_cover_each_artifact_class() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(2, "list_specifications", json!({})),
+            tool_call(3, "describe_specification", json!({ "handle": "alpha" })),
+            tool_call(
+                4,
+                "describe_specification",
+                json!({ "handle": "impl://alpha-rust" }),
+            ),
+            tool_call(5, "list_scratch_pads", json!({})),
+        ],
+    )?;
+
+    let handles = &responses[1]["result"]["structuredContent"]["handles"];
+    assert_eq!(handles[0]["handle"], json!("spec://alpha"));
+    assert_eq!(handles[0]["path"], json!("spec/alpha/spec.md"));
+
+    let described = &responses[2]["result"]["structuredContent"];
+    assert_eq!(described["handle"], json!("spec://alpha"));
+    assert_eq!(described["front_matter"]["version"], json!("1.0.0"));
+
+    assert_eq!(responses[3]["error"]["code"], json!(-32602));
+    assert_eq!(
+        responses[4]["result"]["structuredContent"]["handles"],
+        json!([])
+    );
+    Ok(())
+}
+
+#[test]
+fn dependency_handles_are_read_only_aliases() -> Result<(), Box<dyn std::error::Error>> {
+    let workspace = scaffold_workspace()?;
+    let responses = run_script(
+        workspace.path(),
+        &[
+            initialize_request(1, SUPPORTED_PROTOCOL_VERSIONS[0]),
+            tool_call(
+                2,
+                "dependency_tree",
+                json!({ "locator": "spec://alpha/dependencies" }),
+            ),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "resources/read",
+                "params": { "uri": "impl://alpha-rust/dependencies" },
+            }),
+            tool_call(
+                4,
+                "metadata_add_dependency",
+                json!({ "path": "spec://alpha/dependencies", "locator": "spec://alpha" }),
+            ),
+            json!({ "jsonrpc": "2.0", "id": 5, "method": "resources/templates/list" }),
+        ],
+    )?;
+
+    let tree = &responses[1]["result"]["structuredContent"];
+    assert_eq!(tree["root"]["id"]["name"], json!("alpha"));
+
+    let contents = &responses[2]["result"]["contents"][0];
+    assert_eq!(contents["uri"], json!("impl://alpha-rust/dependencies"));
+    let read_tree: Value = serde_json::from_str(contents["text"].as_str().unwrap())?;
+    assert_eq!(read_tree["upstream"][0]["to"]["id"]["name"], json!("alpha"));
+
+    let rejected = &responses[3]["result"];
+    assert_eq!(rejected["isError"], json!(true));
+    assert_eq!(
+        rejected["structuredContent"]["error"]["kind"],
+        json!("read_only_locator")
+    );
+
+    let templates = responses[4]["result"]["resourceTemplates"]
+        .as_array()
+        .expect("resource templates");
+    assert!(
+        templates
+            .iter()
+            .any(|template| template["uriTemplate"] == "spec://{artifact}/dependencies")
+    );
+    Ok(())
+}
+
+fn run_script(
+    workspace: &Path,
+    messages: &[Value],
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let mut input = String::new();
+    for message in messages {
+        input.push_str(&serde_json::to_string(message)?);
+        input.push('\n');
+    }
+
+    let mut server = server_for(workspace)?;
+    let mut output = Vec::new();
+    server.serve(Cursor::new(input), &mut output)?;
+
+    let mut responses = Vec::new();
+    for line in String::from_utf8(output)?.lines() {
+        responses.push(serde_json::from_str(line)?);
+    }
+    Ok(responses)
+}
+
+fn server_for(workspace: &Path) -> Result<McpServer, Box<dyn std::error::Error>> {
+    let services = SpecmanServices::new(FilesystemWorkspaceLocator::new(workspace))?;
+    Ok(McpServer::new(services))
+}
+
+fn initialize_request(id: u64, version: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": version,
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "0.0.1" },
+        },
+    })
+}
+
+fn tool_call(id: u64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+fn scaffold_workspace() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let root = temp.path();
+    fs::create_dir_all(root.join(".specman"))?;
+
+    write_file(
+        root.join("spec/alpha/spec.md"),
+        "---\nname: alpha\nversion: \"1.0.0\"\n---\n# Alpha\n",
+    )?;
+    write_file(
+        root.join("impl/alpha-rust/impl.md"),
+        r#"---
+name: alpha-rust
+version: "0.1.0"
+spec: ../../spec/alpha/spec.md
+primary_language:
+    language: rust@1.0
+---
+Implementation body.
+"#,
+    )?;
+
+    Ok(temp)
+}
+
+fn write_file(path: impl AsRef<Path>, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,493 @@
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::audit::OperationEnvelope;
+use crate::dependency_tree::{ArtifactId, DependencyTree};
+use crate::graph::ArtifactGraph;
+use crate::lifecycle::{CreationPlan, CreationRequest, DeletionPlan, ScratchPadPlan};
+use crate::metadata::{MetadataMutationRequest, MetadataMutationResult};
+use crate::persistence::{PersistedArtifact, RemovedArtifact};
+use crate::prompt_catalog::{PromptRequest, RenderedPrompt};
+use crate::scratchpad::ScratchPadProfile;
+use crate::template::{RenderedTemplate, TemplateScenario};
+use crate::template_catalog::ResolvedTemplate;
+use crate::vendor::VendorOutcome;
+use crate::workspace::WorkspacePaths;
+
+/// SpecMan Core versions the built-in descriptors are valid for.
+pub const SPECMAN_CORE_VERSION_RANGE: &str = ">=0.1.0, <0.2.0";
+
+const CORE_SPEC: &str = "spec/specman-core/spec.md";
+
+pub const WORKSPACE_DISCOVER: &str = "specman.core.workspace_discovery.discover";
+pub const DEPENDENCY_TREE: &str = "specman.core.dependency_mapping.tree";
+pub const WORKSPACE_GRAPH: &str = "specman.core.dependency_mapping.workspace_graph";
+pub const DEPENDENCY_VENDOR: &str = "specman.core.dependency_mapping.vendor";
+pub const TEMPLATE_RESOLVE: &str = "specman.core.template_orchestration.resolve";
+pub const TEMPLATE_SET_POINTER: &str = "specman.core.template_orchestration.set_pointer";
+pub const TEMPLATE_REMOVE_POINTER: &str = "specman.core.template_orchestration.remove_pointer";
+pub const PROMPT_RENDER: &str = "specman.core.template_orchestration.render_prompt";
+pub const LIFECYCLE_PLAN_CREATION: &str = "specman.core.lifecycle_automation.plan_creation";
+pub const LIFECYCLE_PLAN_DELETION: &str = "specman.core.lifecycle_automation.plan_deletion";
+pub const LIFECYCLE_PLAN_SCRATCHPAD: &str = "specman.core.lifecycle_automation.plan_scratchpad";
+pub const LIFECYCLE_PERSIST: &str = "specman.core.lifecycle_automation.persist";
+pub const LIFECYCLE_DELETE: &str = "specman.core.lifecycle_automation.delete";
+pub const METADATA_MUTATE: &str = "specman.core.metadata_mutation.mutate";
+pub const METADATA_ADD_DEPENDENCY: &str = "specman.core.metadata_mutation.add_dependency";
+pub const METADATA_ADD_REFERENCE: &str = "specman.core.metadata_mutation.add_reference";
+
+/// Tool metadata for a single SpecMan capability
+/// (spec/specman-mcp/spec.md#entity-specmancapabilitydescriptor).
+///
+/// Schemas are generated from the same Rust types the library (de)serializes, so front
+/// ends can advertise capabilities without maintaining their own copies.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct CapabilityDescriptor {
+    pub id: String,
+    pub concept_ref: String,
+    pub specman_core_version: String,
+    pub summary: String,
+    pub input_schema: Value,
+    pub output_schema: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<CapabilityExtension>,
+}
+
+impl CapabilityDescriptor {
+    /// Builds a descriptor for an operation governed by a SpecMan Core concept heading.
+    pub fn core<I: JsonSchema, O: JsonSchema>(
+        id: impl Into<String>,
+        concept_anchor: &str,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            concept_ref: format!("{CORE_SPEC}#{concept_anchor}"),
+            specman_core_version: SPECMAN_CORE_VERSION_RANGE.to_string(),
+            summary: summary.into(),
+            input_schema: schema_value::<I>(),
+            output_schema: schema_value::<O>(),
+            extension: None,
+        }
+    }
+
+    /// Builds a descriptor for an implementation-specific extension. `owner` cites the
+    /// specification or implementation path that defines it.
+    pub fn extension<I: JsonSchema, O: JsonSchema>(
+        id: impl Into<String>,
+        concept_ref: impl Into<String>,
+        owner: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            concept_ref: concept_ref.into(),
+            specman_core_version: SPECMAN_CORE_VERSION_RANGE.to_string(),
+            summary: summary.into(),
+            input_schema: schema_value::<I>(),
+            output_schema: schema_value::<O>(),
+            extension: Some(CapabilityExtension::new(owner)),
+        }
+    }
+}
+
+/// Marks a descriptor as an extension (`type: extension`) and cites its owner.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct CapabilityExtension {
+    #[serde(rename = "type")]
+    pub label: String,
+    pub owner: String,
+}
+
+impl CapabilityExtension {
+    pub fn new(owner: impl Into<String>) -> Self {
+        Self {
+            label: "extension".into(),
+            owner: owner.into(),
+        }
+    }
+}
+
+/// Ordered catalog of capability descriptors.
+#[derive(Clone, Debug, Default)]
+pub struct CapabilityRegistry {
+    descriptors: Vec<CapabilityDescriptor>,
+}
+
+impl CapabilityRegistry {
+    /// Registry covering every SpecMan Core capability implemented by this library.
+    pub fn core() -> Self {
+        let descriptors = vec![
+            CapabilityDescriptor::core::<NoInput, WorkspaceDiscoveryOutput>(
+                WORKSPACE_DISCOVER,
+                "concept-workspace-discovery",
+                "Report the active workspace root and canonical artifact directories.",
+            ),
+            CapabilityDescriptor::core::<DependencyTreeInput, DependencyTree>(
+                DEPENDENCY_TREE,
+                "concept-dependency-mapping-services",
+                "Build upstream and downstream dependency trees for a workspace path, HTTPS URL, resource handle, or read-only <scheme>://{artifact}/dependencies alias.",
+            ),
+            CapabilityDescriptor::core::<NoInput, ArtifactGraph>(
+                WORKSPACE_GRAPH,
+                "concept-dependency-mapping-services",
+                "Load every specification, implementation, and scratch pad with their dependency edges for topological ordering and cycle analysis.",
+            ),
+            CapabilityDescriptor::core::<VendorInput, VendorOutcome>(
+                DEPENDENCY_VENDOR,
+                "concept-dependency-mapping-services",
+                "Copy a remote specification into the workspace and repoint references to it.",
+            ),
+            CapabilityDescriptor::core::<TemplateScenarioInput, ResolvedTemplate>(
+                TEMPLATE_RESOLVE,
+                "concept-template-orchestration",
+                "Resolve the template for a scenario following override, pointer, then embedded tiers.",
+            ),
+            CapabilityDescriptor::core::<TemplatePointerInput, ResolvedTemplate>(
+                TEMPLATE_SET_POINTER,
+                "concept-template-orchestration",
+                "Point a template scenario at a workspace file or HTTPS URL.",
+            ),
+            CapabilityDescriptor::core::<TemplateScenarioInput, ResolvedTemplate>(
+                TEMPLATE_REMOVE_POINTER,
+                "concept-template-orchestration",
+                "Remove a template pointer and fall back to overrides or embedded defaults.",
+            ),
+            CapabilityDescriptor::core::<PromptRequest, RenderedPrompt>(
+                PROMPT_RENDER,
+                "concept-template-orchestration",
+                "Render a templates/prompts blueprint with context and target paths filled from the workspace.",
+            ),
+            CapabilityDescriptor::core::<CreationRequest, CreationPlan>(
+                LIFECYCLE_PLAN_CREATION,
+                "concept-lifecycle-automation",
+                "Render a template and compute the dependency tree for a new artifact.",
+            ),
+            CapabilityDescriptor::core::<ArtifactTargetInput, DeletionPlan>(
+                LIFECYCLE_PLAN_DELETION,
+                "concept-lifecycle-automation",
+                "Compute the dependency tree and blocking status for deleting an artifact.",
+            ),
+            CapabilityDescriptor::core::<ScratchPadProfile, ScratchPadPlan>(
+                LIFECYCLE_PLAN_SCRATCHPAD,
+                "concept-lifecycle-automation",
+                "Render a scratch pad from its profile.",
+            ),
+            CapabilityDescriptor::core::<PersistInput, ArtifactPersistOutput>(
+                LIFECYCLE_PERSIST,
+                "concept-lifecycle-automation",
+                "Write a rendered template to the artifact's canonical workspace path.",
+            ),
+            CapabilityDescriptor::core::<DeletionInput, ArtifactRemovalOutput>(
+                LIFECYCLE_DELETE,
+                "concept-lifecycle-automation",
+                "Delete an artifact directory once dependency guard rails allow it.",
+            ),
+            CapabilityDescriptor::core::<MetadataMutationRequest, MetadataMutationOutput>(
+                METADATA_MUTATE,
+                "concept-metadata-mutation",
+                "Add dependencies or references to an artifact's front matter, or repoint existing locators.",
+            ),
+            CapabilityDescriptor::core::<DependencyAdditionInput, MetadataMutationOutput>(
+                METADATA_ADD_DEPENDENCY,
+                "concept-metadata-mutation",
+                "Add a dependency entry to a specification's front matter (path or spec:// handle).",
+            ),
+            CapabilityDescriptor::core::<ReferenceAdditionInput, MetadataMutationOutput>(
+                METADATA_ADD_REFERENCE,
+                "concept-metadata-mutation",
+                "Add a reference entry to an implementation's front matter (path or impl:// handle).",
+            ),
+        ];
+        Self { descriptors }
+    }
+
+    /// Appends a descriptor, typically an extension registered by a front end.
+    pub fn register(&mut self, descriptor: CapabilityDescriptor) {
+        self.descriptors.push(descriptor);
+    }
+
+    pub fn descriptors(&self) -> &[CapabilityDescriptor] {
+        &self.descriptors
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CapabilityDescriptor> {
+        self.descriptors
+            .iter()
+            .find(|descriptor| descriptor.id == id)
+    }
+}
+
+/// Input for capabilities that take no arguments.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NoInput {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DependencyTreeInput {
+    pub locator: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VendorInput {
+    /// HTTPS URL of the specification to copy into the workspace.
+    pub url: String,
+    /// Specification name; defaults to the document's front matter name.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateScenarioInput {
+    pub scenario: TemplateScenario,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TemplatePointerInput {
+    pub scenario: TemplateScenario,
+    pub locator: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ArtifactTargetInput {
+    pub target: ArtifactId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeletionInput {
+    pub target: ArtifactId,
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PersistInput {
+    pub artifact: ArtifactId,
+    pub rendered: RenderedTemplate,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DependencyAdditionInput {
+    /// Workspace-relative path or `spec://` handle of the specification to update.
+    pub path: String,
+    pub locator: String,
+    #[serde(default = "default_persist")]
+    pub persist: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceAdditionInput {
+    /// Workspace-relative path or `impl://` handle of the implementation to update.
+    pub path: String,
+    pub locator: String,
+    #[serde(default, rename = "type")]
+    pub reference_type: Option<String>,
+    #[serde(default)]
+    pub optional: Option<bool>,
+    #[serde(default = "default_persist")]
+    pub persist: bool,
+}
+
+fn default_persist() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceDiscoveryOutput {
+    pub root: PathBuf,
+    pub dot_specman: PathBuf,
+    pub spec_dir: PathBuf,
+    pub impl_dir: PathBuf,
+    pub scratchpad_dir: PathBuf,
+}
+
+impl From<&WorkspacePaths> for WorkspaceDiscoveryOutput {
+    fn from(paths: &WorkspacePaths) -> Self {
+        Self {
+            root: paths.root().to_path_buf(),
+            dot_specman: paths.dot_specman().to_path_buf(),
+            spec_dir: paths.spec_dir(),
+            impl_dir: paths.impl_dir(),
+            scratchpad_dir: paths.scratchpad_dir(),
+        }
+    }
+}
+
+/// Serializable view of [`PersistedArtifact`] with a workspace-relative path.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ArtifactPersistOutput {
+    pub artifact: ArtifactId,
+    pub path: String,
+    pub envelope: OperationEnvelope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_error: Option<String>,
+}
+
+impl From<&PersistedArtifact> for ArtifactPersistOutput {
+    fn from(persisted: &PersistedArtifact) -> Self {
+        Self {
+            artifact: persisted.artifact.clone(),
+            path: workspace_relative(&persisted.workspace, &persisted.path),
+            envelope: persisted.envelope.clone(),
+            audit_error: persisted.audit_error.clone(),
+        }
+    }
+}
+
+/// Serializable view of [`RemovedArtifact`] with a workspace-relative path.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ArtifactRemovalOutput {
+    pub artifact: ArtifactId,
+    pub removed_path: String,
+    pub envelope: OperationEnvelope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_error: Option<String>,
+}
+
+impl From<&RemovedArtifact> for ArtifactRemovalOutput {
+    fn from(removed: &RemovedArtifact) -> Self {
+        Self {
+            artifact: removed.artifact.clone(),
+            removed_path: workspace_relative(&removed.workspace, &removed.directory),
+            envelope: removed.envelope.clone(),
+            audit_error: removed.audit_error.clone(),
+        }
+    }
+}
+
+/// Serializable view of [`MetadataMutationResult`] with a workspace-relative path.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MetadataMutationOutput {
+    pub artifact: ArtifactId,
+    pub updated_document: String,
+    pub persisted_path: Option<String>,
+    pub envelope: Option<OperationEnvelope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_error: Option<String>,
+}
+
+impl From<&MetadataMutationResult> for MetadataMutationOutput {
+    fn from(result: &MetadataMutationResult) -> Self {
+        Self {
+            artifact: result.artifact.clone(),
+            updated_document: result.updated_document.clone(),
+            persisted_path: result
+                .persisted
+                .as_ref()
+                .map(|persisted| workspace_relative(&persisted.workspace, &persisted.path)),
+            envelope: result
+                .persisted
+                .as_ref()
+                .map(|persisted| persisted.envelope.clone()),
+            audit_error: result
+                .persisted
+                .as_ref()
+                .and_then(|persisted| persisted.audit_error.clone()),
+        }
+    }
+}
+
+fn workspace_relative(workspace: &WorkspacePaths, path: &Path) -> String {
+    path.strip_prefix(workspace.root())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Generates a self-contained JSON Schema (no `$ref`s) so clients that do not resolve
+/// definitions can still validate arguments.
+fn schema_value<T: JsonSchema>() -> Value {
+    let settings = SchemaSettings::draft07().with(|settings| {
+        settings.inline_subschemas = true;
+        settings.meta_schema = None;
+    });
+    let schema = settings.into_generator().into_root_schema_for::<T>();
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::{Version, VersionReq};
+    use std::collections::HashSet;
+
+    #[test]
+    fn core_registry_covers_each_concept_with_unique_ids() {
+        let registry = CapabilityRegistry::core();
+        let ids: HashSet<_> = registry
+            .descriptors()
+            .iter()
+            .map(|descriptor| descriptor.id.as_str())
+            .collect();
+        assert_eq!(ids.len(), registry.descriptors().len());
+
+        for concept in [
+            "workspace_discovery",
+            "dependency_mapping",
+            "template_orchestration",
+            "lifecycle_automation",
+            "metadata_mutation",
+        ] {
+            let prefix = format!("specman.core.{concept}.");
+            assert!(ids.iter().any(|id| id.starts_with(&prefix)), "{concept}");
+        }
+
+        for descriptor in registry.descriptors() {
+            assert!(descriptor.concept_ref.starts_with(CORE_SPEC));
+            assert!(descriptor.extension.is_none());
+            assert_eq!(
+                descriptor.input_schema["type"], "object",
+                "{}",
+                descriptor.id
+            );
+        }
+
+        let range = VersionReq::parse(SPECMAN_CORE_VERSION_RANGE).unwrap();
+        assert!(range.matches(&Version::new(0, 1, 0)));
+    }
+
+    #[test]
+    fn schemas_are_inlined_from_library_types() {
+        let registry = CapabilityRegistry::core();
+        let delete = registry.get(LIFECYCLE_DELETE).expect("delete descriptor");
+        let input = &delete.input_schema;
+        assert_eq!(input["required"], serde_json::json!(["target"]));
+        assert!(input["properties"]["target"]["properties"]["kind"].is_object());
+        assert!(input.get("definitions").is_none());
+        assert!(delete.output_schema["properties"]["envelope"].is_object());
+
+        let tree = registry.get(DEPENDENCY_TREE).expect("tree descriptor");
+        assert!(tree.output_schema["properties"]["upstream"].is_object());
+    }
+
+    #[test]
+    fn extensions_are_labelled_and_cite_owner() {
+        let mut registry = CapabilityRegistry::core();
+        registry.register(CapabilityDescriptor::extension::<NoInput, Value>(
+            "specman.mcp.list_specifications",
+            "spec/specman-mcp/spec.md#concept-specman-capability-parity",
+            "spec/specman-mcp/spec.md",
+            "List specification handles.",
+        ));
+
+        let descriptor = registry
+            .get("specman.mcp.list_specifications")
+            .expect("extension registered");
+        let serialized = serde_json::to_value(descriptor).unwrap();
+        assert_eq!(serialized["extension"]["type"], "extension");
+        assert_eq!(serialized["extension"]["owner"], "spec/specman-mcp/spec.md");
+    }
+}
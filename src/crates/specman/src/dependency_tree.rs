@@ -1,15 +1,21 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::ffi::OsStr;
+use std::fmt;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use schemars::JsonSchema;
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::error::SpecmanError;
+use crate::error::{CodedError, ErrorCode, SpecmanError};
 use crate::front_matter::{self, ArtifactFrontMatter, DependencyEntry, FrontMatterKind};
+use crate::graph::ArtifactGraph;
+use crate::headings;
+use crate::progress::{NoopProgress, ProgressEvent, ProgressObserver};
 use crate::shared_function::SemVer;
 use crate::workspace::{WorkspaceLocator, WorkspacePaths};
 
@@ -79,6 +85,10 @@ pub struct ArtifactSummary {
     pub id: ArtifactId,
     pub version: Option<SemVer>,
     pub metadata: BTreeMap<String, String>,
+    /// Heading anchor when the edge leading here points at a section of the artifact
+    /// (`spec.md#concept-foo`) rather than the whole document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
 }
 
 /// Directed edge between two artifacts.
@@ -102,6 +112,143 @@ pub enum DependencyRelation {
     Downstream,
 }
 
+/// Front-matter field a dependency was declared in.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyField {
+    Dependencies,
+    References,
+    Spec,
+    Target,
+}
+
+impl fmt::Display for DependencyField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DependencyField::Dependencies => "dependencies",
+            DependencyField::References => "references",
+            DependencyField::Spec => "spec",
+            DependencyField::Target => "target",
+        })
+    }
+}
+
+/// One declared dependency: `from` lists `to` in its `field` front matter.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DependencyHop {
+    pub from: ArtifactId,
+    pub to: ArtifactId,
+    pub field: DependencyField,
+    pub optional: bool,
+}
+
+/// Declared version requirement that the resolved dependency does not satisfy.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DependencyDiagnostic {
+    /// Artifact whose front matter declares the requirement.
+    pub artifact: ArtifactId,
+    pub dependency: ArtifactId,
+    pub field: DependencyField,
+    pub requirement: String,
+    /// Version the dependency declares, if any.
+    pub found: Option<SemVer>,
+    pub optional: bool,
+    pub message: String,
+}
+
+/// One front-matter entry on a dependency cycle: `from` lists `locator` in its `field`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct CycleEntry {
+    pub from: ArtifactId,
+    pub to: ArtifactId,
+    pub field: DependencyField,
+    /// The entry as written, fragment included.
+    pub locator: String,
+    pub optional: bool,
+}
+
+impl CycleEntry {
+    /// How to take this entry out of the loop. `spec` and `target` are required single
+    /// values, so they can only be repointed.
+    fn suggestion(&self) -> String {
+        let entry = format!(
+            "`{}` in {}'s `{}`",
+            self.locator,
+            describe_artifact(&self.from),
+            self.field
+        );
+        match self.field {
+            DependencyField::Spec | DependencyField::Target => {
+                format!("point {entry} at another artifact")
+            }
+            DependencyField::Dependencies | DependencyField::References => {
+                format!("remove {entry}")
+            }
+        }
+    }
+}
+
+/// Dependency loop that stopped a tree from being built. Loops are errors even when some of
+/// their entries are optional.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DependencyCycle {
+    /// Artifacts around the loop; the first and last are the same artifact.
+    pub path: Vec<ArtifactId>,
+    /// Entries followed from the first artifact before `closing`.
+    pub entries: Vec<CycleEntry>,
+    /// Entry that leads back to an artifact still being resolved.
+    pub closing: CycleEntry,
+    /// Edges resolved before the loop was found, rooted at the requested artifact.
+    pub partial_tree: DependencyTree,
+}
+
+impl DependencyCycle {
+    /// One way to break the loop per entry, starting with the entry that closes it.
+    pub fn suggestions(&self) -> Vec<String> {
+        std::iter::once(&self.closing)
+            .chain(&self.entries)
+            .map(CycleEntry::suggestion)
+            .collect()
+    }
+}
+
+impl fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path: Vec<String> = self.path.iter().map(describe_artifact).collect();
+        write!(
+            f,
+            "{} (closed by `{}` in {}'s `{}`)",
+            path.join(" -> "),
+            self.closing.locator,
+            describe_artifact(&self.closing.from),
+            self.closing.field
+        )
+    }
+}
+
+/// Outcome of loading one remote artifact in
+/// [`DependencyGraphServices::fetch_remote_artifacts`].
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RemoteArtifact {
+    pub url: String,
+    /// Identity parsed from the document, when it loaded.
+    pub artifact: Option<ArtifactId>,
+    pub error: Option<String>,
+}
+
+/// Which paths [`DependencyGraphServices::paths_between`] returns.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PathSelection {
+    /// Every simple path, shortest first.
+    #[default]
+    All,
+    /// A single shortest path.
+    Shortest,
+}
+
 /// Aggregated dependency data across upstream, downstream, and combined views.
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, Default)]
 pub struct DependencyTree {
@@ -109,6 +256,9 @@ pub struct DependencyTree {
     pub upstream: Vec<DependencyEdge>,
     pub downstream: Vec<DependencyEdge>,
     pub aggregate: Vec<DependencyEdge>,
+    /// Unsatisfied version requirements found while traversing upstream.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<DependencyDiagnostic>,
 }
 
 impl DependencyTree {
@@ -118,6 +268,7 @@ impl DependencyTree {
             upstream: Vec::new(),
             downstream: Vec::new(),
             aggregate: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 
@@ -132,6 +283,108 @@ impl DependencyTree {
             _ => self.downstream.iter().any(|edge| !edge.optional),
         }
     }
+
+    /// Upstream artifacts in breadth-first order from the root, so direct dependencies
+    /// precede transitive ones. Each artifact appears once; the root is excluded.
+    pub fn upstream_chain(&self) -> Vec<&ArtifactSummary> {
+        let mut chain: Vec<&ArtifactSummary> = Vec::new();
+        let mut frontier = VecDeque::from([&self.root.id]);
+        let mut seen = BTreeSet::new();
+        while let Some(current) = frontier.pop_front() {
+            if !seen.insert(current) {
+                continue;
+            }
+            for edge in self.upstream.iter().filter(|edge| &edge.from.id == current) {
+                if edge.to.id != self.root.id && !chain.iter().any(|seen| seen.id == edge.to.id) {
+                    chain.push(&edge.to);
+                }
+                frontier.push_back(&edge.to.id);
+            }
+        }
+        chain
+    }
+
+    /// Nests the flat `upstream` or `downstream` edges under the root. Upstream children are
+    /// the artifacts a node depends on; downstream children are the artifacts depending on
+    /// it. Siblings are ordered by name.
+    pub fn hierarchy(&self, direction: DependencyRelation) -> DependencyNode {
+        let edges = match direction {
+            DependencyRelation::Upstream => &self.upstream,
+            DependencyRelation::Downstream => &self.downstream,
+        };
+        let mut children: HashMap<&ArtifactId, Vec<(&ArtifactSummary, bool)>> = HashMap::new();
+        for edge in edges {
+            let (parent, child) = match direction {
+                DependencyRelation::Upstream => (&edge.from, &edge.to),
+                DependencyRelation::Downstream => (&edge.to, &edge.from),
+            };
+            let siblings = children.entry(&parent.id).or_default();
+            if !siblings
+                .iter()
+                .any(|(existing, _)| existing.id == child.id && existing.fragment == child.fragment)
+            {
+                siblings.push((child, edge.optional));
+            }
+        }
+        for siblings in children.values_mut() {
+            siblings
+                .sort_by(|a, b| (&a.0.id.name, &a.0.fragment).cmp(&(&b.0.id.name, &b.0.fragment)));
+        }
+
+        let mut seen = BTreeSet::from([&self.root.id]);
+        expand_node(&self.root, 0, false, &children, &mut seen)
+    }
+}
+
+fn expand_node<'a>(
+    artifact: &'a ArtifactSummary,
+    depth: usize,
+    optional: bool,
+    children: &HashMap<&'a ArtifactId, Vec<(&'a ArtifactSummary, bool)>>,
+    seen: &mut BTreeSet<&'a ArtifactId>,
+) -> DependencyNode {
+    let mut node = DependencyNode {
+        artifact: artifact.clone(),
+        depth,
+        optional,
+        seen_earlier: false,
+        children: Vec::new(),
+    };
+    for (child, child_optional) in children.get(&artifact.id).into_iter().flatten() {
+        if seen.insert(&child.id) {
+            node.children.push(expand_node(
+                child,
+                depth + 1,
+                *child_optional,
+                children,
+                seen,
+            ));
+        } else {
+            node.children.push(DependencyNode {
+                artifact: (*child).clone(),
+                depth: depth + 1,
+                optional: *child_optional,
+                seen_earlier: true,
+                children: Vec::new(),
+            });
+        }
+    }
+    node
+}
+
+/// Node in the hierarchical view of a [`DependencyTree`]. Artifacts reachable along
+/// several paths are expanded once; later occurrences set `seen_earlier` and carry no
+/// children.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DependencyNode {
+    pub artifact: ArtifactSummary,
+    /// Distance from the root, which sits at depth 0.
+    pub depth: usize,
+    /// Whether the edge leading to this node is optional; always false for the root.
+    pub optional: bool,
+    pub seen_earlier: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DependencyNode>,
 }
 
 /// Contract for dependency traversal services.
@@ -208,6 +461,16 @@ impl<L: WorkspaceLocator> FilesystemDependencyMapper<L> {
 /// workspace artifacts mutate.
 pub trait DependencyInventory: Send + Sync {
     fn invalidate(&self);
+
+    /// Tree `path` would have with `content` written to it, for dry runs. Inventories that
+    /// cannot build trees return `None`.
+    fn draft_tree(
+        &self,
+        _path: &Path,
+        _content: &str,
+    ) -> Result<Option<DependencyTree>, SpecmanError> {
+        Ok(None)
+    }
 }
 
 /// Shared dependency graph + workspace inventory services that other modules
@@ -216,15 +479,12 @@ pub struct DependencyGraphServices<L: WorkspaceLocator> {
     workspace: L,
     fetcher: Arc<dyn ContentFetcher>,
     inventory_cache: Mutex<Option<WorkspaceInventorySnapshot>>,
+    progress: Mutex<Arc<dyn ProgressObserver>>,
 }
 
 impl<L: WorkspaceLocator> DependencyGraphServices<L> {
     pub fn new(workspace: L) -> Self {
-        Self {
-            workspace,
-            fetcher: Arc::new(HttpFetcher::default()),
-            inventory_cache: Mutex::new(None),
-        }
+        Self::with_fetcher(workspace, Arc::new(HttpFetcher::default()))
     }
 
     pub fn with_fetcher(workspace: L, fetcher: Arc<dyn ContentFetcher>) -> Self {
@@ -232,25 +492,41 @@ impl<L: WorkspaceLocator> DependencyGraphServices<L> {
             workspace,
             fetcher,
             inventory_cache: Mutex::new(None),
+            progress: Mutex::new(Arc::new(NoopProgress)),
         }
     }
 
+    /// Routes traversal and inventory progress to `observer`. Takes `&self` so observers
+    /// can be attached to services that are already shared behind an `Arc`.
+    pub fn set_progress_observer(&self, observer: Arc<dyn ProgressObserver>) {
+        *self.progress.lock().unwrap() = observer;
+    }
+
+    fn progress_observer(&self) -> Arc<dyn ProgressObserver> {
+        self.progress.lock().unwrap().clone()
+    }
+
     pub fn dependency_tree_from_path(
         &self,
         path: impl AsRef<Path>,
     ) -> Result<DependencyTree, SpecmanError> {
         let workspace = self.workspace_paths()?;
         let locator = ArtifactLocator::from_path(path.as_ref(), &workspace, None)?;
-        self.build_tree_with_workspace(locator, workspace)
+        self.build_tree_with_workspace(locator, workspace, None)
     }
 
+    /// Builds a dependency tree from any supported locator, including read-only
+    /// `<scheme>://{artifact}/dependencies` queries.
     pub fn dependency_tree_from_locator(
         &self,
         reference: &str,
     ) -> Result<DependencyTree, SpecmanError> {
+        if let Some(query) = DependencyQuery::parse(reference)? {
+            return self.dependency_tree_for_query(&query);
+        }
         let workspace = self.workspace_paths()?;
         let locator = ArtifactLocator::from_reference(reference, &workspace)?;
-        self.build_tree_with_workspace(locator, workspace)
+        self.build_tree_with_workspace(locator, workspace, None)
     }
 
     pub fn dependency_tree_from_url(&self, url: &str) -> Result<DependencyTree, SpecmanError> {
@@ -263,7 +539,144 @@ impl<L: WorkspaceLocator> DependencyGraphServices<L> {
     ) -> Result<DependencyTree, SpecmanError> {
         let workspace = self.workspace_paths()?;
         let locator = self.locator_for_artifact(root, &workspace)?;
-        self.build_tree_with_workspace(locator, workspace)
+        self.build_tree_with_workspace(locator, workspace, None)
+    }
+
+    /// Answers a `/dependencies` query with the addressed artifact's dependency tree.
+    pub fn dependency_tree_for_query(
+        &self,
+        query: &DependencyQuery,
+    ) -> Result<DependencyTree, SpecmanError> {
+        self.dependency_tree_for_artifact(&query.artifact_id())
+    }
+
+    /// Every specification, implementation, and scratch pad in the workspace with the
+    /// dependencies each declares, loaded once through the cached inventory.
+    pub fn workspace_graph(&self) -> Result<ArtifactGraph, SpecmanError> {
+        Ok(ArtifactGraph::from_inventory(&self.inventory_snapshot()?))
+    }
+
+    /// Loads every HTTPS artifact reachable from the workspace through the configured
+    /// fetcher, following remote documents' own dependencies. With a
+    /// [`CachingContentFetcher`](crate::remote_cache::CachingContentFetcher) this mirrors each
+    /// one into the remote cache. Results are ordered by URL; a document that fails to load
+    /// is reported rather than aborting the walk.
+    pub fn fetch_remote_artifacts(&self) -> Result<Vec<RemoteArtifact>, SpecmanError> {
+        let workspace = self.workspace.workspace()?;
+        let inventory = self.inventory_with_workspace(&workspace)?;
+        let progress = self.progress_observer();
+
+        let mut queue: VecDeque<Url> = inventory
+            .entries
+            .iter()
+            .flat_map(|entry| entry.dependencies.iter())
+            .filter_map(|dependency| match &dependency.locator {
+                ArtifactLocator::Url(url) => Some(url.clone()),
+                ArtifactLocator::File(_) => None,
+            })
+            .collect();
+        let mut seen = BTreeSet::new();
+        let mut fetched = Vec::new();
+        while let Some(url) = queue.pop_front() {
+            if !seen.insert(url.clone()) {
+                continue;
+            }
+            let locator = ArtifactLocator::Url(url.clone());
+            match ArtifactDocument::load(
+                &locator,
+                &workspace,
+                self.fetcher.as_ref(),
+                progress.as_ref(),
+                DependencyResolutionMode::BestEffort,
+            ) {
+                Ok(document) => {
+                    queue.extend(document.dependencies.into_iter().filter_map(|dependency| {
+                        match dependency.locator {
+                            ArtifactLocator::Url(url) => Some(url),
+                            ArtifactLocator::File(_) => None,
+                        }
+                    }));
+                    fetched.push(RemoteArtifact {
+                        url: url.to_string(),
+                        artifact: Some(document.summary.id),
+                        error: None,
+                    });
+                }
+                Err(err) => fetched.push(RemoteArtifact {
+                    url: url.to_string(),
+                    artifact: None,
+                    error: Some(err.to_string()),
+                }),
+            }
+        }
+        fetched.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(fetched)
+    }
+
+    /// Explains how `from` comes to depend on `to` as chains of declared dependencies.
+    /// Passing the same artifact twice lists the cycles through it. Returns no paths when
+    /// `to` is not upstream of `from`.
+    pub fn paths_between(
+        &self,
+        from: &ArtifactId,
+        to: &ArtifactId,
+        selection: PathSelection,
+    ) -> Result<Vec<Vec<DependencyHop>>, SpecmanError> {
+        let inventory = self.inventory_snapshot()?;
+        let hops = inventory.hops();
+        for id in [from, to] {
+            let known = hops.iter().any(|hop| &hop.from == id || &hop.to == id)
+                || inventory
+                    .entries
+                    .iter()
+                    .any(|entry| &entry.summary.id == id);
+            if !known {
+                return Err(SpecmanError::Dependency(format!(
+                    "{}://{} is not part of the dependency graph",
+                    ResourceHandle::scheme_for(id.kind),
+                    id.name
+                )));
+            }
+        }
+
+        let mut adjacency: HashMap<&ArtifactId, Vec<&DependencyHop>> = HashMap::new();
+        for hop in &hops {
+            adjacency.entry(&hop.from).or_default().push(hop);
+        }
+
+        let mut paths = Vec::new();
+        match selection {
+            PathSelection::All => {
+                let mut on_path = vec![from];
+                let mut current = Vec::new();
+                collect_simple_paths(from, to, &adjacency, &mut on_path, &mut current, &mut paths);
+                paths.sort_by(|a: &Vec<DependencyHop>, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+            }
+            PathSelection::Shortest => {
+                let mut visited: BTreeSet<&ArtifactId> = BTreeSet::new();
+                let mut queue: VecDeque<(&ArtifactId, Vec<DependencyHop>)> =
+                    VecDeque::from([(from, Vec::new())]);
+                while let Some((node, path)) = queue.pop_front() {
+                    let Some(next) = adjacency.get(node) else {
+                        continue;
+                    };
+                    if let Some(hop) = next.iter().find(|hop| &hop.to == to) {
+                        let mut path = path;
+                        path.push((*hop).clone());
+                        paths.push(path);
+                        break;
+                    }
+                    for hop in next {
+                        if &hop.to != from && visited.insert(&hop.to) {
+                            let mut extended = path.clone();
+                            extended.push((*hop).clone());
+                            queue.push_back((&hop.to, extended));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(paths)
     }
 
     pub fn inventory_snapshot(&self) -> Result<WorkspaceInventorySnapshot, SpecmanError> {
@@ -275,21 +688,53 @@ impl<L: WorkspaceLocator> DependencyGraphServices<L> {
         self.inventory_cache.lock().unwrap().take();
     }
 
-    fn workspace_paths(&self) -> Result<WorkspacePaths, SpecmanError> {
+    pub(crate) fn workspace_paths(&self) -> Result<WorkspacePaths, SpecmanError> {
         self.workspace.workspace()
     }
 
+    /// Builds the tree that `path` would have once `content` is written there, without
+    /// touching the workspace. Used to preview creations and rewrites.
+    pub fn dependency_tree_for_draft(
+        &self,
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> Result<DependencyTree, SpecmanError> {
+        let workspace = self.workspace_paths()?;
+        let path = path.as_ref();
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        self.build_tree_with_workspace(
+            ArtifactLocator::File(path),
+            workspace,
+            Some(content.to_string()),
+        )
+    }
+
+    /// Walks the tree rooted at `root_locator`. A `draft` replaces the root's content on
+    /// disk, which need not exist yet.
     fn build_tree_with_workspace(
         &self,
         root_locator: ArtifactLocator,
         workspace: WorkspacePaths,
+        draft: Option<String>,
     ) -> Result<DependencyTree, SpecmanError> {
-        let mut traversal = Traversal::new(workspace.clone(), self.fetcher.clone());
+        let mut traversal = Traversal::new(
+            workspace.clone(),
+            self.fetcher.clone(),
+            self.progress_observer(),
+        );
+        traversal.draft = draft.map(|raw| (root_locator.key(), raw));
         let root = traversal.visit(&root_locator)?;
         let mut aggregate: BTreeSet<_> = traversal.edges.clone();
+        let mut diagnostics = traversal.diagnostics.clone();
 
         if let Some(root_path) = root_locator.workspace_path().map(Path::to_path_buf) {
             let inventory = self.inventory_with_workspace(&workspace)?;
+            diagnostics.extend(
+                inventory
+                    .version_diagnostics()
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.dependency == root.id),
+            );
             for dependent in inventory.dependents_of(&root_path) {
                 let edge = DependencyEdge {
                     from: dependent.summary,
@@ -318,6 +763,7 @@ impl<L: WorkspaceLocator> DependencyGraphServices<L> {
             upstream,
             downstream,
             aggregate,
+            diagnostics: diagnostics.into_iter().collect(),
         })
     }
 
@@ -346,7 +792,11 @@ impl<L: WorkspaceLocator> DependencyGraphServices<L> {
             return Ok(snapshot);
         }
 
-        let built = WorkspaceInventorySnapshot::build(workspace, self.fetcher.clone())?;
+        let built = WorkspaceInventorySnapshot::build(
+            workspace,
+            self.fetcher.clone(),
+            self.progress_observer().as_ref(),
+        )?;
         *self.inventory_cache.lock().unwrap() = Some(built.clone());
         Ok(built)
     }
@@ -356,6 +806,14 @@ impl<L: WorkspaceLocator> DependencyInventory for DependencyGraphServices<L> {
     fn invalidate(&self) {
         self.invalidate_inventory();
     }
+
+    fn draft_tree(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<Option<DependencyTree>, SpecmanError> {
+        self.dependency_tree_for_draft(path, content).map(Some)
+    }
 }
 
 impl<L: WorkspaceLocator> DependencyMapping for FilesystemDependencyMapper<L> {
@@ -411,12 +869,50 @@ where
 struct ArtifactDocument {
     summary: ArtifactSummary,
     dependencies: Vec<ArtifactDependency>,
+    anchors: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
 struct ArtifactDependency {
     locator: ArtifactLocator,
+    /// The entry as written in front matter, fragment included.
+    declared: String,
     optional: bool,
+    field: DependencyField,
+    version: Option<VersionReq>,
+    fragment: Option<String>,
+}
+
+impl ArtifactDependency {
+    /// Reports a declared version requirement that `resolved` does not satisfy.
+    fn check_version(
+        &self,
+        declaring: &ArtifactSummary,
+        resolved: &ArtifactSummary,
+    ) -> Option<DependencyDiagnostic> {
+        let requirement = self.version.as_ref()?;
+        let found = match &resolved.version {
+            Some(version) if requirement.matches(version) => return None,
+            Some(version) => version.to_string(),
+            None => "no version".to_string(),
+        };
+        Some(DependencyDiagnostic {
+            artifact: declaring.id.clone(),
+            dependency: resolved.id.clone(),
+            field: self.field,
+            requirement: requirement.to_string(),
+            found: resolved.version.clone(),
+            optional: self.optional,
+            message: format!(
+                "{} requires {} {} via `{}`, found {}",
+                describe_artifact(&declaring.id),
+                describe_artifact(&resolved.id),
+                requirement,
+                self.field,
+                found
+            ),
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -439,16 +935,31 @@ enum ArtifactLocator {
     Url(Url),
 }
 
-/// Canonical parser for `spec://`, `impl://`, and `scratch://` resource handles as defined by
-/// SpecMan Core's Dependency Mapping Services concept.
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct ResourceHandle {
+/// Canonical `spec://`, `impl://`, and `scratch://` resource handle as defined by SpecMan Core's
+/// Dependency Mapping Services concept. Handles normalize to lowercase slugs and map onto the
+/// canonical artifact path for their kind.
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(try_from = "String", into = "String")]
+#[schemars(with = "String")]
+pub struct ResourceHandle {
     kind: ArtifactKind,
     slug: String,
 }
 
+/// Front matter and canonical location for an artifact addressed by a resource handle.
+#[derive(Clone, Debug, Serialize)]
+pub struct ResolvedResource {
+    pub handle: ResourceHandle,
+    pub path: PathBuf,
+    pub front_matter: ArtifactFrontMatter,
+}
+
 impl ResourceHandle {
-    fn parse(reference: &str) -> Result<Option<Self>, SpecmanError> {
+    /// Parses `reference` when it uses a resource-handle scheme. Returns `Ok(None)` for
+    /// HTTPS URLs and plain paths so callers can fall back to other locator forms.
+    pub fn parse(reference: &str) -> Result<Option<Self>, SpecmanError> {
         if let Some(rest) = reference.strip_prefix("spec://") {
             return Self::new(ArtifactKind::Specification, rest).map(Some);
         }
@@ -478,11 +989,130 @@ impl ResourceHandle {
         Ok(None)
     }
 
-    fn new(kind: ArtifactKind, raw_slug: &str) -> Result<Self, SpecmanError> {
+    pub fn new(kind: ArtifactKind, raw_slug: &str) -> Result<Self, SpecmanError> {
+        if let Some(artifact) = raw_slug.trim().strip_suffix(DependencyQuery::SUFFIX) {
+            return Err(DependencyQuery::read_only_error(&format!(
+                "{}://{}{}",
+                Self::scheme_for(kind),
+                artifact,
+                DependencyQuery::SUFFIX
+            )));
+        }
         let slug = Self::canonical_slug(raw_slug)?;
         Ok(Self { kind, slug })
     }
 
+    /// Builds the handle addressing an artifact identity.
+    pub fn for_artifact(id: &ArtifactId) -> Result<Self, SpecmanError> {
+        Self::new(id.kind, &id.name)
+    }
+
+    pub fn kind(&self) -> ArtifactKind {
+        self.kind
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    /// Returns the URI scheme for this handle's artifact kind (without `://`).
+    pub fn scheme(&self) -> &'static str {
+        Self::scheme_for(self.kind)
+    }
+
+    pub fn artifact_id(&self) -> ArtifactId {
+        ArtifactId {
+            kind: self.kind,
+            name: self.slug.clone(),
+        }
+    }
+
+    /// Lists handles for every artifact of `kind` whose canonical file exists in the workspace.
+    /// Folders whose names are not valid handle identifiers are skipped.
+    pub fn enumerate(
+        workspace: &WorkspacePaths,
+        kind: ArtifactKind,
+    ) -> Result<Vec<Self>, SpecmanError> {
+        let (root, file_name) = match kind {
+            ArtifactKind::Specification => (workspace.spec_dir(), "spec.md"),
+            ArtifactKind::Implementation => (workspace.impl_dir(), "impl.md"),
+            ArtifactKind::ScratchPad => (workspace.scratchpad_dir(), "scratch.md"),
+        };
+        if !root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut handles = Vec::new();
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() || !entry.path().join(file_name).is_file() {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if let Ok(handle) = Self::new(kind, &name)
+                && handle.slug == name
+            {
+                handles.push(handle);
+            }
+        }
+        handles.sort();
+        Ok(handles)
+    }
+
+    /// Lists handles for every specification, implementation, and scratch pad in the workspace.
+    pub fn enumerate_all(workspace: &WorkspacePaths) -> Result<Vec<Self>, SpecmanError> {
+        let mut handles = Vec::new();
+        for kind in [
+            ArtifactKind::Specification,
+            ArtifactKind::Implementation,
+            ArtifactKind::ScratchPad,
+        ] {
+            handles.extend(Self::enumerate(workspace, kind)?);
+        }
+        Ok(handles)
+    }
+
+    /// Resolves the handle to its canonical file and parses the front matter as the variant
+    /// matching the handle's kind.
+    pub fn resolve(&self, workspace: &WorkspacePaths) -> Result<ResolvedResource, SpecmanError> {
+        let candidate = self.to_path(workspace);
+        if !candidate.is_file() {
+            return Err(SpecmanError::Workspace(format!(
+                "{self} does not resolve to an artifact (expected {})",
+                candidate.display()
+            )));
+        }
+        let path = resolve_workspace_path(&candidate, None, workspace)?;
+        let raw = fs::read_to_string(&path)?;
+        let file = path
+            .strip_prefix(workspace.root())
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let split = front_matter::split_front_matter(&raw).map_err(|err| err.in_file(&file))?;
+        let kind = match self.kind {
+            ArtifactKind::Specification => FrontMatterKind::Specification,
+            ArtifactKind::Implementation => FrontMatterKind::Implementation,
+            ArtifactKind::ScratchPad => FrontMatterKind::ScratchPad,
+        };
+        let front_matter = split.parse_as(kind).map_err(|err| err.in_file(&file))?;
+        Ok(ResolvedResource {
+            handle: self.clone(),
+            path,
+            front_matter,
+        })
+    }
+
+    pub(crate) fn scheme_for(kind: ArtifactKind) -> &'static str {
+        match kind {
+            ArtifactKind::Specification => "spec",
+            ArtifactKind::Implementation => "impl",
+            ArtifactKind::ScratchPad => "scratch",
+        }
+    }
+
     fn canonical_slug(raw: &str) -> Result<String, SpecmanError> {
         let trimmed = raw.trim();
         if trimmed.is_empty() {
@@ -510,7 +1140,8 @@ impl ResourceHandle {
         Ok(canonical)
     }
 
-    fn to_path(&self, workspace: &WorkspacePaths) -> PathBuf {
+    /// Canonical artifact path for this handle (not checked for existence).
+    pub fn to_path(&self, workspace: &WorkspacePaths) -> PathBuf {
         match self.kind {
             ArtifactKind::Specification => workspace.spec_dir().join(&self.slug).join("spec.md"),
             ArtifactKind::Implementation => workspace.impl_dir().join(&self.slug).join("impl.md"),
@@ -526,6 +1157,128 @@ impl ResourceHandle {
     }
 }
 
+impl fmt::Display for ResourceHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme(), self.slug)
+    }
+}
+
+impl FromStr for ResourceHandle {
+    type Err = SpecmanError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)?.ok_or_else(|| {
+            SpecmanError::Dependency(format!(
+                "{raw} is not a resource handle (expected spec://, impl://, or scratch://)"
+            ))
+        })
+    }
+}
+
+impl TryFrom<String> for ResourceHandle {
+    type Error = SpecmanError;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        raw.parse()
+    }
+}
+
+impl From<ResourceHandle> for String {
+    fn from(handle: ResourceHandle) -> Self {
+        handle.to_string()
+    }
+}
+
+/// Derived, read-only `<scheme>://{artifact}/dependencies` locator. Queries resolve to the
+/// dependency tree of the addressed artifact and can never be used as a mutation target.
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(try_from = "String", into = "String")]
+#[schemars(with = "String")]
+pub struct DependencyQuery {
+    handle: ResourceHandle,
+}
+
+impl DependencyQuery {
+    pub const SUFFIX: &'static str = "/dependencies";
+
+    pub fn new(handle: ResourceHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Parses `reference` when it is a resource handle carrying the `/dependencies` suffix.
+    /// Returns `Ok(None)` for every other locator form.
+    pub fn parse(reference: &str) -> Result<Option<Self>, SpecmanError> {
+        let trimmed = reference.trim();
+        let Some(artifact) = trimmed.strip_suffix(Self::SUFFIX) else {
+            return Ok(None);
+        };
+        if !["spec://", "impl://", "scratch://"]
+            .iter()
+            .any(|scheme| artifact.starts_with(scheme))
+        {
+            return Ok(None);
+        }
+        Ok(ResourceHandle::parse(artifact)?.map(Self::new))
+    }
+
+    pub fn handle(&self) -> &ResourceHandle {
+        &self.handle
+    }
+
+    pub fn artifact_id(&self) -> ArtifactId {
+        self.handle.artifact_id()
+    }
+
+    /// Fails with [`SpecmanError::ReadOnlyLocator`] when `reference` is a dependency query.
+    /// Mutation entry points call this before resolving their targets.
+    pub fn ensure_mutable(reference: &str) -> Result<(), SpecmanError> {
+        match Self::parse(reference)? {
+            Some(query) => Err(Self::read_only_error(&query.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn read_only_error(reference: &str) -> SpecmanError {
+        SpecmanError::ReadOnlyLocator(format!(
+            "{reference} is a derived dependency query; only query operations are supported"
+        ))
+    }
+}
+
+impl fmt::Display for DependencyQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.handle, Self::SUFFIX)
+    }
+}
+
+impl FromStr for DependencyQuery {
+    type Err = SpecmanError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::parse(raw)?.ok_or_else(|| {
+            SpecmanError::Dependency(format!(
+                "{raw} is not a dependency query (expected <scheme>://{{artifact}}/dependencies)"
+            ))
+        })
+    }
+}
+
+impl TryFrom<String> for DependencyQuery {
+    type Error = SpecmanError;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        raw.parse()
+    }
+}
+
+impl From<DependencyQuery> for String {
+    fn from(query: DependencyQuery) -> Self {
+        query.to_string()
+    }
+}
+
 impl ArtifactLocator {
     fn from_path(
         path: impl AsRef<Path>,
@@ -597,10 +1350,25 @@ impl ArtifactLocator {
         }
     }
 
-    fn load(&self, fetcher: &dyn ContentFetcher) -> Result<String, SpecmanError> {
+    fn load(
+        &self,
+        fetcher: &dyn ContentFetcher,
+        progress: &dyn ProgressObserver,
+    ) -> Result<String, SpecmanError> {
         match self {
             ArtifactLocator::File(path) => Ok(fs::read_to_string(path)?),
-            ArtifactLocator::Url(url) => fetcher.fetch(url),
+            ArtifactLocator::Url(url) => {
+                let url_text = url.as_str().to_string();
+                progress.on_progress(&ProgressEvent::FetchStarted {
+                    url: url_text.clone(),
+                });
+                let fetched = fetcher.fetch(url);
+                progress.on_progress(&ProgressEvent::FetchFinished {
+                    url: url_text,
+                    ok: fetched.is_ok(),
+                });
+                fetched
+            }
         }
     }
 }
@@ -609,82 +1377,89 @@ struct Traversal {
     workspace: WorkspacePaths,
     edges: BTreeSet<DependencyEdge>,
     visited: HashMap<String, ArtifactSummary>,
-    stack: Vec<String>,
+    /// Artifacts being resolved, outermost first, keyed like `visited`.
+    stack: Vec<(String, ArtifactSummary)>,
+    /// Entries followed between consecutive `stack` frames.
+    trail: Vec<ArtifactDependency>,
+    anchors: HashMap<String, Vec<String>>,
+    diagnostics: BTreeSet<DependencyDiagnostic>,
     fetcher: Arc<dyn ContentFetcher>,
+    progress: Arc<dyn ProgressObserver>,
+    /// Unsaved content for the artifact with this key, read instead of the file.
+    draft: Option<(String, String)>,
 }
 
 impl Traversal {
-    fn new(workspace: WorkspacePaths, fetcher: Arc<dyn ContentFetcher>) -> Self {
+    fn new(
+        workspace: WorkspacePaths,
+        fetcher: Arc<dyn ContentFetcher>,
+        progress: Arc<dyn ProgressObserver>,
+    ) -> Self {
         Self {
             workspace,
             edges: BTreeSet::new(),
             visited: HashMap::new(),
             stack: Vec::new(),
+            trail: Vec::new(),
+            anchors: HashMap::new(),
+            diagnostics: BTreeSet::new(),
             fetcher,
+            progress,
+            draft: None,
         }
     }
 
     fn visit(&mut self, locator: &ArtifactLocator) -> Result<ArtifactSummary, SpecmanError> {
         let key = locator.key();
-        if self.stack.contains(&key) {
-            let cycle = self
-                .stack
-                .iter()
-                .chain(std::iter::once(&key))
-                .cloned()
-                .collect::<Vec<_>>()
-                .join(" -> ");
-
-            let root_summary = self
-                .stack
-                .first()
-                .and_then(|first| self.visited.get(first).cloned())
-                .unwrap_or_else(|| ArtifactSummary {
-                    id: ArtifactId {
-                        kind: ArtifactKind::Specification,
-                        name: key.clone(),
-                    },
-                    ..Default::default()
-                });
-
-            let partial_tree = DependencyTree {
-                root: root_summary,
-                upstream: self
-                    .edges
-                    .iter()
-                    .filter(|edge| matches!(edge.relation, DependencyRelation::Upstream))
-                    .cloned()
-                    .collect(),
-                downstream: self
-                    .edges
-                    .iter()
-                    .filter(|edge| matches!(edge.relation, DependencyRelation::Downstream))
-                    .cloned()
-                    .collect(),
-                aggregate: self.edges.iter().cloned().collect(),
-            };
-
-            let serialized = serde_json::to_string(&partial_tree).unwrap_or_else(|_| "{}".into());
-            return Err(SpecmanError::Dependency(format!(
-                "dependency cycle detected: {cycle}; partial_tree={serialized}"
-            )));
-        }
-
         if let Some(summary) = self.visited.get(&key) {
             return Ok(summary.clone());
         }
 
-        self.stack.push(key.clone());
-        let document = ArtifactDocument::load(
+        let raw = match &self.draft {
+            Some((draft_key, raw)) if draft_key == &key => raw.clone(),
+            _ => locator.load(self.fetcher.as_ref(), self.progress.as_ref())?,
+        };
+        let document = ArtifactDocument::parse(
             locator,
+            raw,
             &self.workspace,
-            self.fetcher.as_ref(),
+            self.progress.as_ref(),
             DependencyResolutionMode::Strict,
         )?;
         let summary = document.summary.clone();
+        self.anchors.insert(key.clone(), document.anchors);
+        self.stack.push((key.clone(), summary.clone()));
 
         for dependency in document.dependencies {
-            let child = self.visit(&dependency.locator)?;
+            let dependency_key = dependency.locator.key();
+            let on_stack = self
+                .stack
+                .iter()
+                .position(|(frame, _)| frame == &dependency_key);
+            if let Some(start) = on_stack {
+                return Err(self.cycle_error(start, &summary, &dependency));
+            }
+            self.trail.push(dependency.clone());
+            let mut child = self.visit(&dependency.locator)?;
+            self.trail.pop();
+            if let Some(diagnostic) = dependency.check_version(&summary, &child) {
+                self.diagnostics.insert(diagnostic);
+            }
+            if let Some(fragment) = &dependency.fragment {
+                let anchors = &self.anchors[&dependency_key];
+                // Anchors are only checked in workspace files; fragments of remote documents
+                // are left to their URL.
+                if matches!(dependency.locator, ArtifactLocator::File(_))
+                    && !anchors.contains(fragment)
+                {
+                    return Err(SpecmanError::Dependency(format!(
+                        "heading #{fragment} not found in {} (referenced by {})",
+                        dependency.locator.describe(),
+                        locator.describe()
+                    )));
+                }
+                child.fragment = Some(fragment.clone());
+            }
             self.record_edge(summary.clone(), child, dependency.optional);
         }
 
@@ -694,6 +1469,61 @@ impl Traversal {
         Ok(summary)
     }
 
+    /// Describes the loop formed when `declaring` lists `closing`, which points back at
+    /// `self.stack[start]`.
+    fn cycle_error(
+        &self,
+        start: usize,
+        declaring: &ArtifactSummary,
+        closing: &ArtifactDependency,
+    ) -> SpecmanError {
+        let frames = &self.stack[start..];
+        let entry =
+            |from: &ArtifactSummary, to: &ArtifactSummary, step: &ArtifactDependency| CycleEntry {
+                from: from.id.clone(),
+                to: to.id.clone(),
+                field: step.field,
+                locator: step.declared.clone(),
+                optional: step.optional,
+            };
+        let entries = frames
+            .windows(2)
+            .zip(&self.trail[start..])
+            .map(|(pair, step)| entry(&pair[0].1, &pair[1].1, step))
+            .collect();
+        let closing = entry(declaring, &frames[0].1, closing);
+        let path = frames
+            .iter()
+            .map(|(_, summary)| summary.id.clone())
+            .chain(std::iter::once(closing.to.clone()))
+            .collect();
+
+        let partial_tree = DependencyTree {
+            root: self.stack[0].1.clone(),
+            upstream: self
+                .edges
+                .iter()
+                .filter(|edge| matches!(edge.relation, DependencyRelation::Upstream))
+                .cloned()
+                .collect(),
+            downstream: self
+                .edges
+                .iter()
+                .filter(|edge| matches!(edge.relation, DependencyRelation::Downstream))
+                .cloned()
+                .collect(),
+            aggregate: self.edges.iter().cloned().collect(),
+            diagnostics: self.diagnostics.iter().cloned().collect(),
+        };
+
+        SpecmanError::DependencyCycle(Box::new(DependencyCycle {
+            path,
+            entries,
+            closing,
+            partial_tree,
+        }))
+    }
+
     fn record_edge(&mut self, parent: ArtifactSummary, child: ArtifactSummary, optional: bool) {
         let upstream = DependencyEdge {
             from: parent,
@@ -701,6 +1531,11 @@ impl Traversal {
             relation: DependencyRelation::Upstream,
             optional,
         };
+        self.progress.on_progress(&ProgressEvent::EdgeDiscovered {
+            from: upstream.from.id.clone(),
+            to: upstream.to.id.clone(),
+            relation: upstream.relation.clone(),
+        });
         self.edges.insert(upstream);
     }
 }
@@ -714,6 +1549,7 @@ impl WorkspaceInventorySnapshot {
     fn build(
         workspace: &WorkspacePaths,
         fetcher: Arc<dyn ContentFetcher>,
+        progress: &dyn ProgressObserver,
     ) -> Result<Self, SpecmanError> {
         let mut files = gather_workspace_artifacts(workspace)?;
         files.sort();
@@ -726,6 +1562,7 @@ impl WorkspaceInventorySnapshot {
                 &locator,
                 workspace,
                 fetcher.as_ref(),
+                progress,
                 DependencyResolutionMode::BestEffort,
             )?;
             entries.push(InventoryEntry {
@@ -762,6 +1599,96 @@ impl WorkspaceInventorySnapshot {
 
         dependents
     }
+
+    /// Summaries for every artifact in the workspace, ordered by path.
+    pub fn artifacts(&self) -> Vec<ArtifactSummary> {
+        self.entries
+            .iter()
+            .map(|entry| entry.summary.clone())
+            .collect()
+    }
+
+    /// Upstream edges for every declared dependency in the workspace. Dependencies that
+    /// point outside the inventory (remote URLs, missing files) are summarized from their
+    /// locator.
+    pub fn edges(&self) -> Vec<DependencyEdge> {
+        self.resolved_dependencies()
+            .into_iter()
+            .map(|(from, to, dependency)| DependencyEdge {
+                from: from.clone(),
+                to,
+                relation: DependencyRelation::Upstream,
+                optional: dependency.optional,
+            })
+            .collect()
+    }
+
+    /// Every declared dependency along with the front-matter field it came from.
+    pub fn hops(&self) -> Vec<DependencyHop> {
+        self.resolved_dependencies()
+            .into_iter()
+            .map(|(from, to, dependency)| DependencyHop {
+                from: from.id.clone(),
+                to: to.id,
+                field: dependency.field,
+                optional: dependency.optional,
+            })
+            .collect()
+    }
+
+    /// Declared version requirements that the workspace artifact they point at does not
+    /// satisfy. Dependencies outside the inventory are not checked.
+    pub fn version_diagnostics(&self) -> Vec<DependencyDiagnostic> {
+        let known: BTreeSet<&ArtifactId> =
+            self.entries.iter().map(|entry| &entry.summary.id).collect();
+        let mut diagnostics: Vec<DependencyDiagnostic> = self
+            .resolved_dependencies()
+            .into_iter()
+            .filter(|(_, to, _)| known.contains(&to.id))
+            .filter_map(|(from, to, dependency)| dependency.check_version(from, &to))
+            .collect();
+        diagnostics.sort();
+        diagnostics.dedup();
+        diagnostics
+    }
+
+    fn resolved_dependencies(
+        &self,
+    ) -> Vec<(&ArtifactSummary, ArtifactSummary, &ArtifactDependency)> {
+        let by_locator: HashMap<&str, &ArtifactSummary> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .summary
+                    .metadata
+                    .get("locator")
+                    .map(|locator| (locator.as_str(), &entry.summary))
+            })
+            .collect();
+
+        let mut resolved = Vec::new();
+        for entry in self.entries.iter() {
+            for dependency in &entry.dependencies {
+                let locator = dependency.locator.describe();
+                let mut to = match by_locator.get(locator.as_str()) {
+                    Some(summary) => (*summary).clone(),
+                    None => ArtifactSummary {
+                        id: ArtifactId {
+                            kind: infer_kind_from_locator(&dependency.locator),
+                            name: infer_name(&dependency.locator),
+                        },
+                        version: None,
+                        metadata: BTreeMap::from([("locator".to_string(), locator)]),
+                        fragment: None,
+                    },
+                };
+                to.fragment = dependency.fragment.clone();
+                resolved.push((&entry.summary, to, dependency));
+            }
+        }
+        resolved
+    }
 }
 
 #[derive(Clone)]
@@ -776,7 +1703,35 @@ pub struct InventoryDependent {
     pub optional: bool,
 }
 
-fn gather_workspace_artifacts(workspace: &WorkspacePaths) -> Result<Vec<PathBuf>, SpecmanError> {
+fn collect_simple_paths<'a>(
+    node: &'a ArtifactId,
+    target: &ArtifactId,
+    adjacency: &HashMap<&'a ArtifactId, Vec<&'a DependencyHop>>,
+    on_path: &mut Vec<&'a ArtifactId>,
+    current: &mut Vec<DependencyHop>,
+    paths: &mut Vec<Vec<DependencyHop>>,
+) {
+    let Some(next) = adjacency.get(node) else {
+        return;
+    };
+    for hop in next {
+        if &hop.to == target {
+            let mut path = current.clone();
+            path.push((*hop).clone());
+            paths.push(path);
+        } else if !on_path.contains(&&hop.to) {
+            on_path.push(&hop.to);
+            current.push((*hop).clone());
+            collect_simple_paths(&hop.to, target, adjacency, on_path, current, paths);
+            current.pop();
+            on_path.pop();
+        }
+    }
+}
+
+pub(crate) fn gather_workspace_artifacts(
+    workspace: &WorkspacePaths,
+) -> Result<Vec<PathBuf>, SpecmanError> {
     let mut files = Vec::new();
     collect_named_files(&workspace.spec_dir(), "spec.md", &mut files)?;
     collect_named_files(&workspace.impl_dir(), "impl.md", &mut files)?;
@@ -811,27 +1766,40 @@ impl ArtifactDocument {
         locator: &ArtifactLocator,
         workspace: &WorkspacePaths,
         fetcher: &dyn ContentFetcher,
+        progress: &dyn ProgressObserver,
+        mode: DependencyResolutionMode,
+    ) -> Result<Self, SpecmanError> {
+        let raw = locator.load(fetcher, progress)?;
+        Self::parse(locator, raw, workspace, progress, mode)
+    }
+
+    fn parse(
+        locator: &ArtifactLocator,
+        raw: String,
+        workspace: &WorkspacePaths,
+        progress: &dyn ProgressObserver,
         mode: DependencyResolutionMode,
     ) -> Result<Self, SpecmanError> {
-        let raw = locator.load(fetcher)?;
         let mut metadata = BTreeMap::new();
         metadata.insert("locator".into(), locator.describe());
 
-        let (frontmatter, status) = front_matter::optional_front_matter(&raw);
-        if let Some(status) = status {
-            metadata.insert("metadata_status".into(), status);
-        }
-
-        let parsed = frontmatter.and_then(|fm| match ArtifactFrontMatter::from_yaml_str(fm) {
-            Ok(value) => Some(value),
-            Err(err) => {
-                metadata.insert(
-                    "metadata_status".into(),
-                    format!("invalid-front-matter: {err}"),
-                );
+        let parsed = match front_matter::split_front_matter(&raw) {
+            Ok(split) => match split.parse() {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    let err = err.in_file(locator.describe());
+                    metadata.insert(
+                        "metadata_status".into(),
+                        format!("invalid-front-matter: {err}"),
+                    );
+                    None
+                }
+            },
+            Err(_) => {
+                metadata.insert("metadata_status".into(), "missing".into());
                 None
             }
-        });
+        };
 
         let (name, version, kind, dependencies) = if let Some(front) = parsed.as_ref() {
             let kind = artifact_kind_from_front(front);
@@ -855,11 +1823,17 @@ impl ArtifactDocument {
             id: ArtifactId { kind, name },
             version,
             metadata,
+            fragment: None,
         };
+        progress.on_progress(&ProgressEvent::ArtifactVisited {
+            artifact: summary.id.clone(),
+            locator: locator.describe(),
+        });
 
         Ok(Self {
             summary,
             dependencies,
+            anchors: headings::heading_anchors(&raw),
         })
     }
 }
@@ -875,12 +1849,17 @@ fn resolve_dependencies(
     match front {
         ArtifactFrontMatter::Specification(spec) => {
             for entry in &spec.dependencies {
-                let (reference, optional) = match entry {
-                    DependencyEntry::Simple(value) => (value.as_str(), false),
-                    DependencyEntry::Detailed(obj) => {
-                        (obj.reference.as_str(), obj.optional.unwrap_or(false))
-                    }
+                let (reference, optional, requirement) = match entry {
+                    DependencyEntry::Simple(value) => (value.as_str(), false, None),
+                    DependencyEntry::Detailed(obj) => (
+                        obj.reference.as_str(),
+                        obj.optional.unwrap_or(false),
+                        obj.version.as_deref(),
+                    ),
                 };
+                let version = parse_requirement(requirement, reference, metadata, mode)?;
+                let declared = reference.to_string();
+                let (reference, fragment) = split_local_fragment(reference);
                 let locator = match resolve_dependency_locator(reference, locator, workspace) {
                     Ok(locator) => Some(locator),
                     Err(err) => {
@@ -894,11 +1873,20 @@ fn resolve_dependencies(
                 let Some(locator) = locator else {
                     continue;
                 };
-                deps.push(ArtifactDependency { locator, optional });
+                deps.push(ArtifactDependency {
+                    locator,
+                    declared,
+                    optional,
+                    field: DependencyField::Dependencies,
+                    version,
+                    fragment: fragment.map(str::to_string),
+                });
             }
         }
         ArtifactFrontMatter::Implementation(implementation) => {
             if let Some(spec_ref) = implementation.spec.as_deref() {
+                let declared = spec_ref.to_string();
+                let (spec_ref, fragment) = split_local_fragment(spec_ref);
                 let locator = match resolve_dependency_locator(spec_ref, locator, workspace) {
                     Ok(locator) => Some(locator),
                     Err(err) => {
@@ -912,13 +1900,24 @@ fn resolve_dependencies(
                 if let Some(locator) = locator {
                     deps.push(ArtifactDependency {
                         locator,
+                        declared,
                         optional: false,
+                        field: DependencyField::Spec,
+                        version: None,
+                        fragment: fragment.map(str::to_string),
                     });
                 }
             }
             for reference in &implementation.references {
+                let version = parse_requirement(
+                    reference.version.as_deref(),
+                    &reference.reference,
+                    metadata,
+                    mode,
+                )?;
+                let (reference_locator, fragment) = split_local_fragment(&reference.reference);
                 let locator =
-                    match resolve_dependency_locator(&reference.reference, locator, workspace) {
+                    match resolve_dependency_locator(reference_locator, locator, workspace) {
                         Ok(locator) => Some(locator),
                         Err(err) => {
                             if mode.is_strict() {
@@ -933,12 +1932,18 @@ fn resolve_dependencies(
                 };
                 deps.push(ArtifactDependency {
                     locator,
+                    declared: reference.reference.clone(),
                     optional: reference.optional.unwrap_or(false),
+                    field: DependencyField::References,
+                    version,
+                    fragment: fragment.map(str::to_string),
                 });
             }
         }
         ArtifactFrontMatter::Scratch(scratch) => {
             if let Some(target) = scratch.target.as_deref() {
+                let declared = target.to_string();
+                let (target, fragment) = split_local_fragment(target);
                 let locator = match resolve_scratch_target_locator(target, locator, workspace) {
                     Ok(locator) => Some(locator),
                     Err(err) => {
@@ -952,17 +1957,26 @@ fn resolve_dependencies(
                 if let Some(locator) = locator {
                     deps.push(ArtifactDependency {
                         locator,
+                        declared,
                         optional: false,
+                        field: DependencyField::Target,
+                        version: None,
+                        fragment: fragment.map(str::to_string),
                     });
                 }
             }
             for entry in &scratch.dependencies {
-                let (reference, optional) = match entry {
-                    DependencyEntry::Simple(value) => (value.as_str(), false),
-                    DependencyEntry::Detailed(obj) => {
-                        (obj.reference.as_str(), obj.optional.unwrap_or(false))
-                    }
+                let (reference, optional, requirement) = match entry {
+                    DependencyEntry::Simple(value) => (value.as_str(), false, None),
+                    DependencyEntry::Detailed(obj) => (
+                        obj.reference.as_str(),
+                        obj.optional.unwrap_or(false),
+                        obj.version.as_deref(),
+                    ),
                 };
+                let version = parse_requirement(requirement, reference, metadata, mode)?;
+                let declared = reference.to_string();
+                let (reference, fragment) = split_local_fragment(reference);
                 let locator = match resolve_scratch_dependency_locator(reference, workspace) {
                     Ok(locator) => Some(locator),
                     Err(err) => {
@@ -976,13 +1990,30 @@ fn resolve_dependencies(
                 let Some(locator) = locator else {
                     continue;
                 };
-                deps.push(ArtifactDependency { locator, optional });
+                deps.push(ArtifactDependency {
+                    locator,
+                    declared,
+                    optional,
+                    field: DependencyField::Dependencies,
+                    version,
+                    fragment: fragment.map(str::to_string),
+                });
             }
         }
     }
     Ok(deps)
 }
 
+/// Like [`headings::split_fragment`], but leaves URLs whole so their fragment stays part of
+/// the [`Url`] rather than becoming a heading the traversal must find.
+fn split_local_fragment(reference: &str) -> (&str, Option<&str>) {
+    if reference.starts_with("https://") || reference.starts_with("http://") {
+        (reference, None)
+    } else {
+        headings::split_fragment(reference)
+    }
+}
+
 fn artifact_kind_from_front(front: &ArtifactFrontMatter) -> ArtifactKind {
     match front.kind() {
         FrontMatterKind::Specification => ArtifactKind::Specification,
@@ -1011,29 +2042,80 @@ fn resolve_workspace_path(
     let path = if candidate.is_absolute() {
         candidate.to_path_buf()
     } else if let Some(base_dir) = base {
-        base_dir.join(candidate)
+        // A new artifact's folder does not exist before it is written, so `..` cannot be
+        // resolved by the filesystem.
+        if base_dir.exists() {
+            base_dir.join(candidate)
+        } else {
+            lexical_join(base_dir, candidate)
+        }
     } else {
         workspace.root().join(candidate)
     };
 
     let canonical = fs::canonicalize(&path)?;
     if !canonical.starts_with(workspace.root()) {
-        return Err(SpecmanError::Workspace(format!(
-            "locator {} escapes workspace {}",
-            canonical.display(),
-            workspace.root().display()
-        )));
+        return Err(CodedError::new(
+            ErrorCode::WorkspaceEscape,
+            format!(
+                "locator {} escapes workspace {}",
+                canonical.display(),
+                workspace.root().display()
+            ),
+        )
+        .with_locator(canonical.display().to_string())
+        .into());
     }
     Ok(canonical)
 }
 
+/// Path a local locator (handle or relative path, without fragment) points at, resolved
+/// lexically so the target need not exist yet. URLs resolve to `None`.
+pub(crate) fn local_reference_path(
+    reference: &str,
+    base: &Path,
+    workspace: &WorkspacePaths,
+) -> Option<PathBuf> {
+    if let Ok(Some(handle)) = ResourceHandle::parse(reference) {
+        return Some(handle.to_path(workspace));
+    }
+    if reference.contains("://") {
+        return None;
+    }
+    Some(lexical_join(base, Path::new(reference)))
+}
+
+/// Joins `relative` onto `base`, applying `..` without consulting the filesystem.
+fn lexical_join(base: &Path, relative: &Path) -> PathBuf {
+    let mut resolved = base.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(part) => resolved.push(part),
+            Component::RootDir | Component::Prefix(_) => {
+                resolved = PathBuf::from(component.as_os_str())
+            }
+            Component::CurDir => {}
+        }
+    }
+    resolved
+}
+
 /// Verifies that a dependency reference stays within the workspace boundaries or points to a
-/// supported locator (HTTPS URLs or SpecMan resource handles).
+/// supported locator (HTTPS URLs or SpecMan resource handles). Every path that writes a
+/// locator into front matter calls this; `parent` is the declaring document's folder and
+/// need not exist yet.
 pub fn validate_workspace_reference(
     reference: &str,
     parent: &Path,
     workspace: &WorkspacePaths,
 ) -> Result<(), SpecmanError> {
+    if reference.trim().is_empty() {
+        return Err(SpecmanError::Dependency("locator must not be empty".into()));
+    }
+    DependencyQuery::ensure_mutable(reference)?;
     if reference.starts_with("http://") {
         return Err(SpecmanError::Dependency(format!(
             "unsupported url scheme in {reference}; use https"
@@ -1044,16 +2126,42 @@ pub fn validate_workspace_reference(
         return Ok(());
     }
 
-    if let Some(handle) = ResourceHandle::parse(reference)? {
-        handle.into_locator(workspace)?;
-        return Ok(());
-    }
+    let (reference, fragment) = headings::split_fragment(reference);
+    let path = match ResourceHandle::parse(reference)? {
+        Some(handle) => match handle.into_locator(workspace)? {
+            ArtifactLocator::File(path) => path,
+            ArtifactLocator::Url(_) => return Ok(()),
+        },
+        None => resolve_workspace_path(Path::new(reference), Some(parent), workspace)?,
+    };
 
-    let candidate = Path::new(reference);
-    resolve_workspace_path(candidate, Some(parent), workspace)?;
+    if let Some(fragment) = fragment {
+        let anchors = headings::heading_anchors(&fs::read_to_string(&path)?);
+        if !anchors.iter().any(|anchor| anchor == fragment) {
+            return Err(SpecmanError::Dependency(format!(
+                "heading #{fragment} not found in {}",
+                path.display()
+            )));
+        }
+    }
     Ok(())
 }
 
+/// [`validate_workspace_reference`] for a scratch pad `target`, which resolves from the
+/// workspace root first and falls back to the scratch pad folder.
+pub fn validate_scratch_target(
+    target: &str,
+    scratch_dir: &Path,
+    workspace: &WorkspacePaths,
+) -> Result<(), SpecmanError> {
+    match validate_workspace_reference(target, workspace.root(), workspace) {
+        Err(SpecmanError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+            validate_workspace_reference(target, scratch_dir, workspace)
+        }
+        result => result,
+    }
+}
+
 /// Resolves dependency references that may point to workspace-relative paths, HTTPS URLs, or
 /// SpecMan resource handles.
 fn resolve_dependency_locator(
@@ -1153,6 +2261,34 @@ fn resolve_scratch_dependency_locator(
     ArtifactLocator::from_path(slug_path, workspace, Some(workspace.root()))
 }
 
+fn parse_requirement(
+    raw: Option<&str>,
+    reference: &str,
+    metadata: &mut BTreeMap<String, String>,
+    mode: DependencyResolutionMode,
+) -> Result<Option<VersionReq>, SpecmanError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    match VersionReq::parse(raw) {
+        Ok(requirement) => Ok(Some(requirement)),
+        Err(err) => {
+            let err = SpecmanError::Dependency(format!(
+                "invalid version requirement `{raw}` for {reference}: {err}"
+            ));
+            if mode.is_strict() {
+                return Err(err);
+            }
+            record_dependency_error(metadata, reference, &err);
+            Ok(None)
+        }
+    }
+}
+
+fn describe_artifact(id: &ArtifactId) -> String {
+    format!("{}://{}", ResourceHandle::scheme_for(id.kind), id.name)
+}
+
 fn record_dependency_error(
     metadata: &mut BTreeMap<String, String>,
     reference: &str,
@@ -1318,6 +2454,54 @@ name: specman-core
         assert_eq!(tree.root.id.name, "specman-core");
     }
 
+    #[test]
+    fn dependency_queries_resolve_trees_and_reject_mutation() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("impl/core-rust")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\n---\n# Core\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("impl/core-rust/impl.md"),
+            "---\nname: core-rust\nspec: spec://core\n---\n# Impl\n",
+        )
+        .unwrap();
+
+        let query = DependencyQuery::parse("spec://Core/dependencies")
+            .expect("parse query")
+            .expect("query detected");
+        assert_eq!(query.to_string(), "spec://core/dependencies");
+        assert_eq!(query.artifact_id().kind, ArtifactKind::Specification);
+        assert!(DependencyQuery::parse("spec://core").unwrap().is_none());
+        assert!(
+            DependencyQuery::parse("spec/core/dependencies")
+                .unwrap()
+                .is_none()
+        );
+
+        let mapper = FilesystemDependencyMapper::new(FilesystemWorkspaceLocator::new(&root));
+        let tree = mapper
+            .dependency_tree_from_locator("spec://core/dependencies")
+            .expect("query builds tree");
+        assert_eq!(tree.root.id.name, "core");
+        assert!(
+            tree.downstream
+                .iter()
+                .any(|edge| edge.from.id.name == "core-rust")
+        );
+
+        let err = ResourceHandle::parse("impl://core-rust/dependencies").expect_err("read-only");
+        assert!(matches!(err, SpecmanError::ReadOnlyLocator(_)));
+        let err = DependencyQuery::ensure_mutable("scratch://pad/dependencies").expect_err("guard");
+        assert!(matches!(err, SpecmanError::ReadOnlyLocator(_)));
+        DependencyQuery::ensure_mutable("spec://core").expect("plain handles are mutable");
+    }
+
     #[test]
     fn resource_handle_parser_normalizes_slug() {
         let handle = ResourceHandle::parse("spec://SpecMan-Core").expect("parse succeeded");
@@ -1331,6 +2515,61 @@ name: specman-core
         assert_eq!(scratch.slug, "pad_one");
     }
 
+    #[test]
+    fn resource_handles_enumerate_and_resolve_front_matter() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman/scratchpad/notes")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("spec/Not_Canonical")).unwrap();
+        fs::create_dir_all(root.join("spec/empty")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\nversion: \"1.0.0\"\n---\n# Core\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("spec/Not_Canonical/spec.md"),
+            "---\nname: other\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(".specman/scratchpad/notes/scratch.md"),
+            "---\nname: notes\ntarget: spec/core/spec.md\nwork_type:\n  draft: {}\n---\n",
+        )
+        .unwrap();
+        let workspace = WorkspacePaths::new(root.clone(), root.join(".specman"));
+
+        let specs = ResourceHandle::enumerate(&workspace, ArtifactKind::Specification).unwrap();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].to_string(), "spec://core");
+
+        let all = ResourceHandle::enumerate_all(&workspace).unwrap();
+        let rendered: Vec<String> = all.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered, vec!["spec://core", "scratch://notes"]);
+
+        let resolved = all[0].resolve(&workspace).expect("resolve spec handle");
+        assert_eq!(resolved.front_matter.name(), Some("core"));
+        assert!(resolved.path.ends_with("spec/core/spec.md"));
+        let scratch = all[1].resolve(&workspace).expect("resolve scratch handle");
+        assert!(scratch.front_matter.as_scratch().is_some());
+
+        let missing: ResourceHandle = "impl://absent".parse().unwrap();
+        let err = missing.resolve(&workspace).expect_err("missing artifact");
+        assert!(matches!(err, SpecmanError::Workspace(_)));
+    }
+
+    #[test]
+    fn resource_handles_serialize_as_strings() {
+        let handle: ResourceHandle = "impl://Engine".parse().unwrap();
+        let json = serde_json::to_value(&handle).unwrap();
+        assert_eq!(json, serde_json::json!("impl://engine"));
+
+        let round_trip: ResourceHandle = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, handle);
+        assert!(serde_json::from_value::<ResourceHandle>(serde_json::json!("spec/core")).is_err());
+    }
+
     #[test]
     fn resolve_dependency_locator_supports_resource_handles() {
         let temp = tempdir().unwrap();
@@ -1578,10 +2817,38 @@ dependencies:
             .dependency_tree_from_path(root.join("spec/alpha/spec.md"))
             .expect_err("cycle expected");
 
-        match err {
-            SpecmanError::Dependency(msg) => assert!(msg.contains("cycle")),
-            other => panic!("unexpected error: {other:?}"),
-        }
+        let SpecmanError::DependencyCycle(cycle) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        let names: Vec<&str> = cycle.path.iter().map(|id| id.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta", "alpha"]);
+        assert_eq!(cycle.entries.len(), 1);
+        assert_eq!(cycle.entries[0].locator, "../beta/spec.md");
+        assert_eq!(cycle.closing.from.name, "beta");
+        assert_eq!(cycle.closing.field, DependencyField::Dependencies);
+        assert_eq!(cycle.closing.locator, "../alpha/spec.md");
+        assert_eq!(cycle.partial_tree.root.id.name, "alpha");
+        assert_eq!(
+            cycle.suggestions()[0],
+            "remove `../alpha/spec.md` in spec://beta's `dependencies`"
+        );
+
+        // Optional entries do not excuse a loop.
+        fs::write(
+            root.join("spec/alpha/spec.md"),
+            "---\nname: alpha\nversion: \"1.0.0\"\ndependencies:\n  - ref: ../beta/spec.md\n    optional: true\n---\n# Alpha\n",
+        )
+        .unwrap();
+        let mapper =
+            FilesystemDependencyMapper::new(FilesystemWorkspaceLocator::new(root.to_path_buf()));
+        let err = mapper
+            .dependency_tree_from_path(root.join("spec/alpha/spec.md"))
+            .expect_err("optional loops are still cycles");
+        let SpecmanError::DependencyCycle(cycle) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert!(cycle.entries[0].optional);
+        assert!(!cycle.closing.optional);
     }
 
     #[test]
@@ -1622,10 +2889,8 @@ version: "0.1.0"
             .dependency_tree_from_path(workspace.join("spec/origin/spec.md"))
             .expect_err("workspace violation");
 
-        match err {
-            SpecmanError::Workspace(msg) => assert!(msg.contains("escapes workspace")),
-            other => panic!("expected workspace error, got {other:?}"),
-        }
+        assert_eq!(err.code(), ErrorCode::WorkspaceEscape);
+        assert!(err.to_string().contains("escapes workspace"), "{err}");
     }
 
     #[test]
@@ -1660,6 +2925,182 @@ version: "0.1.0"
         assert_eq!(tree.upstream[0].to.id.name, "remote-child");
     }
 
+    #[derive(Default)]
+    struct RecordingProgress {
+        events: Mutex<Vec<ProgressEvent>>,
+    }
+
+    impl ProgressObserver for RecordingProgress {
+        fn on_progress(&self, event: &ProgressEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn graph_services_report_traversal_progress() {
+        let temp = tempdir().unwrap();
+        let workspace_root = temp.path().join("workspace");
+        fs::create_dir_all(workspace_root.join(".specman")).unwrap();
+        fs::create_dir_all(workspace_root.join("spec/local")).unwrap();
+        fs::write(
+            workspace_root.join("spec/local/spec.md"),
+            "---\nname: local\nversion: \"1.0.0\"\ndependencies:\n  - https://example.com/child.md\n---\n# Local",
+        )
+        .unwrap();
+        let fetcher: Arc<dyn ContentFetcher> = Arc::new(StubFetcher::new(&[(
+            "https://example.com/child.md",
+            "---\nname: remote-child\nversion: \"0.1.0\"\n---\n# Child",
+        )]));
+        let graph = DependencyGraphServices::with_fetcher(
+            FilesystemWorkspaceLocator::new(&workspace_root),
+            fetcher,
+        );
+        let observer = Arc::new(RecordingProgress::default());
+        graph.set_progress_observer(observer.clone());
+
+        graph
+            .dependency_tree_from_locator("spec/local/spec.md")
+            .expect("tree builds");
+
+        let events = observer.events.lock().unwrap().clone();
+        let url = "https://example.com/child.md".to_string();
+        let position = |wanted: &ProgressEvent| events.iter().position(|event| event == wanted);
+        let started = position(&ProgressEvent::FetchStarted { url: url.clone() }).unwrap();
+        let finished = position(&ProgressEvent::FetchFinished { url, ok: true }).unwrap();
+        assert!(started < finished);
+        let local = ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: "local".into(),
+        };
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ProgressEvent::EdgeDiscovered { from, to, .. }
+                if from == &local && to.name == "remote-child"
+        )));
+        let visits = events
+            .iter()
+            .filter(|event| matches!(event, ProgressEvent::ArtifactVisited { .. }))
+            .count();
+        // Root and child during traversal, plus the workspace file during the inventory build.
+        assert_eq!(visits, 3);
+    }
+
+    #[test]
+    fn fetch_remote_artifacts_follows_remote_dependencies() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/local")).unwrap();
+        fs::write(
+            root.join("spec/local/spec.md"),
+            "---\nname: local\nversion: \"1.0.0\"\ndependencies:\n  - https://example.com/specs/child.md\n  - https://example.com/missing.md\n---\n",
+        )
+        .unwrap();
+        let fetcher: Arc<dyn ContentFetcher> = Arc::new(StubFetcher::new(&[
+            (
+                "https://example.com/specs/child.md",
+                "---\nname: child\nversion: \"1.0.0\"\ndependencies:\n  - grandchild.md\n---\n",
+            ),
+            (
+                "https://example.com/specs/grandchild.md",
+                "---\nname: grandchild\nversion: \"1.0.0\"\n---\n",
+            ),
+        ]));
+        let graph =
+            DependencyGraphServices::with_fetcher(FilesystemWorkspaceLocator::new(&root), fetcher);
+
+        let fetched = graph.fetch_remote_artifacts().unwrap();
+        let summary: Vec<(&str, Option<&str>, bool)> = fetched
+            .iter()
+            .map(|remote| {
+                (
+                    remote.url.as_str(),
+                    remote.artifact.as_ref().map(|id| id.name.as_str()),
+                    remote.error.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("https://example.com/missing.md", None, true),
+                ("https://example.com/specs/child.md", Some("child"), false),
+                (
+                    "https://example.com/specs/grandchild.md",
+                    Some("grandchild"),
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn hierarchy_nests_edges_and_marks_shared_nodes() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        for (name, deps) in [
+            (
+                "top",
+                "\n  - ../left/spec.md\n  - ref: ../right/spec.md\n    optional: true",
+            ),
+            ("left", "\n  - ../shared/spec.md"),
+            ("right", "\n  - ../shared/spec.md"),
+            ("shared", " []"),
+        ] {
+            let dir = root.join("spec").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("spec.md"),
+                format!(
+                    "---\nname: {name}\nversion: \"1.0.0\"\ndependencies:{deps}\n---\n# {name}\n"
+                ),
+            )
+            .unwrap();
+        }
+        let graph = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(root));
+        let id = |name: &str| ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: name.into(),
+        };
+        let tree = graph.dependency_tree_for_artifact(&id("top")).unwrap();
+
+        let upstream = tree.hierarchy(DependencyRelation::Upstream);
+        assert_eq!(upstream.artifact.id, id("top"));
+        assert_eq!(upstream.depth, 0);
+        let [left, right] = upstream.children.as_slice() else {
+            panic!("expected two children: {upstream:?}");
+        };
+        assert_eq!(
+            (left.artifact.id.name.as_str(), left.optional),
+            ("left", false)
+        );
+        assert_eq!(
+            (right.artifact.id.name.as_str(), right.optional),
+            ("right", true)
+        );
+        assert_eq!(left.children[0].artifact.id, id("shared"));
+        assert_eq!(left.children[0].depth, 2);
+        assert!(!left.children[0].seen_earlier);
+        assert!(right.children[0].seen_earlier);
+        assert!(right.children[0].children.is_empty());
+
+        let shared = graph.dependency_tree_for_artifact(&id("shared")).unwrap();
+        let downstream = shared.hierarchy(DependencyRelation::Downstream);
+        let names: Vec<_> = downstream
+            .children
+            .iter()
+            .map(|node| node.artifact.id.name.as_str())
+            .collect();
+        assert_eq!(names, ["left", "right"]);
+        assert!(
+            downstream
+                .children
+                .iter()
+                .all(|node| node.depth == 1 && node.children.is_empty())
+        );
+    }
+
     #[test]
     fn parse_front_matter_handles_bom_and_crlf() {
         let doc = "\u{feff}---\r\nname: alpha\r\nversion: \"1.0.0\"\r\n---\r\n# Body";
@@ -1786,6 +3227,286 @@ work_type:
         assert!(upstream.contains("specman-library"));
     }
 
+    #[test]
+    fn paths_between_reports_the_field_behind_each_hop() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("spec/data")).unwrap();
+        fs::create_dir_all(root.join("impl/lib")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\nversion: \"1.0.0\"\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("spec/data/spec.md"),
+            "---\nname: data\nversion: \"1.0.0\"\ndependencies:\n  - ../core/spec.md\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("impl/lib/impl.md"),
+            r#"---
+name: lib
+spec: ../../spec/data/spec.md
+references:
+  - ref: ../../spec/core/spec.md
+    type: specification
+    optional: true
+---
+"#,
+        )
+        .unwrap();
+
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let lib = ArtifactId {
+            kind: ArtifactKind::Implementation,
+            name: "lib".into(),
+        };
+        let core = ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: "core".into(),
+        };
+
+        let all = services
+            .paths_between(&lib, &core, PathSelection::All)
+            .unwrap();
+        let fields: Vec<Vec<DependencyField>> = all
+            .iter()
+            .map(|path| path.iter().map(|hop| hop.field).collect())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                vec![DependencyField::References],
+                vec![DependencyField::Spec, DependencyField::Dependencies],
+            ]
+        );
+        assert!(all[0][0].optional);
+        assert_eq!(all[1][0].to.name, "data");
+
+        let shortest = services
+            .paths_between(&lib, &core, PathSelection::Shortest)
+            .unwrap();
+        assert_eq!(shortest, vec![all[0].clone()]);
+
+        assert!(
+            services
+                .paths_between(&core, &lib, PathSelection::All)
+                .unwrap()
+                .is_empty()
+        );
+        let missing = ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: "missing".into(),
+        };
+        assert!(
+            services
+                .paths_between(&lib, &missing, PathSelection::All)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn paths_between_an_artifact_and_itself_lists_its_cycles() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/left")).unwrap();
+        fs::create_dir_all(root.join("spec/right")).unwrap();
+        fs::write(
+            root.join("spec/left/spec.md"),
+            "---\nname: left\nversion: \"1.0.0\"\ndependencies:\n  - ../right/spec.md\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("spec/right/spec.md"),
+            "---\nname: right\nversion: \"1.0.0\"\ndependencies:\n  - ../left/spec.md\n---\n",
+        )
+        .unwrap();
+
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let left = ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: "left".into(),
+        };
+        for selection in [PathSelection::All, PathSelection::Shortest] {
+            let cycles = services.paths_between(&left, &left, selection).unwrap();
+            assert_eq!(cycles.len(), 1);
+            let names: Vec<&str> = cycles[0].iter().map(|hop| hop.to.name.as_str()).collect();
+            assert_eq!(names, vec!["right", "left"]);
+        }
+    }
+
+    #[test]
+    fn unsatisfied_version_requirements_become_diagnostics() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("spec/data")).unwrap();
+        fs::create_dir_all(root.join("impl/lib")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\nversion: \"1.4.0\"\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("spec/data/spec.md"),
+            "---\nname: data\nversion: \"1.0.0\"\ndependencies:\n  - ref: ../core/spec.md\n    version: \"^1.2\"\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("impl/lib/impl.md"),
+            r#"---
+name: lib
+spec: ../../spec/data/spec.md
+references:
+  - ref: ../../spec/core/spec.md
+    type: specification
+    version: ">=2.0"
+---
+"#,
+        )
+        .unwrap();
+
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let lib = ArtifactId {
+            kind: ArtifactKind::Implementation,
+            name: "lib".into(),
+        };
+        let core = ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: "core".into(),
+        };
+
+        let tree = services.dependency_tree(&lib).unwrap();
+        assert_eq!(tree.diagnostics.len(), 1);
+        let diagnostic = &tree.diagnostics[0];
+        assert_eq!(diagnostic.artifact, lib);
+        assert_eq!(diagnostic.dependency, core);
+        assert_eq!(diagnostic.field, DependencyField::References);
+        assert_eq!(diagnostic.requirement, ">=2.0");
+        assert_eq!(diagnostic.found, Some(SemVer::new(1, 4, 0)));
+        assert_eq!(
+            diagnostic.message,
+            "impl://lib requires spec://core >=2.0 via `references`, found 1.4.0"
+        );
+
+        // The downstream view of the dependency surfaces the same requirement.
+        let core_tree = services.dependency_tree(&core).unwrap();
+        assert_eq!(core_tree.diagnostics, tree.diagnostics);
+
+        let inventory = services.inventory_snapshot().unwrap();
+        assert_eq!(inventory.version_diagnostics(), tree.diagnostics);
+
+        fs::write(
+            root.join("impl/lib/impl.md"),
+            "---\nname: lib\nspec: ../../spec/data/spec.md\nreferences:\n  - ref: ../../spec/core/spec.md\n    type: specification\n    version: \"not a range\"\n---\n",
+        )
+        .unwrap();
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let err = services.dependency_tree(&lib).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid version requirement `not a range`")
+        );
+    }
+
+    #[test]
+    fn fragment_locators_resolve_to_concept_level_edges() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("impl/lib")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\nversion: \"1.0.0\"\n---\n# Core\n\n## Concept: Foo\n\n## Concept: Bar\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("impl/lib/impl.md"),
+            r#"---
+name: lib
+spec: ../../spec/core/spec.md#concept-foo
+references:
+  - ref: spec://core#concept-bar
+    type: specification
+---
+"#,
+        )
+        .unwrap();
+
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let lib = ArtifactId {
+            kind: ArtifactKind::Implementation,
+            name: "lib".into(),
+        };
+        let tree = services.dependency_tree(&lib).unwrap();
+        let fragments: Vec<Option<&str>> = tree
+            .upstream
+            .iter()
+            .map(|edge| {
+                assert_eq!(edge.to.id.name, "core");
+                edge.to.fragment.as_deref()
+            })
+            .collect();
+        assert_eq!(fragments, vec![Some("concept-bar"), Some("concept-foo")]);
+
+        let children = tree.hierarchy(DependencyRelation::Upstream).children;
+        assert_eq!(children.len(), 2);
+        assert!(children[1].seen_earlier);
+
+        let workspace = services.workspace_paths().unwrap();
+        let parent = root.join("impl/lib");
+        validate_workspace_reference("../../spec/core/spec.md#concept-foo", &parent, &workspace)
+            .unwrap();
+        let missing = validate_workspace_reference("spec://core#concept-baz", &parent, &workspace)
+            .unwrap_err();
+        assert!(
+            missing
+                .to_string()
+                .contains("heading #concept-baz not found")
+        );
+
+        fs::write(
+            root.join("impl/lib/impl.md"),
+            "---\nname: lib\nspec: ../../spec/core/spec.md#concept-baz\n---\n",
+        )
+        .unwrap();
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(&root));
+        let err = services.dependency_tree(&lib).unwrap_err();
+        assert!(err.to_string().contains("heading #concept-baz not found"));
+    }
+
+    #[test]
+    fn url_fragments_stay_on_the_url_and_are_not_checked_as_headings() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/local")).unwrap();
+        fs::write(
+            root.join("spec/local/spec.md"),
+            "---\nname: local\nversion: \"1.0.0\"\ndependencies:\n  - https://example.com/child.md#L10\n---\n# Local",
+        )
+        .unwrap();
+        let fetcher: Arc<dyn ContentFetcher> = Arc::new(StubFetcher::new(&[(
+            "https://example.com/child.md#L10",
+            "---\nname: remote-child\nversion: \"0.1.0\"\n---\n# Child",
+        )]));
+        let graph =
+            DependencyGraphServices::with_fetcher(FilesystemWorkspaceLocator::new(&root), fetcher);
+
+        let tree = graph
+            .dependency_tree_from_locator("spec/local/spec.md")
+            .expect("remote fragments do not fail traversal");
+        assert_eq!(tree.upstream.len(), 1);
+        assert_eq!(tree.upstream[0].to.id.name, "remote-child");
+        assert_eq!(tree.upstream[0].to.fragment, None);
+    }
+
     #[test]
     fn scratch_dependencies_resolve_named_and_path_variants() {
         let temp = tempdir().unwrap();
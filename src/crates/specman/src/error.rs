@@ -1,7 +1,14 @@
 use std::fmt;
+use std::str::FromStr;
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::dependency_tree::{ArtifactId, DependencyCycle, DependencyField};
+use crate::front_matter::FrontMatterError;
+use crate::lint::DATA_MODEL_SPEC;
+
 /// High-level error type shared across SpecMan components.
 #[derive(Debug, Error)]
 pub enum SpecmanError {
@@ -9,12 +16,27 @@ pub enum SpecmanError {
     Template(String),
     #[error("dependency error: {0}")]
     Dependency(String),
+    /// Front matter is missing, malformed, or does not match its artifact's schema.
+    #[error("front matter error: {0}")]
+    FrontMatter(Box<FrontMatterError>),
+    /// Dependencies loop back to an artifact that is still being resolved.
+    #[error("dependency cycle detected: {0}")]
+    DependencyCycle(Box<DependencyCycle>),
     #[error("workspace error: {0}")]
     Workspace(String),
     #[error("serialization error: {0}")]
     Serialization(String),
+    /// A mutation targeted a derived, query-only locator such as `spec://{artifact}/dependencies`.
+    #[error("read-only locator: {0}")]
+    ReadOnlyLocator(String),
+    /// Another operation holds the advisory lock for the targeted artifact.
+    #[error("lock error: {0}")]
+    Lock(String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+    /// A failure with a specific [`ErrorCode`] and the locator or artifact it concerns.
+    #[error("{0}")]
+    Coded(Box<CodedError>),
 }
 
 impl From<serde_json::Error> for SpecmanError {
@@ -28,11 +50,412 @@ impl SpecmanError {
         match self {
             SpecmanError::Template(msg) => SpecmanError::Template(format!("{ctx}: {msg}")),
             SpecmanError::Dependency(msg) => SpecmanError::Dependency(format!("{ctx}: {msg}")),
+            SpecmanError::FrontMatter(mut err) => {
+                err.message = format!("{ctx}: {}", err.message);
+                SpecmanError::FrontMatter(err)
+            }
+            SpecmanError::DependencyCycle(cycle) => SpecmanError::DependencyCycle(cycle),
             SpecmanError::Workspace(msg) => SpecmanError::Workspace(format!("{ctx}: {msg}")),
             SpecmanError::Serialization(msg) => {
                 SpecmanError::Serialization(format!("{ctx}: {msg}"))
             }
+            SpecmanError::ReadOnlyLocator(msg) => {
+                SpecmanError::ReadOnlyLocator(format!("{ctx}: {msg}"))
+            }
+            SpecmanError::Lock(msg) => SpecmanError::Lock(format!("{ctx}: {msg}")),
             SpecmanError::Io(err) => SpecmanError::Io(err),
+            SpecmanError::Coded(mut err) => {
+                err.message = format!("{ctx}: {}", err.message);
+                SpecmanError::Coded(err)
+            }
+        }
+    }
+
+    /// Stable code identifying what kind of failure this is.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SpecmanError::Template(_) => ErrorCode::TemplateInvalid,
+            SpecmanError::Dependency(_) => ErrorCode::DependencyUnresolved,
+            SpecmanError::FrontMatter(_) => ErrorCode::FrontMatterInvalid,
+            SpecmanError::DependencyCycle(_) => ErrorCode::DependencyCycle,
+            SpecmanError::Workspace(_) => ErrorCode::WorkspaceInvalid,
+            SpecmanError::Serialization(_) => ErrorCode::Serialization,
+            SpecmanError::ReadOnlyLocator(_) => ErrorCode::ReadOnlyLocator,
+            SpecmanError::Lock(_) => ErrorCode::LockBusy,
+            SpecmanError::Io(_) => ErrorCode::Io,
+            SpecmanError::Coded(err) => err.code,
+        }
+    }
+
+    /// Machine-readable payload: the code plus whichever of the offending locator, artifact,
+    /// and data-model heading are known.
+    pub fn details(&self) -> ErrorDetails {
+        let mut details = ErrorDetails {
+            code: self.code(),
+            message: self.to_string(),
+            locator: None,
+            artifact: None,
+            heading: self.code().heading().map(citation),
+        };
+        match self {
+            SpecmanError::Coded(err) => {
+                details.locator = err.locator.clone();
+                details.artifact = err.artifact.clone();
+            }
+            SpecmanError::DependencyCycle(cycle) => {
+                details.locator = Some(cycle.closing.locator.clone());
+                details.artifact = Some(cycle.closing.from.clone());
+                details.heading = Some(citation(match cycle.closing.field {
+                    DependencyField::Dependencies => "dependencies",
+                    DependencyField::References => "references",
+                    DependencyField::Spec => "implementation-metadata",
+                    DependencyField::Target => "target-artifact",
+                }));
+            }
+            SpecmanError::FrontMatter(err) => details.locator = err.file.clone(),
+            _ => {}
         }
+        details
+    }
+
+    /// Records the document (path or URL) a front matter error came from, unless it already
+    /// names one. Other errors pass through unchanged.
+    pub fn in_file<T: fmt::Display>(self, file: T) -> Self {
+        match self {
+            SpecmanError::FrontMatter(mut err) => {
+                err.file.get_or_insert_with(|| file.to_string());
+                SpecmanError::FrontMatter(err)
+            }
+            other => other,
+        }
+    }
+}
+
+/// A failure whose kind is known precisely enough to carry its own [`ErrorCode`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodedError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Locator (path, handle, or URL) the failure is about.
+    pub locator: Option<String>,
+    /// Artifact whose content caused the failure.
+    pub artifact: Option<ArtifactId>,
+}
+
+impl CodedError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            locator: None,
+            artifact: None,
+        }
+    }
+
+    pub fn with_locator(mut self, locator: impl Into<String>) -> Self {
+        self.locator = Some(locator.into());
+        self
+    }
+
+    pub fn with_artifact(mut self, artifact: ArtifactId) -> Self {
+        self.artifact = Some(artifact);
+        self
+    }
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code.category(), self.message)
+    }
+}
+
+impl From<CodedError> for SpecmanError {
+    fn from(err: CodedError) -> Self {
+        SpecmanError::Coded(Box::new(err))
+    }
+}
+
+/// Stable, machine-readable identifiers for SpecMan failures. Codes are never renamed or
+/// reused, so automation can branch on them instead of on message text.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum ErrorCode {
+    #[serde(rename = "SM-DEP-CYCLE")]
+    DependencyCycle,
+    #[serde(rename = "SM-DEP-UNRESOLVED")]
+    DependencyUnresolved,
+    #[serde(rename = "SM-FM-INVALID")]
+    FrontMatterInvalid,
+    #[serde(rename = "SM-SCRATCH-TARGET")]
+    ScratchTarget,
+    #[serde(rename = "SM-TPL-MISSING-TOKEN")]
+    TemplateMissingToken,
+    #[serde(rename = "SM-TPL-INVALID")]
+    TemplateInvalid,
+    #[serde(rename = "SM-WS-NOT-FOUND")]
+    WorkspaceNotFound,
+    #[serde(rename = "SM-WS-ESCAPE")]
+    WorkspaceEscape,
+    #[serde(rename = "SM-WS-INVALID")]
+    WorkspaceInvalid,
+    #[serde(rename = "SM-WS-READ-ONLY")]
+    ReadOnlyLocator,
+    #[serde(rename = "SM-LOCK-BUSY")]
+    LockBusy,
+    #[serde(rename = "SM-SERIALIZATION")]
+    Serialization,
+    #[serde(rename = "SM-IO")]
+    Io,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 13] = [
+        ErrorCode::DependencyCycle,
+        ErrorCode::DependencyUnresolved,
+        ErrorCode::FrontMatterInvalid,
+        ErrorCode::ScratchTarget,
+        ErrorCode::TemplateMissingToken,
+        ErrorCode::TemplateInvalid,
+        ErrorCode::WorkspaceNotFound,
+        ErrorCode::WorkspaceEscape,
+        ErrorCode::WorkspaceInvalid,
+        ErrorCode::ReadOnlyLocator,
+        ErrorCode::LockBusy,
+        ErrorCode::Serialization,
+        ErrorCode::Io,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::DependencyCycle => "SM-DEP-CYCLE",
+            ErrorCode::DependencyUnresolved => "SM-DEP-UNRESOLVED",
+            ErrorCode::FrontMatterInvalid => "SM-FM-INVALID",
+            ErrorCode::ScratchTarget => "SM-SCRATCH-TARGET",
+            ErrorCode::TemplateMissingToken => "SM-TPL-MISSING-TOKEN",
+            ErrorCode::TemplateInvalid => "SM-TPL-INVALID",
+            ErrorCode::WorkspaceNotFound => "SM-WS-NOT-FOUND",
+            ErrorCode::WorkspaceEscape => "SM-WS-ESCAPE",
+            ErrorCode::WorkspaceInvalid => "SM-WS-INVALID",
+            ErrorCode::ReadOnlyLocator => "SM-WS-READ-ONLY",
+            ErrorCode::LockBusy => "SM-LOCK-BUSY",
+            ErrorCode::Serialization => "SM-SERIALIZATION",
+            ErrorCode::Io => "SM-IO",
+        }
+    }
+
+    /// Message prefix shared with the untyped variants of the same family.
+    fn category(self) -> &'static str {
+        match self {
+            ErrorCode::DependencyCycle | ErrorCode::DependencyUnresolved => "dependency error",
+            ErrorCode::FrontMatterInvalid => "front matter error",
+            ErrorCode::ScratchTarget
+            | ErrorCode::TemplateMissingToken
+            | ErrorCode::TemplateInvalid => "template error",
+            ErrorCode::WorkspaceNotFound
+            | ErrorCode::WorkspaceEscape
+            | ErrorCode::WorkspaceInvalid => "workspace error",
+            ErrorCode::ReadOnlyLocator => "read-only locator",
+            ErrorCode::LockBusy => "lock error",
+            ErrorCode::Serialization => "serialization error",
+            ErrorCode::Io => "io error",
+        }
+    }
+
+    /// One-line description of the failure.
+    pub fn summary(self) -> &'static str {
+        match self {
+            ErrorCode::DependencyCycle => {
+                "Dependencies loop back to an artifact that is still being resolved."
+            }
+            ErrorCode::DependencyUnresolved => {
+                "A dependency, reference, spec, or target locator could not be resolved or parsed."
+            }
+            ErrorCode::FrontMatterInvalid => {
+                "An artifact's YAML front matter is missing, malformed, or has fields of the wrong shape."
+            }
+            ErrorCode::ScratchTarget => {
+                "A scratch pad's target or heading lists do not fit its work type."
+            }
+            ErrorCode::TemplateMissingToken => {
+                "A template requires a token that was not supplied, or a rendered artifact still contains `{{token}}` placeholders."
+            }
+            ErrorCode::TemplateInvalid => {
+                "A template, template pointer, or artifact name could not be used."
+            }
+            ErrorCode::WorkspaceNotFound => {
+                "No ancestor of the starting directory contains a `.specman/` folder."
+            }
+            ErrorCode::WorkspaceEscape => {
+                "A locator resolves to a path outside the workspace root."
+            }
+            ErrorCode::WorkspaceInvalid => {
+                "A path, handle, or artifact does not fit the workspace layout."
+            }
+            ErrorCode::ReadOnlyLocator => "A mutation targeted a derived, query-only locator.",
+            ErrorCode::LockBusy => {
+                "Another operation holds the advisory lock for the targeted artifact."
+            }
+            ErrorCode::Serialization => "Data could not be encoded or decoded.",
+            ErrorCode::Io => "A filesystem or network operation failed.",
+        }
+    }
+
+    /// What to change to make the failure go away.
+    pub fn remediation(self) -> &'static str {
+        match self {
+            ErrorCode::DependencyCycle => {
+                "Remove one `dependencies` or `references` entry on the loop, or repoint a `spec` or `target` field. Marking an entry `optional: true` does not break a loop. The error lists one fix per entry."
+            }
+            ErrorCode::DependencyUnresolved => {
+                "Check that the locator names an existing artifact (paths are relative to the declaring document), that heading anchors exist in the target, and that version requirements are valid semver. Run `specman fetch` for https:// locators used offline."
+            }
+            ErrorCode::FrontMatterInvalid => {
+                "Open the file at the reported line and column. Front matter must start and end with `---` and be a YAML mapping of the fields the data model defines for the artifact kind."
+            }
+            ErrorCode::ScratchTarget => {
+                "`draft` and `revision` pads target a specification; `feat`, `ref`, and `fix` pads target an implementation. Listed headings must exist in the relevant specification."
+            }
+            ErrorCode::TemplateMissingToken => {
+                "Pass the missing value (for example with `--token key=value` or the command's flags), or fill in every `{{token}}` left in the rendered document."
+            }
+            ErrorCode::TemplateInvalid => {
+                "Check the template pointer with `specman template set`, or remove it to fall back to the embedded default. Artifact names must be lowercase words separated by hyphens."
+            }
+            ErrorCode::WorkspaceNotFound => {
+                "Run from inside a workspace, pass `--workspace <path>`, or create a `.specman/` directory at the repository root."
+            }
+            ErrorCode::WorkspaceEscape => {
+                "Point the locator at a file inside the workspace root, or vendor the document with `specman vendor` and use its `spec://` handle."
+            }
+            ErrorCode::WorkspaceInvalid => {
+                "Use a workspace-relative path or a `spec://`, `impl://`, or `scratch://` handle naming an existing artifact folder."
+            }
+            ErrorCode::ReadOnlyLocator => {
+                "Edit the artifact itself rather than a derived locator such as `spec://{artifact}/dependencies`."
+            }
+            ErrorCode::LockBusy => {
+                "Wait for the other operation to finish and retry. Remove the lock file under `.specman/` only if its process is gone."
+            }
+            ErrorCode::Serialization => {
+                "Report the failure along with the input that triggered it; it usually indicates a bug."
+            }
+            ErrorCode::Io => "Check that the path exists and is readable and writable, then retry.",
+        }
+    }
+
+    /// Anchor of the `spec/specman-data-model` heading the failure relates to, if any.
+    pub fn heading(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::DependencyCycle | ErrorCode::DependencyUnresolved => Some("dependencies"),
+            ErrorCode::ScratchTarget => Some("work-type"),
+            _ => None,
+        }
+    }
+
+    /// Everything `specman explain` prints for this code.
+    pub fn explanation(self) -> ErrorExplanation {
+        ErrorExplanation {
+            code: self,
+            summary: self.summary().to_string(),
+            remediation: self.remediation().to_string(),
+            heading: self.heading().map(citation),
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = SpecmanError;
+
+    /// Accepts codes in any case, e.g. `sm-dep-cycle`.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let trimmed = raw.trim();
+        ErrorCode::ALL
+            .into_iter()
+            .find(|code| code.as_str().eq_ignore_ascii_case(trimmed))
+            .ok_or_else(|| SpecmanError::Workspace(format!("unknown error code '{trimmed}'")))
+    }
+}
+
+/// Machine-readable form of a [`SpecmanError`], as carried in CLI `--json` output and MCP
+/// error data.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ErrorDetails {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ArtifactId>,
+    /// Data-model heading the failure relates to, e.g.
+    /// `spec/specman-data-model/spec.md#dependencies`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+}
+
+/// Description and remediation of one [`ErrorCode`].
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ErrorExplanation {
+    pub code: ErrorCode,
+    pub summary: String,
+    pub remediation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+}
+
+fn citation(heading: &str) -> String {
+    format!("{DATA_MODEL_SPEC}#{heading}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_tree::ArtifactKind;
+
+    #[test]
+    fn codes_round_trip_through_strings_and_serde() {
+        for code in ErrorCode::ALL {
+            assert_eq!(
+                code.as_str().to_lowercase().parse::<ErrorCode>().unwrap(),
+                code
+            );
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+        assert!("SM-NOPE".parse::<ErrorCode>().is_err());
+    }
+
+    #[test]
+    fn coded_errors_carry_their_payload() {
+        let err: SpecmanError = CodedError::new(ErrorCode::ScratchTarget, "wrong target")
+            .with_locator("spec/core/spec.md")
+            .with_artifact(ArtifactId {
+                kind: ArtifactKind::ScratchPad,
+                name: "fix-core".into(),
+            })
+            .into();
+        let err = err.context("scratch://fix-core");
+        assert_eq!(
+            err.to_string(),
+            "template error: scratch://fix-core: wrong target"
+        );
+
+        let details = err.details();
+        assert_eq!(details.code, ErrorCode::ScratchTarget);
+        assert_eq!(details.locator.as_deref(), Some("spec/core/spec.md"));
+        assert_eq!(details.artifact.unwrap().name, "fix-core");
+        assert_eq!(
+            details.heading.as_deref(),
+            Some("spec/specman-data-model/spec.md#work-type")
+        );
+        assert_eq!(
+            SpecmanError::Lock("busy".into()).code(),
+            ErrorCode::LockBusy
+        );
     }
 }
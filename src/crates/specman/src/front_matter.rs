@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::fmt;
 
 use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject, SubschemaValidation};
 use schemars::{JsonSchema, SchemaGenerator};
@@ -68,8 +69,10 @@ pub struct ScratchFrontMatter {
     pub dependencies: Vec<DependencyEntry>,
 }
 
-/// Unified view of artifact-specific front matter.
-#[derive(Debug, Clone)]
+/// Unified view of artifact-specific front matter. Serializes untagged so the output matches
+/// the YAML shape defined by the SpecMan Data Model.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
 pub enum ArtifactFrontMatter {
     Specification(SpecificationFrontMatter),
     Implementation(ImplementationFrontMatter),
@@ -88,16 +91,15 @@ impl ArtifactFrontMatter {
     /// Parses YAML into a typed front matter enum using the discriminators recorded in the
     /// SpecMan Data Model.
     pub fn from_yaml_str(yaml: &str) -> Result<Self, SpecmanError> {
-        let value: YamlValue = serde_yaml::from_str(yaml)
-            .map_err(|err| SpecmanError::Serialization(err.to_string()))?;
-        Self::from_value(value)
+        let kind = detect_kind(&parse_mapping(yaml)?);
+        Self::from_yaml_str_for_kind(yaml, kind)
     }
 
     /// Builds a typed front matter enum from a previously parsed YAML value.
     pub fn from_value(value: YamlValue) -> Result<Self, SpecmanError> {
         let mapping = value
             .as_mapping()
-            .ok_or_else(|| SpecmanError::Template("front matter must be a YAML mapping".into()))?;
+            .ok_or_else(|| FrontMatterError::new("front matter must be a YAML mapping"))?;
         let kind = detect_kind(mapping);
         match kind {
             FrontMatterKind::Specification => parse_variant::<SpecificationFrontMatter>(&value)
@@ -110,6 +112,21 @@ impl ArtifactFrontMatter {
         }
     }
 
+    /// Parses YAML as the variant for `kind`, skipping discriminator detection. Used when the
+    /// artifact class is already known from its location (e.g., a resource handle).
+    pub fn from_yaml_str_for_kind(yaml: &str, kind: FrontMatterKind) -> Result<Self, SpecmanError> {
+        parse_mapping(yaml)?;
+        match kind {
+            FrontMatterKind::Specification => deserialize_yaml::<SpecificationFrontMatter>(yaml)
+                .map(ArtifactFrontMatter::Specification),
+            FrontMatterKind::Implementation => deserialize_yaml::<ImplementationFrontMatter>(yaml)
+                .map(ArtifactFrontMatter::Implementation),
+            FrontMatterKind::ScratchPad => {
+                deserialize_yaml::<ScratchFrontMatter>(yaml).map(ArtifactFrontMatter::Scratch)
+            }
+        }
+    }
+
     /// Provides typed access to the specification variant.
     pub fn as_specification(&self) -> Option<&SpecificationFrontMatter> {
         match self {
@@ -182,6 +199,9 @@ pub struct DependencyObject {
     pub reference: String,
     #[serde(default, rename = "optional")]
     pub optional: Option<bool>,
+    /// Semver requirement (e.g. `^1.2`) the dependency's `version` must satisfy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 /// Implementation reference entry defined alongside the Implementation Metadata rules.
@@ -193,6 +213,9 @@ pub struct ReferenceEntry {
     pub reference_type: Option<String>,
     #[serde(default, rename = "optional")]
     pub optional: Option<bool>,
+    /// Semver requirement (e.g. `^1.2`) the referenced artifact's `version` must satisfy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 /// Represents either a plain string or object-form implementation library reference.
@@ -394,19 +417,43 @@ pub struct FrontMatterSplit<'a> {
     pub body: &'a str,
 }
 
+impl FrontMatterSplit<'_> {
+    /// Parses the YAML, picking the variant from its discriminator fields. Error positions
+    /// count the opening `---` fence, so they match the document's line numbers.
+    pub fn parse(&self) -> Result<ArtifactFrontMatter, SpecmanError> {
+        ArtifactFrontMatter::from_yaml_str(self.yaml).map_err(below_fence)
+    }
+
+    /// Parses the YAML as the variant for `kind`; see [`FrontMatterSplit::parse`].
+    pub fn parse_as(&self, kind: FrontMatterKind) -> Result<ArtifactFrontMatter, SpecmanError> {
+        ArtifactFrontMatter::from_yaml_str_for_kind(self.yaml, kind).map_err(below_fence)
+    }
+
+    /// Deserializes the YAML into any front matter type; see [`FrontMatterSplit::parse`].
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, SpecmanError> {
+        deserialize_yaml(self.yaml).map_err(below_fence)
+    }
+}
+
 /// Attempts to split raw markdown content into YAML front matter and body.
 pub fn split_front_matter(content: &str) -> Result<FrontMatterSplit<'_>, SpecmanError> {
     let stripped = content.trim_start_matches('\u{feff}');
     let Some(rest) = stripped.strip_prefix("---") else {
-        return Err(SpecmanError::Template(
-            "missing front matter delimiter (---)".into(),
-        ));
+        return Err(FrontMatterError::located(
+            "missing front matter delimiter (---)",
+            stripped,
+            1,
+            1,
+        )
+        .into());
     };
 
     let rest = rest
         .strip_prefix("\r\n")
         .or_else(|| rest.strip_prefix('\n'))
-        .ok_or_else(|| SpecmanError::Template("missing newline after front matter start".into()))?;
+        .ok_or_else(|| {
+            FrontMatterError::located("missing newline after front matter start", stripped, 1, 4)
+        })?;
 
     if let Some(idx) = rest.find("\n---") {
         let yaml = rest[..idx].trim_end();
@@ -417,9 +464,150 @@ pub fn split_front_matter(content: &str) -> Result<FrontMatterSplit<'_>, Specman
             .unwrap_or(after);
         Ok(FrontMatterSplit { yaml, body })
     } else {
-        Err(SpecmanError::Template(
-            "missing closing front matter delimiter (---)".into(),
-        ))
+        Err(FrontMatterError::located(
+            "missing closing front matter delimiter (---)",
+            stripped,
+            1,
+            1,
+        )
+        .into())
+    }
+}
+
+/// A front matter problem, positioned in its source when the YAML parser reports where.
+/// Lines and columns are 1-based.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct FrontMatterError {
+    pub message: String,
+    /// Document the front matter came from (path or URL), when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Source lines around `line`, for rendering a code frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excerpt: Vec<SourceLine>,
+}
+
+/// One numbered line of a [`FrontMatterError`] excerpt.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SourceLine {
+    pub line: usize,
+    pub text: String,
+}
+
+impl FrontMatterError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            excerpt: Vec::new(),
+        }
+    }
+
+    fn located(message: impl Into<String>, source: &str, line: usize, column: usize) -> Self {
+        let first = line.saturating_sub(2).max(1);
+        let excerpt = source
+            .lines()
+            .enumerate()
+            .map(|(index, text)| (index + 1, text))
+            .skip(first - 1)
+            .take_while(|(number, _)| *number <= line + 1)
+            .map(|(line, text)| SourceLine {
+                line,
+                text: text.trim_end_matches('\r').to_string(),
+            })
+            .collect();
+        Self {
+            line: Some(line),
+            column: Some(column),
+            excerpt,
+            ..Self::new(message)
+        }
+    }
+
+    fn from_yaml(err: &serde_yaml::Error, source: &str) -> Self {
+        let message = err.to_string();
+        match err.location() {
+            // serde_yaml reports the start of the mapping (and leaves it out of the message)
+            // when it cannot tell which value failed, e.g. inside flattened fields.
+            Some(location) if location.index() > 0 || message.contains(" at line ") => {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                let message = message.replacen(&suffix, "", 1);
+                Self::located(message, source, location.line(), location.column())
+            }
+            _ => Self::new(message),
+        }
+    }
+
+    /// Moves the position down by `lines`, including line numbers quoted in the message.
+    fn shifted(mut self, lines: usize) -> Self {
+        self.line = self.line.map(|line| line + lines);
+        for source in &mut self.excerpt {
+            source.line += lines;
+        }
+        let mut message = String::with_capacity(self.message.len());
+        let mut rest = self.message.as_str();
+        while let Some(index) = rest.find("at line ") {
+            let (before, after) = rest.split_at(index + "at line ".len());
+            message.push_str(before);
+            let digits = after.len()
+                - after
+                    .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                    .len();
+            match after[..digits].parse::<usize>() {
+                Ok(line) => message.push_str(&(line + lines).to_string()),
+                Err(_) => message.push_str(&after[..digits]),
+            }
+            rest = &after[digits..];
+        }
+        message.push_str(rest);
+        self.message = message;
+        self
+    }
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => write!(f, "{file}:{line}:{column}: ")?,
+            (Some(file), _, _) => write!(f, "{file}: ")?,
+            (None, Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            _ => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl From<FrontMatterError> for SpecmanError {
+    fn from(err: FrontMatterError) -> Self {
+        SpecmanError::FrontMatter(Box::new(err))
+    }
+}
+
+/// Shifts positions reported against the bare YAML so they count the opening fence line.
+fn below_fence(err: SpecmanError) -> SpecmanError {
+    match err {
+        SpecmanError::FrontMatter(err) => {
+            let mut err = err.shifted(1);
+            if err.excerpt.first().is_some_and(|first| first.line == 2)
+                && err.line.is_some_and(|line| line <= 3)
+            {
+                err.excerpt.insert(
+                    0,
+                    SourceLine {
+                        line: 1,
+                        text: "---".into(),
+                    },
+                );
+            }
+            SpecmanError::FrontMatter(Box::new(err))
+        }
+        other => other,
     }
 }
 
@@ -431,12 +619,137 @@ pub fn optional_front_matter(content: &str) -> (Option<&str>, Option<String>) {
     }
 }
 
+/// Stamps a freshly rendered specification with its `name` and `version`, appending any
+/// `dependencies` the template did not already list.
+pub fn update_spec_document(
+    content: &str,
+    name: &str,
+    version: &str,
+    dependencies: &[String],
+) -> Result<String, SpecmanError> {
+    rewrite_front_matter(content, |doc| {
+        doc.insert(YamlValue::from("name"), YamlValue::from(name));
+        doc.insert(YamlValue::from("version"), YamlValue::from(version));
+        if !dependencies.is_empty() {
+            let mut sequence = current_sequence(doc, "dependencies");
+            for dep in dependencies {
+                let value = YamlValue::from(dep.clone());
+                // Templates that expand `{{dependencies}}` already list these entries.
+                if !sequence.contains(&value) {
+                    sequence.push(value);
+                }
+            }
+            doc.insert(
+                YamlValue::from("dependencies"),
+                YamlValue::Sequence(sequence),
+            );
+        }
+    })
+}
+
+/// Stamps a freshly rendered implementation with its name, target specification, language,
+/// and source location. The specification becomes the only `references` entry.
+pub fn update_impl_document(
+    content: &str,
+    name: &str,
+    spec_locator: &str,
+    language: &str,
+    location: &str,
+) -> Result<String, SpecmanError> {
+    rewrite_front_matter(content, |doc| {
+        doc.insert(YamlValue::from("name"), YamlValue::from(name));
+        doc.insert(YamlValue::from("spec"), YamlValue::from(spec_locator));
+        doc.insert(YamlValue::from("location"), YamlValue::from(location));
+
+        let mut language_map = Mapping::new();
+        language_map.insert(YamlValue::from("language"), YamlValue::from(language));
+        language_map.insert(
+            YamlValue::from("properties"),
+            YamlValue::Mapping(Mapping::new()),
+        );
+        language_map.insert(
+            YamlValue::from("libraries"),
+            YamlValue::Sequence(Vec::new()),
+        );
+        doc.insert(
+            YamlValue::from("primary_language"),
+            YamlValue::Mapping(language_map),
+        );
+
+        let mut references = Mapping::new();
+        references.insert(YamlValue::from("ref"), YamlValue::from(spec_locator));
+        references.insert(YamlValue::from("type"), YamlValue::from("specification"));
+        references.insert(YamlValue::from("optional"), YamlValue::from(false));
+        doc.insert(
+            YamlValue::from("references"),
+            YamlValue::Sequence(vec![YamlValue::Mapping(references)]),
+        );
+    })
+}
+
+/// Stamps a freshly rendered scratch pad with its target, branch, and bare work type key.
+pub fn update_scratch_document(
+    content: &str,
+    target: &str,
+    branch: &str,
+    work_type: &str,
+) -> Result<String, SpecmanError> {
+    rewrite_front_matter(content, |doc| {
+        doc.insert(YamlValue::from("target"), YamlValue::from(target));
+        doc.insert(YamlValue::from("branch"), YamlValue::from(branch));
+        let mut work_map = Mapping::new();
+        work_map.insert(
+            YamlValue::from(work_type),
+            YamlValue::Mapping(Mapping::new()),
+        );
+        doc.insert(YamlValue::from("work_type"), YamlValue::Mapping(work_map));
+    })
+}
+
+/// Applies `edit` to the front matter of `content` and re-serializes it, keeping the body.
+pub(crate) fn rewrite_front_matter<F>(content: &str, edit: F) -> Result<String, SpecmanError>
+where
+    F: FnOnce(&mut Mapping),
+{
+    let split = split_front_matter(content)?;
+    let mut doc = parse_mapping(split.yaml).map_err(below_fence)?;
+    edit(&mut doc);
+    let yaml =
+        serde_yaml::to_string(&doc).map_err(|err| SpecmanError::Serialization(err.to_string()))?;
+    Ok(format!("---\n{yaml}---\n{}", split.body))
+}
+
+fn current_sequence(doc: &Mapping, key: &str) -> Vec<YamlValue> {
+    doc.get(YamlValue::from(key))
+        .and_then(|value| value.as_sequence())
+        .cloned()
+        .unwrap_or_default()
+}
+
 fn parse_variant<T>(value: &YamlValue) -> Result<T, SpecmanError>
 where
     T: DeserializeOwned,
 {
     serde_yaml::from_value(value.clone())
-        .map_err(|err| SpecmanError::Serialization(err.to_string()))
+        .map_err(|err| FrontMatterError::new(err.to_string()).into())
+}
+
+/// Parses `yaml` from source so errors keep their line and column.
+fn deserialize_yaml<T>(yaml: &str) -> Result<T, SpecmanError>
+where
+    T: DeserializeOwned,
+{
+    serde_yaml::from_str(yaml).map_err(|err| FrontMatterError::from_yaml(&err, yaml).into())
+}
+
+/// Parses `yaml` and checks that it is a mapping.
+fn parse_mapping(yaml: &str) -> Result<Mapping, SpecmanError> {
+    match deserialize_yaml(yaml)? {
+        YamlValue::Mapping(mapping) => Ok(mapping),
+        _ => {
+            Err(FrontMatterError::located("front matter must be a YAML mapping", yaml, 1, 1).into())
+        }
+    }
 }
 
 fn detect_kind(mapping: &Mapping) -> FrontMatterKind {
@@ -517,4 +830,68 @@ work_type:
             serde_yaml::from_str(&serialized).expect("deserialize work type");
         assert_eq!(deserialized.kind(), ScratchWorkTypeKind::Revision);
     }
+
+    #[test]
+    fn kind_directed_parse_serializes_in_data_model_shape() {
+        let yaml = r#"
+name: spec-core
+spec: not-an-implementation-field
+dependencies:
+    - ref: ../data/spec.md
+      optional: true
+"#;
+
+        let front =
+            ArtifactFrontMatter::from_yaml_str_for_kind(yaml, FrontMatterKind::Specification)
+                .expect("parse as specification");
+        assert_eq!(front.kind(), FrontMatterKind::Specification);
+
+        let json = serde_json::to_value(&front).expect("serialize front matter");
+        assert_eq!(json["name"], "spec-core");
+        assert_eq!(json["dependencies"][0]["ref"], "../data/spec.md");
+        assert!(json.get("Specification").is_none());
+    }
+
+    #[test]
+    fn errors_point_at_document_lines_below_the_fence() {
+        let content = "---\nname: spec-core\nversion: \"1.0.0\"\ndependencies: nope\n---\n# Body\n";
+        let split = split_front_matter(content).unwrap();
+        let err = split
+            .parse_as(FrontMatterKind::Specification)
+            .unwrap_err()
+            .in_file("spec/spec-core/spec.md");
+        let SpecmanError::FrontMatter(err) = err else {
+            panic!("expected a front matter error, got {err:?}");
+        };
+        assert_eq!(err.file.as_deref(), Some("spec/spec-core/spec.md"));
+        assert_eq!((err.line, err.column), (Some(4), Some(15)));
+        assert!(!err.message.contains("at line"), "{}", err.message);
+        assert_eq!(
+            err.excerpt.iter().map(|line| line.line).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(err.excerpt[2].text, "dependencies: nope");
+        assert!(
+            err.to_string()
+                .starts_with("spec/spec-core/spec.md:4:15: dependencies: invalid type"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn syntax_errors_include_the_opening_fence_in_the_excerpt() {
+        let content = "---\nname: [spec-core\n---\n";
+        let err = split_front_matter(content).unwrap().parse().unwrap_err();
+        let SpecmanError::FrontMatter(err) = err else {
+            panic!("expected a front matter error, got {err:?}");
+        };
+        assert!(err.line.is_some_and(|line| line >= 2));
+        assert_eq!(err.excerpt[0].text, "---");
+
+        let Err(SpecmanError::FrontMatter(err)) = split_front_matter("name: spec-core\n") else {
+            panic!("expected a missing delimiter error");
+        };
+        assert_eq!(err.message, "missing front matter delimiter (---)");
+        assert_eq!((err.line, err.column), (Some(1), Some(1)));
+    }
 }
@@ -0,0 +1,717 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::dependency_tree::{
+    ArtifactId, ArtifactKind, ArtifactSummary, DependencyEdge, DependencyRelation, DependencyTree,
+    ResourceHandle, WorkspaceInventorySnapshot,
+};
+use crate::error::SpecmanError;
+
+/// Text formats [`ArtifactGraph::render`] can emit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// Mermaid `flowchart`.
+    Mermaid,
+    /// GraphML XML.
+    #[serde(rename = "graphml")]
+    GraphMl,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 3] =
+        [GraphFormat::Dot, GraphFormat::Mermaid, GraphFormat::GraphMl];
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mermaid",
+            GraphFormat::GraphMl => "graphml",
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.slug())
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = SpecmanError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        GraphFormat::ALL
+            .into_iter()
+            .find(|format| format.slug() == raw)
+            .ok_or_else(|| {
+                SpecmanError::Dependency(format!(
+                    "unknown graph format {raw} (expected one of {})",
+                    GraphFormat::ALL.map(GraphFormat::slug).join(", ")
+                ))
+            })
+    }
+}
+
+/// Dependency between two graph nodes: `from` depends on `to`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphEdge {
+    pub from: ArtifactId,
+    pub to: ArtifactId,
+    pub optional: bool,
+}
+
+/// Artifacts and the dependencies between them, detached from any single traversal root.
+///
+/// Nodes are ordered by [`ArtifactId`] and edges by endpoint so every export is
+/// deterministic.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ArtifactGraph {
+    pub nodes: Vec<ArtifactSummary>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl ArtifactGraph {
+    /// Builds a graph from summaries plus dependency edges. Edge endpoints missing from
+    /// `nodes` are added; duplicate edges collapse and stay optional only if every
+    /// occurrence is optional.
+    pub fn from_edges(
+        nodes: impl IntoIterator<Item = ArtifactSummary>,
+        edges: impl IntoIterator<Item = DependencyEdge>,
+    ) -> Self {
+        let mut by_id: BTreeMap<ArtifactId, ArtifactSummary> = BTreeMap::new();
+        for node in nodes {
+            by_id.entry(node.id.clone()).or_insert(node);
+        }
+
+        let mut collapsed: BTreeMap<(ArtifactId, ArtifactId), bool> = BTreeMap::new();
+        for edge in edges {
+            let key = (edge.from.id.clone(), edge.to.id.clone());
+            by_id.entry(edge.from.id.clone()).or_insert(edge.from);
+            // Nodes are whole artifacts; section anchors only qualify the edge.
+            by_id.entry(edge.to.id.clone()).or_insert(ArtifactSummary {
+                fragment: None,
+                ..edge.to
+            });
+            let optional = collapsed.entry(key).or_insert(true);
+            *optional &= edge.optional;
+        }
+
+        Self {
+            nodes: by_id.into_values().collect(),
+            edges: collapsed
+                .into_iter()
+                .map(|((from, to), optional)| GraphEdge { from, to, optional })
+                .collect(),
+        }
+    }
+
+    /// Every artifact in the workspace and every dependency it declares.
+    pub fn from_inventory(snapshot: &WorkspaceInventorySnapshot) -> Self {
+        Self::from_edges(snapshot.artifacts(), snapshot.edges())
+    }
+
+    /// The artifacts and edges of a single dependency tree.
+    pub fn from_tree(tree: &DependencyTree) -> Self {
+        Self::from_edges([tree.root.clone()], tree.aggregate.iter().cloned())
+    }
+
+    pub fn contains(&self, id: &ArtifactId) -> bool {
+        self.nodes.iter().any(|node| &node.id == id)
+    }
+
+    /// Restricts the graph to `root` plus everything reachable from it: what it depends on
+    /// for [`DependencyRelation::Upstream`], what depends on it for
+    /// [`DependencyRelation::Downstream`], or both when `direction` is `None`.
+    pub fn closure(
+        &self,
+        root: &ArtifactId,
+        direction: Option<DependencyRelation>,
+    ) -> Result<Self, SpecmanError> {
+        if !self.contains(root) {
+            return Err(SpecmanError::Dependency(format!(
+                "{} is not part of the dependency graph",
+                describe_id(root)
+            )));
+        }
+
+        let mut reached = BTreeSet::from([root.clone()]);
+        let directions = match direction {
+            Some(direction) => vec![direction],
+            None => vec![DependencyRelation::Upstream, DependencyRelation::Downstream],
+        };
+        for direction in directions {
+            let mut queue = VecDeque::from([root]);
+            let mut visited = BTreeSet::from([root]);
+            while let Some(current) = queue.pop_front() {
+                for edge in &self.edges {
+                    let next = match direction {
+                        DependencyRelation::Upstream if &edge.from == current => &edge.to,
+                        DependencyRelation::Downstream if &edge.to == current => &edge.from,
+                        _ => continue,
+                    };
+                    if visited.insert(next) {
+                        reached.insert(next.clone());
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|node| reached.contains(&node.id))
+                .cloned()
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|edge| reached.contains(&edge.from) && reached.contains(&edge.to))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Strongly connected components, each sorted by id and ordered so that every
+    /// component comes after the components it depends on.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<ArtifactId>> {
+        let adjacency = self.adjacency();
+        let mut tarjan = Tarjan::new(adjacency.len());
+        for node in 0..adjacency.len() {
+            if tarjan.index[node].is_none() {
+                tarjan.visit(node, &adjacency);
+            }
+        }
+        tarjan
+            .components
+            .into_iter()
+            .map(|component| {
+                let mut ids: Vec<ArtifactId> = component
+                    .into_iter()
+                    .map(|node| self.nodes[node].id.clone())
+                    .collect();
+                ids.sort();
+                ids
+            })
+            .collect()
+    }
+
+    /// Every dependency cycle: components with more than one member, plus artifacts that
+    /// depend on themselves.
+    pub fn cycles(&self) -> Vec<Vec<ArtifactId>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .edges
+                        .iter()
+                        .any(|edge| edge.from == component[0] && edge.to == component[0])
+            })
+            .collect()
+    }
+
+    /// Artifacts grouped by dependency depth. Level 0 holds artifacts without
+    /// dependencies; every other artifact sits one level above its deepest dependency.
+    /// Members of a cycle share a level.
+    pub fn levels(&self) -> Vec<Vec<ArtifactId>> {
+        let components = self.strongly_connected_components();
+        let component_of: BTreeMap<&ArtifactId, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(index, members)| members.iter().map(move |id| (id, index)))
+            .collect();
+
+        // Components arrive dependencies-first, so every dependency's level is final
+        // before its dependents are visited.
+        let mut depth = vec![0usize; components.len()];
+        for (index, members) in components.iter().enumerate() {
+            for edge in &self.edges {
+                if !members.contains(&edge.from) {
+                    continue;
+                }
+                let target = component_of[&edge.to];
+                if target != index {
+                    depth[index] = depth[index].max(depth[target] + 1);
+                }
+            }
+        }
+
+        let mut levels: Vec<Vec<ArtifactId>> =
+            vec![Vec::new(); depth.iter().max().map_or(0, |max| max + 1)];
+        for (members, level) in components.into_iter().zip(depth) {
+            levels[level].extend(members);
+        }
+        for level in &mut levels {
+            level.sort();
+        }
+        levels
+    }
+
+    /// Build order with every dependency ahead of its dependents, level by level. Fails
+    /// with every cycle listed when the graph is not acyclic.
+    pub fn topological_order(&self) -> Result<Vec<ArtifactId>, SpecmanError> {
+        let cycles = self.cycles();
+        if !cycles.is_empty() {
+            let described: Vec<String> = cycles
+                .iter()
+                .map(|cycle| cycle.iter().map(describe_id).collect::<Vec<_>>().join(", "))
+                .collect();
+            return Err(SpecmanError::Dependency(format!(
+                "dependency graph has {} cycle(s): [{}]",
+                cycles.len(),
+                described.join("], [")
+            )));
+        }
+        Ok(self.levels().into_iter().flatten().collect())
+    }
+
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let index_of: BTreeMap<&ArtifactId, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (&node.id, index))
+            .collect();
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            adjacency[index_of[&edge.from]].push(index_of[&edge.to]);
+        }
+        adjacency
+    }
+
+    /// Serializes the graph. Nodes are styled by [`ArtifactKind`] and optional edges are
+    /// dashed; GraphML carries both as `kind` and `optional` data attributes instead.
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::GraphMl => self.to_graphml(),
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph specman {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [fontname=\"Helvetica\", style=filled];\n");
+        for node in &self.nodes {
+            let (shape, fill) = match node.id.kind {
+                ArtifactKind::Specification => ("box", "#dbeafe"),
+                ArtifactKind::Implementation => ("component", "#dcfce7"),
+                ArtifactKind::ScratchPad => ("note", "#fef9c3"),
+            };
+            let _ = writeln!(
+                out,
+                "  {} [label=\"{}\", shape={}, fillcolor=\"{}\"];",
+                node_id(&node.id),
+                node_label(node).replace('\\', "\\\\").replace('"', "\\\""),
+                shape,
+                fill
+            );
+        }
+        for edge in &self.edges {
+            let style = if edge.optional { " [style=dashed]" } else { "" };
+            let _ = writeln!(
+                out,
+                "  {} -> {}{};",
+                node_id(&edge.from),
+                node_id(&edge.to),
+                style
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "  {}[\"{}\"]:::{}",
+                node_id(&node.id),
+                node_label(node).replace('"', "#quot;"),
+                ResourceHandle::scheme_for(node.id.kind)
+            );
+        }
+        for edge in &self.edges {
+            let arrow = if edge.optional { "-.->" } else { "-->" };
+            let _ = writeln!(
+                out,
+                "  {} {} {}",
+                node_id(&edge.from),
+                arrow,
+                node_id(&edge.to)
+            );
+        }
+        out.push_str("  classDef spec fill:#dbeafe,stroke:#1d4ed8\n");
+        out.push_str("  classDef impl fill:#dcfce7,stroke:#15803d\n");
+        out.push_str("  classDef scratch fill:#fef9c3,stroke:#a16207\n");
+        out
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n");
+        out.push_str(
+            "  <key id=\"version\" for=\"node\" attr.name=\"version\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"optional\" for=\"edge\" attr.name=\"optional\" attr.type=\"boolean\"/>\n",
+        );
+        out.push_str("  <graph id=\"specman\" edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", node_id(&node.id));
+            let _ = writeln!(
+                out,
+                "      <data key=\"kind\">{}</data>",
+                ResourceHandle::scheme_for(node.id.kind)
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"name\">{}</data>",
+                xml_escape(&node.id.name)
+            );
+            if let Some(version) = &node.version {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"version\">{}</data>",
+                    xml_escape(&version.to_string())
+                );
+            }
+            out.push_str("    </node>\n");
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+                index,
+                node_id(&edge.from),
+                node_id(&edge.to)
+            );
+            let _ = writeln!(out, "      <data key=\"optional\">{}</data>", edge.optional);
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n");
+        out.push_str("</graphml>\n");
+        out
+    }
+}
+
+/// Tarjan's algorithm. Components are emitted once everything reachable from them has
+/// been, i.e. dependencies first.
+struct Tarjan {
+    next_index: usize,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan {
+    fn new(size: usize) -> Self {
+        Self {
+            next_index: 0,
+            index: vec![None; size],
+            low_link: vec![0; size],
+            on_stack: vec![false; size],
+            stack: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, node: usize, adjacency: &[Vec<usize>]) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &next in &adjacency[node] {
+            match self.index[next] {
+                None => {
+                    self.visit(next, adjacency);
+                    self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.low_link[node] = self.low_link[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low_link[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+pub(crate) fn describe_id(id: &ArtifactId) -> String {
+    format!("{}://{}", ResourceHandle::scheme_for(id.kind), id.name)
+}
+
+/// Identifier safe for DOT, Mermaid, and XML: `{scheme}_{name}` with every other
+/// character replaced by `_`.
+fn node_id(id: &ArtifactId) -> String {
+    let name: String = id
+        .name
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect();
+    format!("{}_{}", ResourceHandle::scheme_for(id.kind), name)
+}
+
+fn node_label(node: &ArtifactSummary) -> String {
+    match &node.version {
+        Some(version) => format!("{}@{}", node.id.name, version),
+        None => node.id.name.clone(),
+    }
+}
+
+fn xml_escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_tree::DependencyGraphServices;
+    use crate::workspace::FilesystemWorkspaceLocator;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn exports_style_kinds_and_dash_optional_edges() {
+        let graph = sample();
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(
+            dot.contains("  spec_alpha [label=\"alpha@1.0.0\", shape=box, fillcolor=\"#dbeafe\"];")
+        );
+        assert!(dot.contains("  impl_alpha_rust -> spec_alpha;\n"));
+        assert!(dot.contains("  scratch_notes -> impl_alpha_rust [style=dashed];\n"));
+
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  impl_alpha_rust[\"alpha-rust\"]:::impl\n"));
+        assert!(mermaid.contains("  scratch_notes -.-> impl_alpha_rust\n"));
+        assert!(mermaid.contains("  impl_alpha_rust --> spec_alpha\n"));
+
+        let graphml = graph.render(GraphFormat::GraphMl);
+        assert!(graphml.contains("<data key=\"kind\">scratch</data>"));
+        assert!(graphml.contains(
+            "<edge id=\"e1\" source=\"impl_alpha_rust\" target=\"spec_alpha\">\n      <data key=\"optional\">false</data>"
+        ));
+    }
+
+    #[test]
+    fn closure_limits_graph_to_reachable_artifacts() {
+        let graph = sample();
+        let alpha = id(ArtifactKind::Specification, "alpha");
+        let rust = id(ArtifactKind::Implementation, "alpha-rust");
+
+        let upstream = graph
+            .closure(&rust, Some(DependencyRelation::Upstream))
+            .unwrap();
+        assert_eq!(names(&upstream), vec!["alpha", "base", "alpha-rust"]);
+        assert_eq!(upstream.edges.len(), 2);
+
+        let downstream = graph
+            .closure(&alpha, Some(DependencyRelation::Downstream))
+            .unwrap();
+        assert_eq!(names(&downstream), vec!["alpha", "alpha-rust", "notes"]);
+
+        let both = graph.closure(&rust, None).unwrap();
+        assert_eq!(both.nodes.len(), 4);
+
+        let missing = graph.closure(&id(ArtifactKind::Specification, "nope"), None);
+        assert!(matches!(missing, Err(SpecmanError::Dependency(_))));
+    }
+
+    #[test]
+    fn duplicate_edges_stay_required_unless_all_are_optional() {
+        let alpha = summary(ArtifactKind::Specification, "alpha", Some("1.0.0"));
+        let notes = summary(ArtifactKind::ScratchPad, "notes", None);
+        let graph = ArtifactGraph::from_edges(
+            [],
+            [edge(&notes, &alpha, true), edge(&notes, &alpha, false)],
+        );
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+        assert!(!graph.edges[0].optional);
+    }
+
+    #[test]
+    fn levels_and_topological_order_put_dependencies_first() {
+        let graph = sample();
+        let levels = graph.levels();
+        let levels: Vec<Vec<&str>> = levels
+            .iter()
+            .map(|level| level.iter().map(|id| id.name.as_str()).collect())
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                vec!["base"],
+                vec!["alpha"],
+                vec!["alpha-rust"],
+                vec!["notes"]
+            ]
+        );
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.first().unwrap().name, "base");
+        assert_eq!(order.last().unwrap().name, "notes");
+        assert!(graph.cycles().is_empty());
+        assert_eq!(graph.strongly_connected_components().len(), 4);
+    }
+
+    #[test]
+    fn cycles_report_every_strongly_connected_component() {
+        let a = summary(ArtifactKind::Specification, "a", None);
+        let b = summary(ArtifactKind::Specification, "b", None);
+        let c = summary(ArtifactKind::Specification, "c", None);
+        let d = summary(ArtifactKind::Specification, "d", None);
+        let own = summary(ArtifactKind::Specification, "own", None);
+        let rust = summary(ArtifactKind::Implementation, "a-rust", None);
+        let graph = ArtifactGraph::from_edges(
+            [],
+            [
+                edge(&a, &b, false),
+                edge(&b, &a, false),
+                edge(&c, &d, false),
+                edge(&d, &c, true),
+                edge(&own, &own, false),
+                edge(&rust, &a, false),
+            ],
+        );
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 3);
+        assert!(cycles.contains(&vec![a.id.clone(), b.id.clone()]));
+        assert!(cycles.contains(&vec![c.id.clone(), d.id.clone()]));
+        assert!(cycles.contains(&vec![own.id.clone()]));
+
+        let levels = graph.levels();
+        assert!(levels[0].contains(&a.id) && levels[0].contains(&b.id));
+        assert_eq!(levels[1], vec![rust.id.clone()]);
+
+        let err = graph.topological_order().unwrap_err();
+        assert!(err.to_string().contains("3 cycle(s)"));
+        assert!(err.to_string().contains("[spec://a, spec://b]"));
+    }
+
+    #[test]
+    fn workspace_graph_loads_every_artifact() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        write(
+            &root.join("spec/base/spec.md"),
+            "---\nname: base\nversion: \"1.0.0\"\ndependencies: []\n---\nBase.\n",
+        );
+        write(
+            &root.join("spec/alpha/spec.md"),
+            "---\nname: alpha\nversion: \"1.0.0\"\ndependencies:\n  - ref: ../base/spec.md\n    optional: true\n---\nAlpha.\n",
+        );
+        write(
+            &root.join("impl/alpha-rust/impl.md"),
+            "---\nname: alpha-rust\nspec: ../../spec/alpha/spec.md\nprimary_language:\n  language: rust@1.0\n---\nImpl.\n",
+        );
+        let services = DependencyGraphServices::new(FilesystemWorkspaceLocator::new(root));
+
+        let graph = services.workspace_graph().unwrap();
+        assert_eq!(names(&graph), vec!["alpha", "base", "alpha-rust"]);
+        assert_eq!(
+            graph.edges,
+            vec![
+                GraphEdge {
+                    from: id(ArtifactKind::Specification, "alpha"),
+                    to: id(ArtifactKind::Specification, "base"),
+                    optional: true,
+                },
+                GraphEdge {
+                    from: id(ArtifactKind::Implementation, "alpha-rust"),
+                    to: id(ArtifactKind::Specification, "alpha"),
+                    optional: false,
+                },
+            ]
+        );
+        assert_eq!(graph.topological_order().unwrap().len(), 3);
+    }
+
+    fn sample() -> ArtifactGraph {
+        let base = summary(ArtifactKind::Specification, "base", Some("1.0.0"));
+        let alpha = summary(ArtifactKind::Specification, "alpha", Some("1.0.0"));
+        let rust = summary(ArtifactKind::Implementation, "alpha-rust", None);
+        let notes = summary(ArtifactKind::ScratchPad, "notes", None);
+        ArtifactGraph::from_edges(
+            [base.clone(), alpha.clone(), rust.clone(), notes.clone()],
+            [
+                edge(&alpha, &base, false),
+                edge(&rust, &alpha, false),
+                edge(&notes, &rust, true),
+            ],
+        )
+    }
+
+    fn names(graph: &ArtifactGraph) -> Vec<&str> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| node.id.name.as_str())
+            .collect()
+    }
+
+    fn id(kind: ArtifactKind, name: &str) -> ArtifactId {
+        ArtifactId {
+            kind,
+            name: name.into(),
+        }
+    }
+
+    fn summary(kind: ArtifactKind, name: &str, version: Option<&str>) -> ArtifactSummary {
+        ArtifactSummary {
+            id: id(kind, name),
+            version: version.map(|raw| raw.parse().unwrap()),
+            metadata: BTreeMap::new(),
+            fragment: None,
+        }
+    }
+
+    fn edge(from: &ArtifactSummary, to: &ArtifactSummary, optional: bool) -> DependencyEdge {
+        DependencyEdge {
+            from: from.clone(),
+            to: to.clone(),
+            relation: DependencyRelation::Upstream,
+            optional,
+        }
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use markdown::{Block, ListItem, Span};
+
+use crate::front_matter;
+
+/// Splits a locator such as `../core/spec.md#concept-foo` into the artifact locator and its
+/// heading fragment. An empty fragment (`spec.md#`) counts as no fragment.
+pub fn split_fragment(reference: &str) -> (&str, Option<&str>) {
+    match reference.split_once('#') {
+        Some((locator, fragment)) if !fragment.is_empty() => (locator, Some(fragment)),
+        Some((locator, _)) => (locator, None),
+        None => (reference, None),
+    }
+}
+
+/// Anchor that GitHub-flavoured renderers generate for a heading: lowercase, punctuation
+/// dropped, spaces turned into hyphens.
+pub fn anchor_for(heading: &str) -> String {
+    heading
+        .trim()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            '-' | '_' => Some(ch),
+            ch if ch.is_alphanumeric() => Some(ch),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Anchors for every heading in a Markdown document, in document order. Front matter is
+/// skipped, and repeated headings get `-1`, `-2`, ... suffixes the way GitHub numbers them.
+pub fn heading_anchors(content: &str) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    base_anchors(content)
+        .into_iter()
+        .map(|anchor| {
+            let count = seen.entry(anchor.clone()).or_insert(0);
+            let unique = match *count {
+                0 => anchor,
+                n => format!("{anchor}-{n}"),
+            };
+            *count += 1;
+            unique
+        })
+        .collect()
+}
+
+/// Anchors for every heading before duplicate numbering, so repeated headings share an anchor.
+pub(crate) fn base_anchors(content: &str) -> Vec<String> {
+    let body = front_matter::split_front_matter(content)
+        .map(|split| split.body)
+        .unwrap_or(content);
+
+    let mut headings = Vec::new();
+    collect_headings(&markdown::tokenize(&without_fences(body)), &mut headings);
+    headings.iter().map(|heading| anchor_for(heading)).collect()
+}
+
+/// Drops fenced code blocks. The tokenizer reads a fence opener followed by a `---` line (as
+/// in YAML front matter examples) as a setext heading.
+fn without_fences(body: &str) -> String {
+    let mut fence: Option<&str> = None;
+    let mut kept = String::with_capacity(body.len());
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker)
+                    && trimmed.trim_start_matches(marker).trim().is_empty()
+                {
+                    fence = None;
+                }
+            }
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None => kept.push_str(line),
+        }
+        kept.push('\n');
+    }
+    kept
+}
+
+fn collect_headings(blocks: &[Block], headings: &mut Vec<String>) {
+    for block in blocks {
+        match block {
+            Block::Header(spans, _) => headings.push(span_text(spans)),
+            Block::Blockquote(inner) => collect_headings(inner, headings),
+            Block::OrderedList(items, _) | Block::UnorderedList(items) => {
+                for item in items {
+                    if let ListItem::Paragraph(inner) = item {
+                        collect_headings(inner, headings);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn span_text(spans: &[Span]) -> String {
+    let mut text = String::new();
+    for span in spans {
+        match span {
+            Span::Text(value) | Span::Code(value) => text.push_str(value),
+            Span::Link(label, _, _) | Span::Image(label, _, _) => text.push_str(label),
+            Span::Emphasis(inner) | Span::Strong(inner) => text.push_str(&span_text(inner)),
+            Span::Break => text.push(' '),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_follow_github_rules() {
+        let document = r#"---
+name: core
+---
+# SpecMan Core
+
+## Concept: Dependency Mapping
+
+```
+# not a heading
+```
+
+```yaml
+---
+name: not-a-heading
+---
+```
+
+### [Dependencies](../docs/founding-spec.md#dependencies)
+
+## Concept: Dependency Mapping
+"#;
+        assert_eq!(
+            heading_anchors(document),
+            vec![
+                "specman-core",
+                "concept-dependency-mapping",
+                "dependencies",
+                "concept-dependency-mapping-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_fragments_from_locators() {
+        assert_eq!(
+            split_fragment("../core/spec.md#concept-foo"),
+            ("../core/spec.md", Some("concept-foo"))
+        );
+        assert_eq!(split_fragment("spec://core#"), ("spec://core", None));
+        assert_eq!(split_fragment("spec://core"), ("spec://core", None));
+    }
+}
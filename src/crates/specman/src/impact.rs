@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Component, Path};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::dependency_tree::{ArtifactId, ArtifactKind, ArtifactSummary};
+use crate::error::SpecmanError;
+use crate::graph::{ArtifactGraph, describe_id};
+use crate::workspace::WorkspacePaths;
+
+/// Whether an impacted artifact depends on a changed artifact itself or through others.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpactReach {
+    Direct,
+    Transitive,
+}
+
+/// An artifact downstream of at least one changed artifact.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ImpactedArtifact {
+    pub artifact: ArtifactSummary,
+    pub reach: ImpactReach,
+    /// Set when a chain of non-optional dependencies links this artifact to a change.
+    pub required: bool,
+    /// Shortest chain from a changed artifact to this one, both ends included. Required
+    /// artifacts are reached over required edges only.
+    pub path: Vec<ArtifactId>,
+}
+
+/// Everything that transitively depends on a set of changed artifacts.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ImpactReport {
+    pub changed: Vec<ArtifactId>,
+    /// Impacted artifacts, nearest first. Changed artifacts are never listed here even when
+    /// they depend on one another.
+    pub impacted: Vec<ImpactedArtifact>,
+}
+
+impl ImpactReport {
+    /// Walks downstream edges of `graph` from every artifact in `changed`.
+    pub fn analyze(graph: &ArtifactGraph, changed: &[ArtifactId]) -> Result<Self, SpecmanError> {
+        for id in changed {
+            if !graph.contains(id) {
+                return Err(SpecmanError::Dependency(format!(
+                    "{} is not part of the dependency graph",
+                    describe_id(id)
+                )));
+            }
+        }
+
+        let required = downstream_paths(graph, changed, false);
+        let reachable = downstream_paths(graph, changed, true);
+
+        let mut impacted: Vec<ImpactedArtifact> = graph
+            .nodes
+            .iter()
+            .filter(|node| !changed.contains(&node.id))
+            .filter_map(|node| {
+                let any_path = reachable.get(&node.id)?;
+                let reach = if any_path.len() == 2 {
+                    ImpactReach::Direct
+                } else {
+                    ImpactReach::Transitive
+                };
+                let (required, path) = match required.get(&node.id) {
+                    Some(path) => (true, path.clone()),
+                    None => (false, any_path.clone()),
+                };
+                Some(ImpactedArtifact {
+                    artifact: node.clone(),
+                    reach,
+                    required,
+                    path,
+                })
+            })
+            .collect();
+        impacted.sort_by(|a, b| {
+            a.path
+                .len()
+                .cmp(&b.path.len())
+                .then_with(|| a.artifact.id.cmp(&b.artifact.id))
+        });
+
+        let mut changed = changed.to_vec();
+        changed.sort();
+        changed.dedup();
+        Ok(Self { changed, impacted })
+    }
+}
+
+/// Maps a changed file (absolute, or relative to the workspace root) to the artifact whose
+/// folder contains it. Files outside `spec/`, `impl/`, and `.specman/scratchpad/` map to
+/// nothing.
+pub fn artifact_for_path(workspace: &WorkspacePaths, path: &Path) -> Option<ArtifactId> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        workspace.root().join(path)
+    };
+
+    [
+        (workspace.spec_dir(), ArtifactKind::Specification),
+        (workspace.impl_dir(), ArtifactKind::Implementation),
+        (workspace.scratchpad_dir(), ArtifactKind::ScratchPad),
+    ]
+    .into_iter()
+    .find_map(|(dir, kind)| {
+        let relative = path.strip_prefix(&dir).ok()?;
+        let mut components = relative.components();
+        let name = match components.next()? {
+            Component::Normal(name) => name.to_str()?.to_string(),
+            _ => return None,
+        };
+        // Only files inside the artifact folder count, not the folder name alone.
+        components.next()?;
+        Some(ArtifactId { kind, name })
+    })
+}
+
+/// Breadth-first search over reversed edges from every changed artifact, keeping the first
+/// (shortest) path found to each dependent.
+fn downstream_paths(
+    graph: &ArtifactGraph,
+    changed: &[ArtifactId],
+    include_optional: bool,
+) -> BTreeMap<ArtifactId, Vec<ArtifactId>> {
+    let mut paths: BTreeMap<ArtifactId, Vec<ArtifactId>> = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    for id in changed {
+        paths.insert(id.clone(), vec![id.clone()]);
+        queue.push_back(id.clone());
+    }
+
+    while let Some(current) = queue.pop_front() {
+        for edge in &graph.edges {
+            if edge.to != current || (edge.optional && !include_optional) {
+                continue;
+            }
+            if paths.contains_key(&edge.from) {
+                continue;
+            }
+            let mut path = paths[&current].clone();
+            path.push(edge.from.clone());
+            paths.insert(edge.from.clone(), path);
+            queue.push_back(edge.from.clone());
+        }
+    }
+
+    for id in changed {
+        paths.remove(id);
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_tree::{DependencyEdge, DependencyRelation};
+    use crate::workspace::FilesystemWorkspaceLocator;
+    use crate::workspace::WorkspaceLocator;
+    use std::fs;
+
+    #[test]
+    fn classifies_direct_transitive_and_optional_dependents() {
+        let base = summary(ArtifactKind::Specification, "base");
+        let alpha = summary(ArtifactKind::Specification, "alpha");
+        let rust = summary(ArtifactKind::Implementation, "alpha-rust");
+        let notes = summary(ArtifactKind::ScratchPad, "notes");
+        let unrelated = summary(ArtifactKind::Specification, "unrelated");
+        let graph = ArtifactGraph::from_edges(
+            [unrelated],
+            [
+                edge(&alpha, &base, false),
+                edge(&rust, &alpha, false),
+                edge(&notes, &base, true),
+            ],
+        );
+
+        let report = ImpactReport::analyze(&graph, std::slice::from_ref(&base.id)).unwrap();
+        let summary: Vec<(&str, ImpactReach, bool, usize)> = report
+            .impacted
+            .iter()
+            .map(|item| {
+                (
+                    item.artifact.id.name.as_str(),
+                    item.reach,
+                    item.required,
+                    item.path.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("alpha", ImpactReach::Direct, true, 2),
+                ("notes", ImpactReach::Direct, false, 2),
+                ("alpha-rust", ImpactReach::Transitive, true, 3),
+            ]
+        );
+        assert_eq!(
+            report.impacted[2].path,
+            vec![base.id.clone(), alpha.id.clone(), rust.id.clone()]
+        );
+
+        let missing = ImpactReport::analyze(&graph, &[id(ArtifactKind::Specification, "nope")]);
+        assert!(matches!(missing, Err(SpecmanError::Dependency(_))));
+    }
+
+    #[test]
+    fn maps_changed_files_to_their_artifacts() {
+        let tempdir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tempdir.path().join(".specman")).unwrap();
+        let workspace = FilesystemWorkspaceLocator::new(tempdir.path())
+            .workspace()
+            .unwrap();
+
+        assert_eq!(
+            artifact_for_path(&workspace, Path::new("spec/alpha/spec.md")),
+            Some(id(ArtifactKind::Specification, "alpha"))
+        );
+        assert_eq!(
+            artifact_for_path(&workspace, Path::new("impl/alpha-rust/notes/extra.md")),
+            Some(id(ArtifactKind::Implementation, "alpha-rust"))
+        );
+        assert_eq!(
+            artifact_for_path(
+                &workspace,
+                &workspace.root().join(".specman/scratchpad/fix/scratch.md")
+            ),
+            Some(id(ArtifactKind::ScratchPad, "fix"))
+        );
+        assert_eq!(artifact_for_path(&workspace, Path::new("README.md")), None);
+        assert_eq!(artifact_for_path(&workspace, Path::new("spec/alpha")), None);
+    }
+
+    fn id(kind: ArtifactKind, name: &str) -> ArtifactId {
+        ArtifactId {
+            kind,
+            name: name.into(),
+        }
+    }
+
+    fn summary(kind: ArtifactKind, name: &str) -> ArtifactSummary {
+        ArtifactSummary {
+            id: id(kind, name),
+            version: None,
+            metadata: BTreeMap::new(),
+            fragment: None,
+        }
+    }
+
+    fn edge(from: &ArtifactSummary, to: &ArtifactSummary, optional: bool) -> DependencyEdge {
+        DependencyEdge {
+            from: from.clone(),
+            to: to.clone(),
+            relation: DependencyRelation::Upstream,
+            optional,
+        }
+    }
+}
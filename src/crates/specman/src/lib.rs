@@ -1,33 +1,61 @@
 pub mod adapter;
+pub mod audit;
+pub mod capability;
 pub mod dependency_tree;
 pub mod error;
 pub mod front_matter;
+pub mod graph;
+pub mod headings;
+pub mod impact;
 pub mod lifecycle;
+pub mod lint;
+pub mod locking;
 pub mod metadata;
 pub mod persistence;
+pub mod preview;
+pub mod progress;
+pub mod prompt_catalog;
+pub mod remote_cache;
+pub mod schema;
 pub mod scratchpad;
 pub mod shared_function;
 pub mod template;
 pub mod template_catalog;
+pub mod template_tokens;
+pub mod vendor;
 pub mod workspace;
 
 pub use adapter::{DataModelAdapter, InMemoryAdapter};
+pub use audit::OperationEnvelope;
+pub use capability::{CapabilityDescriptor, CapabilityExtension, CapabilityRegistry};
 pub use dependency_tree::{
-    ArtifactId, ArtifactKind, ArtifactSummary, DependencyEdge, DependencyGraphServices,
-    DependencyMapping, DependencyRelation, DependencyTree, FilesystemDependencyMapper,
-    InventoryDependent, WorkspaceInventorySnapshot,
+    ArtifactId, ArtifactKind, ArtifactSummary, CycleEntry, DependencyCycle, DependencyDiagnostic,
+    DependencyEdge, DependencyField, DependencyGraphServices, DependencyHop, DependencyMapping,
+    DependencyNode, DependencyQuery, DependencyRelation, DependencyTree,
+    FilesystemDependencyMapper, InventoryDependent, PathSelection, RemoteArtifact,
+    ResolvedResource, ResourceHandle, WorkspaceInventorySnapshot,
 };
-pub use error::SpecmanError;
+pub use error::{CodedError, ErrorCode, ErrorDetails, ErrorExplanation, SpecmanError};
+pub use graph::{ArtifactGraph, GraphEdge, GraphFormat};
+pub use impact::{ImpactReach, ImpactReport, ImpactedArtifact};
 pub use lifecycle::{
     CreationPlan, CreationRequest, DefaultLifecycleController, DeletionPlan, LifecycleController,
     ScratchPadPlan,
 };
+pub use lint::{LintDiagnostic, LintFinding, LintReport, LintRule, Linter, Severity};
+pub use locking::{ArtifactLock, LockOptions, WorkspaceLockManager};
 pub use metadata::{
-    MetadataMutationRequest, MetadataMutationResult, MetadataMutator, ReferenceAddition,
+    LocatorReplacement, MetadataMutationRequest, MetadataMutationResult, MetadataMutator,
+    ReferenceAddition,
 };
 pub use persistence::{
     ArtifactRemovalStore, PersistedArtifact, RemovedArtifact, WorkspacePersistence,
 };
+pub use preview::{FileAction, FileChange, MutationPreview};
+pub use progress::{NoopProgress, ProgressEvent, ProgressObserver};
+pub use prompt_catalog::{PromptCatalog, PromptKind, PromptRequest, RenderedPrompt};
+pub use remote_cache::{CachingContentFetcher, FetchMode};
+pub use schema::{SCHEMA_KINDS, front_matter_schema, write_front_matter_schemas};
 pub use scratchpad::ScratchPadProfile;
 pub use shared_function::{EntityKind, SchemaRef, SemVer};
 pub use template::{
@@ -35,6 +63,8 @@ pub use template::{
     TemplateProvenance, TemplateScenario, TemplateTier, TokenMap,
 };
 pub use template_catalog::{ResolvedTemplate, TemplateCatalog};
+pub use template_tokens::{ContractTokenProvider, TokenProvider, TokenRequest};
+pub use vendor::{VendorCheck, VendorManifest, VendorOutcome, VendoredArtifact, Vendorer};
 pub use workspace::{
     FilesystemWorkspaceLocator, WorkspaceLocator, WorkspacePaths, discover as discover_workspace,
 };
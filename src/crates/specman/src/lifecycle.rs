@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::dependency_tree::{ArtifactId, DependencyMapping, DependencyTree};
 use crate::error::SpecmanError;
 use crate::persistence::{ArtifactRemovalStore, RemovedArtifact};
+use crate::preview::MutationPreview;
 use crate::scratchpad::ScratchPadProfile;
 use crate::template::{
     RenderedTemplate, TemplateDescriptor, TemplateEngine, TemplateProvenance, TokenMap,
@@ -49,6 +50,45 @@ pub trait LifecycleController: Send + Sync {
         persistence: &dyn ArtifactRemovalStore,
         force: bool,
     ) -> Result<RemovedArtifact, SpecmanError>;
+    /// Dry-run variant of [`Self::execute_deletion`]: applies the same guard rails, then
+    /// reports the files that would be deleted alongside the dependency tree consulted.
+    fn preview_deletion(
+        &self,
+        target: ArtifactId,
+        existing_plan: Option<DeletionPlan>,
+        persistence: &dyn ArtifactRemovalStore,
+        force: bool,
+    ) -> Result<MutationPreview, SpecmanError> {
+        let plan = match existing_plan {
+            Some(plan) => plan,
+            None => self.plan_deletion(target.clone())?,
+        };
+        let plan = guard_deletion(&target, plan, force)?;
+        let preview = persistence.preview_removal(&target)?;
+        Ok(preview.with_dependencies(plan.dependencies))
+    }
+}
+
+/// Checks that `plan` belongs to `target` and, unless `force` is set, that no downstream
+/// dependents block the deletion.
+fn guard_deletion(
+    target: &ArtifactId,
+    plan: DeletionPlan,
+    force: bool,
+) -> Result<DeletionPlan, SpecmanError> {
+    if &plan.dependencies.root.id != target {
+        return Err(SpecmanError::Dependency(format!(
+            "deletion plan target mismatch for {}",
+            target.name
+        )));
+    }
+    if plan.blocked && !force {
+        return Err(SpecmanError::Dependency(format!(
+            "cannot delete {}; downstream dependents detected",
+            target.name
+        )));
+    }
+    Ok(plan)
 }
 
 pub struct DefaultLifecycleController<M, T>
@@ -110,25 +150,10 @@ where
         force: bool,
     ) -> Result<RemovedArtifact, SpecmanError> {
         let plan = match existing_plan {
-            Some(plan) => {
-                if plan.dependencies.root.id != target {
-                    return Err(SpecmanError::Dependency(format!(
-                        "deletion plan target mismatch for {}",
-                        target.name
-                    )));
-                }
-                plan
-            }
+            Some(plan) => plan,
             None => self.plan_deletion(target.clone())?,
         };
-
-        if plan.blocked && !force {
-            return Err(SpecmanError::Dependency(format!(
-                "cannot delete {}; downstream dependents detected",
-                target.name
-            )));
-        }
-
+        guard_deletion(&target, plan, force)?;
         let removed = persistence.remove_artifact(&target)?;
         Ok(removed)
     }
@@ -438,6 +463,48 @@ mod tests {
         assert_eq!(invalidated, vec![artifact]);
     }
 
+    #[test]
+    fn lifecycle_preview_deletion_applies_guard_and_leaves_files() {
+        let temp = tempdir().unwrap();
+        let workspace_root = temp.path().join("ws");
+        fs::create_dir_all(workspace_root.join(".specman")).unwrap();
+        let impl_dir = workspace_root.join("impl");
+        let artifact = ArtifactId {
+            kind: ArtifactKind::Implementation,
+            name: "preview-delete".into(),
+        };
+        let artifact_dir = impl_dir.join(&artifact.name);
+        fs::create_dir_all(&artifact_dir).unwrap();
+        fs::write(artifact_dir.join("impl.md"), "body\n").unwrap();
+
+        let controller = controller();
+        let persistence = WorkspacePersistence::new(FilesystemWorkspaceLocator::new(impl_dir));
+        let blocked = DeletionPlan {
+            dependencies: DependencyTree::empty(ArtifactSummary {
+                id: artifact.clone(),
+                ..Default::default()
+            }),
+            blocked: true,
+        };
+
+        let err = controller
+            .preview_deletion(artifact.clone(), Some(blocked.clone()), &persistence, false)
+            .expect_err("blocked preview");
+        assert!(matches!(err, SpecmanError::Dependency(_)));
+
+        let preview = controller
+            .preview_deletion(artifact.clone(), Some(blocked), &persistence, true)
+            .expect("forced preview");
+        assert!(preview.dry_run);
+        assert_eq!(preview.changes.len(), 1);
+        assert!(preview.changes[0].diff.contains("-body\n"));
+        assert_eq!(
+            preview.dependencies.map(|tree| tree.root.id),
+            Some(artifact)
+        );
+        assert!(artifact_dir.join("impl.md").exists());
+    }
+
     #[test]
     fn scratchpad_deletion_ignores_target_edges() {
         let root_artifact = ArtifactId {
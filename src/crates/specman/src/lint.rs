@@ -0,0 +1,915 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::dependency_tree::{
+    ArtifactId, ArtifactKind, gather_workspace_artifacts, local_reference_path,
+};
+use crate::error::SpecmanError;
+use crate::front_matter::{
+    self, ArtifactFrontMatter, DependencyEntry, FrontMatterError, FrontMatterKind,
+    ImplementationFrontMatter,
+};
+use crate::headings;
+use crate::impact::artifact_for_path;
+use crate::scratchpad;
+use crate::workspace::WorkspacePaths;
+
+/// Specification every built-in rule cites.
+pub const DATA_MODEL_SPEC: &str = "spec/specman-data-model/spec.md";
+
+/// Words that make a name describe an action rather than a feature.
+const NAME_VERBS: &[&str] = &[
+    "add",
+    "create",
+    "delete",
+    "edit",
+    "fix",
+    "generate",
+    "implement",
+    "improve",
+    "migrate",
+    "modify",
+    "refactor",
+    "remove",
+    "rename",
+    "update",
+];
+
+/// How much a rule violation matters. Errors fail `specman lint`; warnings are reported only.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// One artifact as the rules see it: its folder identity, raw content, and front matter
+/// parsed as the kind its location implies.
+pub struct LintArtifact {
+    pub id: ArtifactId,
+    pub path: PathBuf,
+    /// Workspace-relative path of the artifact document.
+    pub relative: String,
+    pub content: String,
+    pub front: Result<ArtifactFrontMatter, FrontMatterError>,
+}
+
+/// Everything a rule may consult besides the artifact under check.
+pub struct LintContext {
+    pub workspace: WorkspacePaths,
+    pub artifacts: Vec<LintArtifact>,
+}
+
+/// One problem a rule found, positioned in the document when the rule knows where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl From<String> for LintFinding {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            line: None,
+            column: None,
+        }
+    }
+}
+
+impl From<&str> for LintFinding {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+type CheckFn = fn(&LintArtifact, &LintContext) -> Vec<LintFinding>;
+
+/// A data-model rule for one artifact kind. `heading` is the anchor of the
+/// `spec/specman-data-model` heading the rule enforces.
+#[derive(Clone, Debug, Serialize)]
+pub struct LintRule {
+    pub id: &'static str,
+    pub kind: ArtifactKind,
+    pub severity: Severity,
+    pub heading: &'static str,
+    pub summary: &'static str,
+    #[serde(skip)]
+    check: CheckFn,
+}
+
+impl LintRule {
+    pub fn new(
+        id: &'static str,
+        kind: ArtifactKind,
+        severity: Severity,
+        heading: &'static str,
+        summary: &'static str,
+        check: CheckFn,
+    ) -> Self {
+        Self {
+            id,
+            kind,
+            severity,
+            heading,
+            summary,
+            check,
+        }
+    }
+
+    /// Link to the heading the rule enforces, e.g.
+    /// `spec/specman-data-model/spec.md#scratch-pad-content`.
+    pub fn citation(&self) -> String {
+        format!("{DATA_MODEL_SPEC}#{}", self.heading)
+    }
+}
+
+/// A rule violation found in one artifact.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub artifact: ArtifactId,
+    /// Workspace-relative path of the offending document.
+    pub path: String,
+    /// Data-model heading the rule enforces.
+    pub heading: String,
+    pub message: String,
+    /// 1-based position in the document, when the rule reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// Result of linting a workspace.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct LintReport {
+    /// Number of artifacts checked.
+    pub artifacts: usize,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+/// Ordered set of lint rules run against every specification, implementation, and scratch
+/// pad in a workspace.
+#[derive(Clone, Debug, Default)]
+pub struct Linter {
+    rules: Vec<LintRule>,
+}
+
+impl Linter {
+    /// Linter enforcing the SpecMan Data Model rules built into this library.
+    pub fn data_model() -> Self {
+        use ArtifactKind::{Implementation, ScratchPad, Specification};
+        use Severity::{Error, Warning};
+
+        let rules = vec![
+            LintRule::new(
+                "spec-front-matter",
+                Specification,
+                Error,
+                "specification-metadata",
+                "Specification front matter is a YAML mapping with the documented fields.",
+                check_front_matter,
+            ),
+            LintRule::new(
+                "spec-name-format",
+                Specification,
+                Error,
+                "specification-metadata",
+                "Specification names are lowercase words joined by hyphens, four words at most.",
+                check_name_format,
+            ),
+            LintRule::new(
+                "spec-name-verb",
+                Specification,
+                Warning,
+                "specification-metadata",
+                "Specification names do not contain verbs.",
+                check_name_verbs,
+            ),
+            LintRule::new(
+                "spec-unique-headings",
+                Specification,
+                Error,
+                "specification-headings",
+                "Every heading in a specification is unique.",
+                check_unique_headings,
+            ),
+            LintRule::new(
+                "spec-terminology-heading",
+                Specification,
+                Warning,
+                "specification-headings",
+                "Specifications include a \"Terminology & References\" heading.",
+                check_terminology_heading,
+            ),
+            LintRule::new(
+                "spec-implementation-dependency",
+                Specification,
+                Error,
+                "dependencies",
+                "Specifications do not declare implementations as dependencies.",
+                check_spec_dependencies,
+            ),
+            LintRule::new(
+                "impl-front-matter",
+                Implementation,
+                Error,
+                "implementation-metadata",
+                "Implementation front matter is a YAML mapping with the documented fields.",
+                check_front_matter,
+            ),
+            LintRule::new(
+                "impl-name-format",
+                Implementation,
+                Error,
+                "implementation-metadata",
+                "Implementation names are lowercase words joined by hyphens, four words at most.",
+                check_name_format,
+            ),
+            LintRule::new(
+                "impl-name-verb",
+                Implementation,
+                Warning,
+                "implementation-metadata",
+                "Implementation names do not contain verbs.",
+                check_name_verbs,
+            ),
+            LintRule::new(
+                "impl-name-language",
+                Implementation,
+                Warning,
+                "implementation-metadata",
+                "Implementation names carry the implementing language keyword.",
+                check_name_language,
+            ),
+            LintRule::new(
+                "impl-name-collision",
+                Implementation,
+                Error,
+                "implementation-metadata",
+                "Implementation names do not collide with specification names.",
+                check_name_collision,
+            ),
+            LintRule::new(
+                "impl-spec-required",
+                Implementation,
+                Error,
+                "specification-coverage",
+                "Implementations declare the specification they implement in `spec`.",
+                check_spec_declared,
+            ),
+            LintRule::new(
+                "impl-reference-type",
+                Implementation,
+                Error,
+                "references",
+                "Implementation references declare `type: implementation` or `type: specification`.",
+                check_reference_types,
+            ),
+            LintRule::new(
+                "scratch-front-matter",
+                ScratchPad,
+                Error,
+                "scratch-pad-metadata",
+                "Scratch pad front matter is a YAML mapping with the documented fields.",
+                check_front_matter,
+            ),
+            LintRule::new(
+                "scratch-name-format",
+                ScratchPad,
+                Error,
+                "scratch-pads",
+                "Scratch pad names are lowercase words joined by hyphens, four words at most.",
+                check_name_format,
+            ),
+            LintRule::new(
+                "scratch-target-required",
+                ScratchPad,
+                Error,
+                "target-artifact",
+                "Scratch pads declare the artifact they work on in `target`.",
+                check_target_declared,
+            ),
+            LintRule::new(
+                "scratch-work-type-required",
+                ScratchPad,
+                Error,
+                "work-type",
+                "Scratch pads declare exactly one `work_type`.",
+                check_work_type_declared,
+            ),
+            LintRule::new(
+                "scratch-work-type-target",
+                ScratchPad,
+                Error,
+                "work-type",
+                "Scratch pad targets and heading fragments fit the declared work type.",
+                check_work_type_target,
+            ),
+            LintRule::new(
+                "scratch-notes-section",
+                ScratchPad,
+                Error,
+                "scratch-pad-content",
+                "Scratch pads contain a notes section.",
+                check_notes_section,
+            ),
+        ];
+        Self { rules }
+    }
+
+    /// Appends a rule, typically one specific to a workspace or front end.
+    pub fn register(&mut self, rule: LintRule) {
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> &[LintRule] {
+        &self.rules
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LintRule> {
+        self.rules.iter().find(|rule| rule.id == id)
+    }
+
+    /// Runs every rule against every artifact in the workspace. Diagnostics are ordered by
+    /// path, then by rule order.
+    pub fn run(&self, workspace: &WorkspacePaths) -> Result<LintReport, SpecmanError> {
+        let context = LintContext::load(workspace)?;
+        let mut diagnostics = Vec::new();
+        for artifact in &context.artifacts {
+            for rule in self
+                .rules
+                .iter()
+                .filter(|rule| rule.kind == artifact.id.kind)
+            {
+                for finding in (rule.check)(artifact, &context) {
+                    diagnostics.push(LintDiagnostic {
+                        rule: rule.id.to_string(),
+                        severity: rule.severity,
+                        artifact: artifact.id.clone(),
+                        path: artifact.relative.clone(),
+                        heading: rule.citation(),
+                        message: finding.message,
+                        line: finding.line,
+                        column: finding.column,
+                    });
+                }
+            }
+        }
+        Ok(LintReport {
+            artifacts: context.artifacts.len(),
+            diagnostics,
+        })
+    }
+
+    /// Renders a report as a SARIF 2.1.0 log so code-scanning tools can annotate the
+    /// offending documents.
+    pub fn sarif(&self, report: &LintReport) -> Value {
+        let rules: Vec<Value> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                json!({
+                    "id": rule.id,
+                    "shortDescription": { "text": rule.summary },
+                    "help": { "text": format!("See {}", rule.citation()) },
+                    "defaultConfiguration": { "level": rule.severity.to_string() },
+                    "properties": { "heading": rule.citation() },
+                })
+            })
+            .collect();
+        let results: Vec<Value> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let mut result = json!({
+                    "ruleId": diagnostic.rule,
+                    "level": diagnostic.severity.to_string(),
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": diagnostic.path,
+                                "uriBaseId": "%SRCROOT%",
+                            }
+                        }
+                    }],
+                });
+                if let Some(line) = diagnostic.line {
+                    let mut region = json!({ "startLine": line });
+                    if let Some(column) = diagnostic.column {
+                        region["startColumn"] = json!(column);
+                    }
+                    result["locations"][0]["physicalLocation"]["region"] = region;
+                }
+                if let Some(index) = self
+                    .rules
+                    .iter()
+                    .position(|rule| rule.id == diagnostic.rule)
+                {
+                    result["ruleIndex"] = json!(index);
+                }
+                result
+            })
+            .collect();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "specman",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        })
+    }
+}
+
+impl LintContext {
+    fn load(workspace: &WorkspacePaths) -> Result<Self, SpecmanError> {
+        let mut files = gather_workspace_artifacts(workspace)?;
+        files.sort();
+
+        let mut artifacts = Vec::new();
+        for path in files {
+            let Some(id) = artifact_for_path(workspace, &path) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)?;
+            let kind = match id.kind {
+                ArtifactKind::Specification => FrontMatterKind::Specification,
+                ArtifactKind::Implementation => FrontMatterKind::Implementation,
+                ArtifactKind::ScratchPad => FrontMatterKind::ScratchPad,
+            };
+            let front = front_matter::split_front_matter(&content)
+                .and_then(|split| split.parse_as(kind))
+                .map_err(|err| match err {
+                    SpecmanError::FrontMatter(err) => *err,
+                    other => FrontMatterError::new(other.to_string()),
+                });
+            let relative = path
+                .strip_prefix(workspace.root())
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            artifacts.push(LintArtifact {
+                id,
+                path,
+                relative,
+                content,
+                front,
+            });
+        }
+        Ok(Self {
+            workspace: workspace.clone(),
+            artifacts,
+        })
+    }
+}
+
+fn check_front_matter(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    match &artifact.front {
+        Ok(_) => Vec::new(),
+        Err(err) => vec![LintFinding {
+            message: format!("front matter does not parse: {}", err.message),
+            line: err.line,
+            column: err.column,
+        }],
+    }
+}
+
+fn check_name_format(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let name = &artifact.id.name;
+    if name.is_empty()
+        || name
+            .chars()
+            .any(|ch| !(ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-'))
+    {
+        return vec![LintFinding::from(format!(
+            "name '{name}' must be lowercase alphanumeric words separated by hyphens"
+        ))];
+    }
+    let words = name_words(name).len();
+    if words > 4 {
+        return vec![LintFinding::from(format!(
+            "name '{name}' has {words} words; use four or fewer"
+        ))];
+    }
+    Vec::new()
+}
+
+fn check_name_verbs(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    name_words(&artifact.id.name)
+        .into_iter()
+        .filter(|word| NAME_VERBS.contains(word))
+        .map(|verb| {
+            format!(
+                "name '{}' contains the verb '{verb}'; name the feature, not the change",
+                artifact.id.name
+            )
+            .into()
+        })
+        .collect()
+}
+
+fn check_unique_headings(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    let mut messages = Vec::new();
+    for anchor in headings::base_anchors(&artifact.content) {
+        if !seen.insert(anchor.clone()) && reported.insert(anchor.clone()) {
+            messages.push(format!("heading #{anchor} appears more than once").into());
+        }
+    }
+    messages
+}
+
+fn check_terminology_heading(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let anchors = headings::heading_anchors(&artifact.content);
+    if anchors
+        .iter()
+        .any(|anchor| anchor == "terminology--references")
+    {
+        Vec::new()
+    } else {
+        vec!["missing a \"Terminology & References\" heading".into()]
+    }
+}
+
+fn check_spec_dependencies(artifact: &LintArtifact, context: &LintContext) -> Vec<LintFinding> {
+    let Ok(ArtifactFrontMatter::Specification(spec)) = &artifact.front else {
+        return Vec::new();
+    };
+    spec.dependencies
+        .iter()
+        .map(|entry| match entry {
+            DependencyEntry::Simple(reference) => reference.as_str(),
+            DependencyEntry::Detailed(object) => object.reference.as_str(),
+        })
+        .filter(|reference| {
+            target_kind(reference, &artifact.path, &context.workspace)
+                == Some(ArtifactKind::Implementation)
+        })
+        .map(|reference| format!("dependency {reference} is an implementation").into())
+        .collect()
+}
+
+fn check_name_language(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let Some(implementation) = implementation(artifact) else {
+        return Vec::new();
+    };
+    let keywords: Vec<&str> = implementation
+        .primary_language
+        .iter()
+        .chain(&implementation.secondary_languages)
+        .map(|language| {
+            language
+                .language
+                .split('@')
+                .next()
+                .unwrap_or_default()
+                .trim()
+        })
+        .filter(|keyword| !keyword.is_empty())
+        .collect();
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+    let words = name_words(&artifact.id.name);
+    if keywords
+        .iter()
+        .any(|keyword| words.contains(&keyword.to_ascii_lowercase().as_str()))
+    {
+        Vec::new()
+    } else {
+        vec![LintFinding::from(format!(
+            "name '{}' does not mention its language ({})",
+            artifact.id.name,
+            keywords.join(", ")
+        ))]
+    }
+}
+
+fn check_name_collision(artifact: &LintArtifact, context: &LintContext) -> Vec<LintFinding> {
+    let collides = context.artifacts.iter().any(|other| {
+        other.id.kind == ArtifactKind::Specification && other.id.name == artifact.id.name
+    });
+    if collides {
+        vec![LintFinding::from(format!(
+            "name '{}' is also a specification name",
+            artifact.id.name
+        ))]
+    } else {
+        Vec::new()
+    }
+}
+
+fn check_spec_declared(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    match implementation(artifact) {
+        Some(implementation)
+            if implementation
+                .spec
+                .as_deref()
+                .is_none_or(|spec| spec.trim().is_empty()) =>
+        {
+            vec!["front matter has no `spec` field".into()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn check_reference_types(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let Some(implementation) = implementation(artifact) else {
+        return Vec::new();
+    };
+    implementation
+        .references
+        .iter()
+        .filter_map(|reference| match reference.reference_type.as_deref() {
+            Some("implementation" | "specification") => None,
+            Some(other) => Some(format!(
+                "reference {} has type '{other}'",
+                reference.reference
+            )),
+            None => Some(format!("reference {} has no type", reference.reference)),
+        })
+        .map(LintFinding::from)
+        .collect()
+}
+
+fn check_target_declared(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    match &artifact.front {
+        Ok(ArtifactFrontMatter::Scratch(scratch))
+            if scratch
+                .target
+                .as_deref()
+                .is_none_or(|target| target.trim().is_empty()) =>
+        {
+            vec!["front matter has no `target` field".into()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn check_work_type_declared(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    match &artifact.front {
+        Ok(ArtifactFrontMatter::Scratch(scratch)) if scratch.work_type.is_none() => {
+            vec!["front matter has no `work_type` field".into()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn check_work_type_target(artifact: &LintArtifact, context: &LintContext) -> Vec<LintFinding> {
+    let Ok(ArtifactFrontMatter::Scratch(scratch)) = &artifact.front else {
+        return Vec::new();
+    };
+    let (Some(work_type), Some(target), Some(dir)) = (
+        &scratch.work_type,
+        scratch.target.as_deref(),
+        artifact.path.parent(),
+    ) else {
+        return Vec::new();
+    };
+    scratchpad::work_type_violations(work_type, target, dir, &context.workspace)
+        .into_iter()
+        .map(LintFinding::from)
+        .collect()
+}
+
+fn check_notes_section(artifact: &LintArtifact, _: &LintContext) -> Vec<LintFinding> {
+    let anchors = headings::heading_anchors(&artifact.content);
+    if anchors.iter().any(|anchor| anchor.contains("notes")) {
+        Vec::new()
+    } else {
+        vec!["missing a notes section".into()]
+    }
+}
+
+fn implementation(artifact: &LintArtifact) -> Option<&ImplementationFrontMatter> {
+    match &artifact.front {
+        Ok(ArtifactFrontMatter::Implementation(implementation)) => Some(implementation),
+        _ => None,
+    }
+}
+
+fn name_words(name: &str) -> Vec<&str> {
+    name.split('-').filter(|word| !word.is_empty()).collect()
+}
+
+/// Kind of the workspace artifact a locator points at, if it points inside the workspace.
+fn target_kind(reference: &str, from: &Path, workspace: &WorkspacePaths) -> Option<ArtifactKind> {
+    let (reference, _) = headings::split_fragment(reference);
+    let path = local_reference_path(reference, from.parent()?, workspace)?;
+    artifact_for_path(workspace, &path).map(|id| id.kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator};
+
+    #[test]
+    fn reports_data_model_violations_with_heading_citations() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        write(
+            root,
+            "spec/core/spec.md",
+            "---\nname: core\nversion: \"1.0.0\"\ndependencies:\n  - ../../impl/core-rust/impl.md\n---\n# Core\n\n## Terminology & References\n\n## Concepts\n\n## Concepts\n",
+        );
+        write(
+            root,
+            "spec/add-widgets/spec.md",
+            "---\nname: add-widgets\n---\n# Widgets\n\n## Overview\n",
+        );
+        write(
+            root,
+            "impl/core-rust/impl.md",
+            "---\nspec: spec://core\nprimary_language:\n  language: rust@1.91\nreferences:\n  - ref: spec://add-widgets\n---\n# Core in Rust\n",
+        );
+        write(
+            root,
+            "impl/core/impl.md",
+            "---\nprimary_language:\n  language: python@3.12\n---\n# Core\n",
+        );
+        write(
+            root,
+            ".specman/scratchpad/fix-core/scratch.md",
+            "---\ntarget: spec://core\nwork_type:\n  fix: {}\n---\n# Fix core\n\n## Context\n",
+        );
+        write(
+            root,
+            ".specman/scratchpad/core-notes/scratch.md",
+            "---\nbranch: main\n---\n# Core\n\n## Notes\n",
+        );
+        let workspace = FilesystemWorkspaceLocator::new(root).workspace().unwrap();
+
+        let report = Linter::data_model().run(&workspace).unwrap();
+        assert_eq!(report.artifacts, 6);
+        let found: Vec<(&str, &str, Severity)> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.path.as_str(),
+                    diagnostic.rule.as_str(),
+                    diagnostic.severity,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    ".specman/scratchpad/core-notes/scratch.md",
+                    "scratch-target-required",
+                    Severity::Error
+                ),
+                (
+                    ".specman/scratchpad/core-notes/scratch.md",
+                    "scratch-work-type-required",
+                    Severity::Error
+                ),
+                (
+                    ".specman/scratchpad/fix-core/scratch.md",
+                    "scratch-work-type-target",
+                    Severity::Error
+                ),
+                (
+                    ".specman/scratchpad/fix-core/scratch.md",
+                    "scratch-notes-section",
+                    Severity::Error
+                ),
+                ("impl/core/impl.md", "impl-name-language", Severity::Warning),
+                ("impl/core/impl.md", "impl-name-collision", Severity::Error),
+                ("impl/core/impl.md", "impl-spec-required", Severity::Error),
+                (
+                    "impl/core-rust/impl.md",
+                    "impl-reference-type",
+                    Severity::Error
+                ),
+                (
+                    "spec/add-widgets/spec.md",
+                    "spec-name-verb",
+                    Severity::Warning
+                ),
+                (
+                    "spec/add-widgets/spec.md",
+                    "spec-terminology-heading",
+                    Severity::Warning
+                ),
+                ("spec/core/spec.md", "spec-unique-headings", Severity::Error),
+                (
+                    "spec/core/spec.md",
+                    "spec-implementation-dependency",
+                    Severity::Error
+                ),
+            ]
+        );
+        assert_eq!(
+            report.diagnostics[2].heading,
+            "spec/specman-data-model/spec.md#work-type"
+        );
+        assert_eq!(
+            report.diagnostics[2].message,
+            "`fix` scratch pads must target an implementation, but spec://core is a specification"
+        );
+        assert_eq!(report.count(Severity::Error), 9);
+    }
+
+    #[test]
+    fn renders_sarif_with_rule_metadata() {
+        let linter = Linter::data_model();
+        let report = LintReport {
+            artifacts: 1,
+            diagnostics: vec![LintDiagnostic {
+                rule: "spec-name-verb".into(),
+                severity: Severity::Warning,
+                artifact: ArtifactId {
+                    kind: ArtifactKind::Specification,
+                    name: "add-widgets".into(),
+                },
+                path: "spec/add-widgets/spec.md".into(),
+                heading: linter.get("spec-name-verb").unwrap().citation(),
+                message: "name 'add-widgets' contains the verb 'add'".into(),
+                line: None,
+                column: None,
+            }],
+        };
+        let mut located = report.diagnostics[0].clone();
+        located.rule = "spec-front-matter".into();
+        located.severity = Severity::Error;
+        located.line = Some(3);
+        located.column = Some(10);
+        let report = LintReport {
+            artifacts: 1,
+            diagnostics: vec![report.diagnostics[0].clone(), located],
+        };
+
+        let log = linter.sarif(&report);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"].as_array().unwrap().len(),
+            linter.rules().len()
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "spec/add-widgets/spec.md"
+        );
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(
+            run["tool"]["driver"]["rules"][index]["id"],
+            "spec-name-verb"
+        );
+        assert!(result["locations"][0]["physicalLocation"]["region"].is_null());
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"]["region"],
+            json!({ "startLine": 3, "startColumn": 10 })
+        );
+    }
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
@@ -0,0 +1,286 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde_json::json;
+
+use crate::audit;
+use crate::dependency_tree::{ArtifactId, ArtifactKind};
+use crate::error::SpecmanError;
+use crate::workspace::WorkspacePaths;
+
+/// Timing knobs for advisory artifact locks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockOptions {
+    /// How long to wait for a competing holder before failing.
+    pub timeout: Duration,
+    /// Locks older than this are assumed to belong to a crashed process and are reclaimed.
+    pub stale_after: Duration,
+    /// Delay between acquisition attempts while waiting.
+    pub retry_interval: Duration,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            stale_after: Duration::from_secs(300),
+            retry_interval: Duration::from_millis(50),
+        }
+    }
+}
+
+impl LockOptions {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn stale_after(mut self, stale_after: Duration) -> Self {
+        self.stale_after = stale_after;
+        self
+    }
+
+    /// Fails immediately instead of waiting for a competing holder.
+    pub fn fail_fast() -> Self {
+        Self::default().timeout(Duration::ZERO)
+    }
+}
+
+/// Hands out advisory per-artifact locks stored as files under `.specman/locks/`, so
+/// concurrent SpecMan processes serialize writes to the same artifact
+/// (spec/specman-core/spec.md#concept-deterministic-execution).
+#[derive(Clone, Debug)]
+pub struct WorkspaceLockManager {
+    locks_dir: PathBuf,
+    options: LockOptions,
+}
+
+impl WorkspaceLockManager {
+    pub fn new(workspace: &WorkspacePaths) -> Self {
+        Self::with_options(workspace, LockOptions::default())
+    }
+
+    pub fn with_options(workspace: &WorkspacePaths, options: LockOptions) -> Self {
+        Self {
+            locks_dir: workspace.dot_specman().join("locks"),
+            options,
+        }
+    }
+
+    /// Lock file backing `artifact` (`.specman/locks/{kind}-{name}.lock`).
+    pub fn lock_path(&self, artifact: &ArtifactId) -> PathBuf {
+        let kind = match artifact.kind {
+            ArtifactKind::Specification => "spec",
+            ArtifactKind::Implementation => "impl",
+            ArtifactKind::ScratchPad => "scratch",
+        };
+        self.locks_dir
+            .join(format!("{kind}-{}.lock", artifact.name))
+    }
+
+    /// Acquires the lock for `artifact`, waiting up to the configured timeout. Locks whose
+    /// files are older than `stale_after` are reclaimed and acquisition is retried.
+    pub fn acquire(&self, artifact: &ArtifactId) -> Result<ArtifactLock, SpecmanError> {
+        fs::create_dir_all(&self.locks_dir)?;
+        let path = self.lock_path(artifact);
+        let start = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let holder = json!({
+                        "artifact": artifact,
+                        "pid": std::process::id(),
+                        "acquired_at": audit::timestamp(SystemTime::now()),
+                    });
+                    let lock = ArtifactLock { path };
+                    writeln!(file, "{holder}")?;
+                    return Ok(lock);
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                    if self.reclaim_if_stale(&path)? {
+                        continue;
+                    }
+                    if start.elapsed() >= self.options.timeout {
+                        return Err(SpecmanError::Lock(format!(
+                            "{} {} is locked by another operation ({})",
+                            kind_label(artifact.kind),
+                            artifact.name,
+                            path.display()
+                        )));
+                    }
+                    thread::sleep(self.options.retry_interval);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Reclaims `path` when its holder looks crashed, without deleting a fresh lock that
+    /// another contender acquired after we read the stale one.
+    fn reclaim_if_stale(&self, path: &Path) -> Result<bool, SpecmanError> {
+        let modified = match fs::metadata(path).and_then(|meta| meta.modified()) {
+            Ok(modified) => modified,
+            // The holder released the lock between our attempts; retry right away.
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(true),
+            Err(err) => return Err(err.into()),
+        };
+        let age = modified.elapsed().unwrap_or_default();
+        if age < self.options.stale_after {
+            return Ok(false);
+        }
+        let stale_holder = match fs::read(path) {
+            Ok(holder) => holder,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(true),
+            Err(err) => return Err(err.into()),
+        };
+
+        reclaim(path, &stale_holder)?;
+        Ok(true)
+    }
+}
+
+/// Removes the lock at `path` if it still holds `stale_holder`. Renaming the file aside is
+/// atomic, so concurrent reclaimers cannot both delete it; if the moved file turns out to be a
+/// fresh holder's lock, it is linked back unless yet another holder has taken the path since.
+fn reclaim(path: &Path, stale_holder: &[u8]) -> Result<(), SpecmanError> {
+    static RECLAIMS: AtomicUsize = AtomicUsize::new(0);
+    let aside = path.with_extension(format!(
+        "lock.stale-{}-{}",
+        std::process::id(),
+        RECLAIMS.fetch_add(1, Ordering::Relaxed)
+    ));
+    match fs::rename(path, &aside) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    }
+    if fs::read(&aside)? != stale_holder {
+        let _ = fs::hard_link(&aside, path);
+    }
+    fs::remove_file(&aside)?;
+    Ok(())
+}
+
+/// Held artifact lock; the lock file is removed when dropped.
+#[derive(Debug)]
+pub struct ArtifactLock {
+    path: PathBuf,
+}
+
+impl ArtifactLock {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ArtifactLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn kind_label(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Specification => "specification",
+        ArtifactKind::Implementation => "implementation",
+        ArtifactKind::ScratchPad => "scratch pad",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn workspace() -> (tempfile::TempDir, WorkspacePaths) {
+        let temp = tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        let paths = WorkspacePaths::new(root.clone(), root.join(".specman"));
+        (temp, paths)
+    }
+
+    fn spec(name: &str) -> ArtifactId {
+        ArtifactId {
+            kind: ArtifactKind::Specification,
+            name: name.into(),
+        }
+    }
+
+    #[test]
+    fn locks_are_exclusive_per_artifact_and_released_on_drop() {
+        let (_temp, workspace) = workspace();
+        let manager = WorkspaceLockManager::with_options(&workspace, LockOptions::fail_fast());
+
+        let held = manager.acquire(&spec("alpha")).expect("first lock");
+        assert!(held.path().ends_with(".specman/locks/spec-alpha.lock"));
+        let err = manager.acquire(&spec("alpha")).expect_err("contended lock");
+        assert!(matches!(err, SpecmanError::Lock(_)));
+        let _other = manager
+            .acquire(&spec("beta"))
+            .expect("independent artifact");
+
+        drop(held);
+        manager
+            .acquire(&spec("alpha"))
+            .expect("lock released on drop");
+    }
+
+    #[test]
+    fn waiting_acquire_succeeds_once_holder_releases() {
+        let (_temp, workspace) = workspace();
+        let manager = WorkspaceLockManager::with_options(
+            &workspace,
+            LockOptions::default().timeout(Duration::from_secs(5)),
+        );
+        let held = manager.acquire(&spec("alpha")).unwrap();
+
+        let waiter = {
+            let manager = manager.clone();
+            thread::spawn(move || manager.acquire(&spec("alpha")).map(|_| ()))
+        };
+        thread::sleep(Duration::from_millis(100));
+        drop(held);
+        waiter
+            .join()
+            .unwrap()
+            .expect("waiter acquires after release");
+    }
+
+    #[test]
+    fn stale_locks_are_reclaimed() {
+        let (_temp, workspace) = workspace();
+        let manager = WorkspaceLockManager::with_options(
+            &workspace,
+            LockOptions::fail_fast().stale_after(Duration::ZERO),
+        );
+        let abandoned = manager.acquire(&spec("alpha")).unwrap();
+        std::mem::forget(abandoned);
+
+        let reclaimed = manager
+            .acquire(&spec("alpha"))
+            .expect("stale lock reclaimed");
+        assert!(reclaimed.path().is_file());
+    }
+
+    #[test]
+    fn reclaiming_a_stale_lock_spares_a_holder_that_replaced_it() {
+        let (_temp, workspace) = workspace();
+        let manager = WorkspaceLockManager::new(&workspace);
+        let path = manager.lock_path(&spec("alpha"));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        fs::write(&path, "{\"pid\":1}\n").unwrap();
+        reclaim(&path, b"{\"pid\":1}\n").unwrap();
+        assert!(!path.exists(), "stale holder removed");
+
+        // Another contender reclaimed the stale record and took the lock before our rename.
+        fs::write(&path, "{\"pid\":2}\n").unwrap();
+        reclaim(&path, b"{\"pid\":1}\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"pid\":2}\n");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+}
@@ -4,18 +4,30 @@ use std::sync::Arc;
 
 use serde_yaml::{Mapping, Value};
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
 use crate::adapter::DataModelAdapter;
-use crate::dependency_tree::{ArtifactId, ArtifactKind, validate_workspace_reference};
+use crate::audit::{self, AuditLog, OperationEnvelope};
+use crate::capability::METADATA_MUTATE;
+use crate::dependency_tree::{
+    ArtifactId, ArtifactKind, DependencyQuery, ResourceHandle, validate_workspace_reference,
+};
 use crate::error::SpecmanError;
-use crate::front_matter::{self, ArtifactFrontMatter, FrontMatterKind};
+use crate::front_matter::{self, ArtifactFrontMatter, FrontMatterKind, ScratchFrontMatter};
+use crate::impact::artifact_for_path;
+use crate::locking::{LockOptions, WorkspaceLockManager};
 use crate::persistence::PersistedArtifact;
+use crate::scratchpad;
 use crate::workspace::{WorkspaceLocator, WorkspacePaths};
 
-/// Adds dependencies or references to an artifact's YAML front matter without
-/// rewriting the Markdown body.
+/// Adds dependencies or references to an artifact's YAML front matter, or repoints existing
+/// locators, without rewriting the Markdown body.
 pub struct MetadataMutator<L: WorkspaceLocator> {
     workspace: L,
     adapter: Option<Arc<dyn DataModelAdapter>>,
+    lock_options: LockOptions,
 }
 
 impl<L: WorkspaceLocator> MetadataMutator<L> {
@@ -23,6 +35,7 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
         Self {
             workspace,
             adapter: None,
+            lock_options: LockOptions::default(),
         }
     }
 
@@ -30,21 +43,32 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
         Self {
             workspace,
             adapter: Some(adapter),
+            lock_options: LockOptions::default(),
         }
     }
 
+    /// Overrides how long mutations wait for, and when they reclaim, per-artifact locks.
+    pub fn lock_options(mut self, options: LockOptions) -> Self {
+        self.lock_options = options;
+        self
+    }
+
     pub fn mutate(
         &self,
         request: MetadataMutationRequest,
     ) -> Result<MetadataMutationResult, SpecmanError> {
-        if request.add_dependencies.is_empty() && request.add_references.is_empty() {
+        if request.add_dependencies.is_empty()
+            && request.add_references.is_empty()
+            && request.replace_locators.is_empty()
+        {
             return Err(SpecmanError::Template(
                 "metadata mutation requires at least one operation".into(),
             ));
         }
 
         let workspace_paths = self.workspace.workspace()?;
-        let canonical_path = fs::canonicalize(&request.path)?;
+        let target_path = resolve_target_path(&request.path, &workspace_paths)?;
+        let canonical_path = fs::canonicalize(&target_path)?;
         if !canonical_path.starts_with(workspace_paths.root()) {
             return Err(SpecmanError::Workspace(format!(
                 "path {} is outside the workspace {}",
@@ -60,14 +84,28 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
             ))
         })?;
 
+        // Lock before reading so a concurrent writer cannot slip in between our read and
+        // write of the same document. Previews never write, so they skip the lock.
+        let _lock = if request.persist {
+            let target = lock_target(&canonical_path, &workspace_paths)?;
+            Some(
+                WorkspaceLockManager::with_options(&workspace_paths, self.lock_options)
+                    .acquire(&target)?,
+            )
+        } else {
+            None
+        };
         let raw = fs::read_to_string(&canonical_path)?;
-        let split = front_matter::split_front_matter(&raw)?;
-        let yaml_segment = split.yaml.to_string();
+        let file = canonical_path
+            .strip_prefix(workspace_paths.root())
+            .unwrap_or(&canonical_path)
+            .display()
+            .to_string();
+        let split = front_matter::split_front_matter(&raw).map_err(|err| err.in_file(&file))?;
         let body_segment = split.body.to_string();
 
-        let mut yaml_value: Value = serde_yaml::from_str(&yaml_segment)
-            .map_err(|err| SpecmanError::Serialization(err.to_string()))?;
-        let typed_front = ArtifactFrontMatter::from_yaml_value(&yaml_value)?;
+        let typed_front = split.parse().map_err(|err| err.in_file(&file))?;
+        let mut yaml_value: Value = split.deserialize()?;
         let mapping = yaml_value
             .as_mapping_mut()
             .ok_or_else(|| SpecmanError::Template("front matter must be a YAML mapping".into()))?;
@@ -83,6 +121,11 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
             workspace: &workspace_paths,
         };
 
+        let scratch_fields = |mapping: &Mapping| {
+            ["target", "work_type"].map(|field| mapping.get(Value::String(field.into())).cloned())
+        };
+        let original_scratch_fields = scratch_fields(mapping);
+
         let mut mutated = false;
         if !request.add_dependencies.is_empty() {
             let handler = SpecificationMetadataHandler::new(&request.add_dependencies);
@@ -94,6 +137,19 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
             mutated |= handler.apply(&artifact, mapping, &context)?;
         }
 
+        if !request.replace_locators.is_empty() {
+            let handler = LocatorReplacementHandler::new(&request.replace_locators);
+            mutated |= handler.apply(&artifact, mapping, &context)?;
+        }
+
+        // Only a changed target can break work-type compatibility; leave unrelated edits to
+        // scratch pads that are already out of line to `specman lint`.
+        if artifact.kind == ArtifactKind::ScratchPad
+            && scratch_fields(mapping) != original_scratch_fields
+        {
+            validate_scratch_work_type(mapping, &context)?;
+        }
+
         let mut updated_document = raw;
         if mutated {
             let rendered_yaml = serde_yaml::to_string(&Value::Mapping(mapping.clone()))
@@ -103,11 +159,22 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
 
         let mut persisted = None;
         if mutated && request.persist {
+            let envelope = OperationEnvelope::begin(
+                METADATA_MUTATE,
+                sanitized_inputs(&request, &workspace_paths),
+            );
+            let audit_log = AuditLog::open(&workspace_paths)?;
             fs::write(&canonical_path, &updated_document)?;
+            let envelope = envelope
+                .updated(&workspace_paths, &canonical_path)
+                .complete();
+            let audit_error = audit_log.record(&envelope);
             let artifact_record = PersistedArtifact {
                 artifact: artifact.clone(),
                 path: canonical_path.clone(),
                 workspace: workspace_paths.clone(),
+                envelope,
+                audit_error,
             };
             if let Some(adapter) = &self.adapter {
                 adapter.invalidate_dependency_tree(&artifact)?;
@@ -123,6 +190,67 @@ impl<L: WorkspaceLocator> MetadataMutator<L> {
     }
 }
 
+/// Lock key for a document, matching the ids `WorkspacePersistence` derives from canonical
+/// artifact directories. Documents outside an artifact folder cannot be persisted.
+fn lock_target(path: &Path, workspace: &WorkspacePaths) -> Result<ArtifactId, SpecmanError> {
+    artifact_for_path(workspace, path).ok_or_else(|| {
+        SpecmanError::Workspace(format!(
+            "{} is not inside a specification, implementation, or scratch pad folder",
+            path.display()
+        ))
+    })
+}
+
+fn sanitized_inputs(
+    request: &MetadataMutationRequest,
+    workspace: &WorkspacePaths,
+) -> serde_json::Value {
+    let references: Vec<_> = request
+        .add_references
+        .iter()
+        .map(|reference| {
+            json!({
+                "locator": audit::sanitize_locator(&reference.locator),
+                "type": reference.reference_type,
+                "optional": reference.optional,
+            })
+        })
+        .collect();
+    json!({
+        "path": request.path.strip_prefix(workspace.root()).unwrap_or(&request.path),
+        "add_dependencies": request
+            .add_dependencies
+            .iter()
+            .map(|locator| audit::sanitize_locator(locator))
+            .collect::<Vec<_>>(),
+        "add_references": references,
+        "replace_locators": request
+            .replace_locators
+            .iter()
+            .map(|replacement| {
+                json!({
+                    "from": audit::sanitize_locator(&replacement.from),
+                    "to": audit::sanitize_locator(&replacement.to),
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn validate_scratch_work_type(
+    mapping: &Mapping,
+    ctx: &MetadataContext,
+) -> Result<(), SpecmanError> {
+    let scratch: ScratchFrontMatter = serde_yaml::from_value(Value::Mapping(mapping.clone()))
+        .map_err(|err| SpecmanError::Serialization(err.to_string()))?;
+    match (&scratch.work_type, scratch.target.as_deref()) {
+        (Some(work_type), Some(target)) => {
+            scratchpad::validate_work_type(work_type, target, ctx.parent_dir, ctx.workspace)
+        }
+        _ => Ok(()),
+    }
+}
+
 fn compose_document(yaml: &str, body: &str) -> String {
     let mut output = String::from("---\n");
     output.push_str(yaml);
@@ -255,12 +383,27 @@ fn reference_matches(value: &Value, locator: &str) -> bool {
     }
 }
 
+/// Accepts either a filesystem path or a `spec://`/`impl://`/`scratch://` handle as the mutation
+/// target. Read-only `/dependencies` queries are rejected before any file is touched.
+fn resolve_target_path(path: &Path, workspace: &WorkspacePaths) -> Result<PathBuf, SpecmanError> {
+    let raw = path.to_string_lossy();
+    DependencyQuery::ensure_mutable(&raw)?;
+    match ResourceHandle::parse(&raw)? {
+        Some(handle) => Ok(handle.to_path(workspace)),
+        None => Ok(path.to_path_buf()),
+    }
+}
+
 /// Parameters for metadata mutation operations.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct MetadataMutationRequest {
     pub path: PathBuf,
     pub add_dependencies: Vec<String>,
     pub add_references: Vec<ReferenceAddition>,
+    /// Locators to repoint wherever they appear in `dependencies`, `references`, `spec`, or
+    /// `target`.
+    pub replace_locators: Vec<LocatorReplacement>,
     pub persist: bool,
 }
 
@@ -279,10 +422,12 @@ impl MetadataMutationRequest {
 }
 
 /// Reference metadata to add to an implementation artifact.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReferenceAddition {
     pub locator: String,
+    #[serde(default, rename = "type")]
     pub reference_type: Option<String>,
+    #[serde(default)]
     pub optional: Option<bool>,
 }
 
@@ -306,6 +451,33 @@ impl ReferenceAddition {
     }
 }
 
+/// Repoints front-matter entries that use `from` to `to`. An entry with a heading fragment
+/// (`{from}#concept`) keeps its fragment.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocatorReplacement {
+    pub from: String,
+    pub to: String,
+}
+
+impl LocatorReplacement {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// The rewritten locator when `value` refers to `from`.
+    fn apply(&self, value: &str) -> Option<String> {
+        let rest = value.strip_prefix(self.from.as_str())?;
+        if rest.is_empty() || rest.starts_with('#') {
+            Some(format!("{}{}", self.to, rest))
+        } else {
+            None
+        }
+    }
+}
+
 /// Result of a metadata mutation attempt.
 #[derive(Debug)]
 pub struct MetadataMutationResult {
@@ -382,11 +554,69 @@ impl<'a> MetadataHandler for ImplementationMetadataHandler<'a> {
     }
 }
 
+struct LocatorReplacementHandler<'a> {
+    replacements: &'a [LocatorReplacement],
+}
+
+impl<'a> LocatorReplacementHandler<'a> {
+    fn new(replacements: &'a [LocatorReplacement]) -> Self {
+        Self { replacements }
+    }
+
+    fn rewrite(&self, value: &mut Value, ctx: &MetadataContext) -> Result<bool, SpecmanError> {
+        let slot = match value {
+            Value::Mapping(map) => match map.get_mut(Value::String("ref".into())) {
+                Some(slot) => slot,
+                None => return Ok(false),
+            },
+            other => other,
+        };
+        let Some(current) = slot.as_str() else {
+            return Ok(false);
+        };
+        let Some(replaced) = self
+            .replacements
+            .iter()
+            .find_map(|replacement| replacement.apply(current))
+        else {
+            return Ok(false);
+        };
+        validate_workspace_reference(&replaced, ctx.parent_dir, ctx.workspace)?;
+        *slot = Value::String(replaced);
+        Ok(true)
+    }
+}
+
+impl<'a> MetadataHandler for LocatorReplacementHandler<'a> {
+    fn apply(
+        &self,
+        _artifact: &ArtifactId,
+        mapping: &mut Mapping,
+        ctx: &MetadataContext,
+    ) -> Result<bool, SpecmanError> {
+        let mut mutated = false;
+        for field in ["dependencies", "references"] {
+            if let Some(Value::Sequence(entries)) = mapping.get_mut(Value::String(field.into())) {
+                for entry in entries {
+                    mutated |= self.rewrite(entry, ctx)?;
+                }
+            }
+        }
+        for field in ["spec", "target"] {
+            if let Some(value) = mapping.get_mut(Value::String(field.into())) {
+                mutated |= self.rewrite(value, ctx)?;
+            }
+        }
+        Ok(mutated)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::adapter::DataModelAdapter;
     use crate::dependency_tree::{ArtifactKind, DependencyTree};
+    use crate::error::ErrorCode;
     use crate::workspace::FilesystemWorkspaceLocator;
     use std::sync::Mutex;
     use tempfile::tempdir;
@@ -418,6 +648,7 @@ mod tests {
             path: spec_path.canonicalize().unwrap(),
             add_dependencies: vec!["../data-model/spec.md".into()],
             add_references: Vec::new(),
+            replace_locators: Vec::new(),
             persist: true,
         };
 
@@ -434,6 +665,20 @@ mod tests {
         let contents = fs::read_to_string(spec_path).unwrap();
         assert!(contents.contains("dependencies"));
         assert!(contents.contains("../data-model/spec.md"));
+
+        let envelope = &result.persisted.as_ref().unwrap().envelope;
+        assert_eq!(envelope.capability, METADATA_MUTATE);
+        assert_eq!(envelope.updated, vec![PathBuf::from("spec/core/spec.md")]);
+        assert_eq!(envelope.inputs["path"], "spec/core/spec.md");
+        assert_eq!(
+            envelope.inputs["add_dependencies"],
+            serde_json::json!(["../data-model/spec.md"])
+        );
+        let workspace = FilesystemWorkspaceLocator::new(&root).workspace().unwrap();
+        assert_eq!(
+            audit::read_envelopes(&workspace).unwrap(),
+            vec![envelope.clone()]
+        );
     }
 
     #[test]
@@ -458,6 +703,7 @@ mod tests {
             path: spec_path.canonicalize().unwrap(),
             add_dependencies: vec!["spec://data-model".into()],
             add_references: Vec::new(),
+            replace_locators: Vec::new(),
             persist: false,
         };
 
@@ -497,6 +743,7 @@ mod tests {
             path: impl_path.canonicalize().unwrap(),
             add_dependencies: Vec::new(),
             add_references: vec![ReferenceAddition::new("../../spec/spec-beta/spec.md")],
+            replace_locators: Vec::new(),
             persist: true,
         };
 
@@ -506,6 +753,7 @@ mod tests {
             path: impl_path.canonicalize().unwrap(),
             add_dependencies: Vec::new(),
             add_references: vec![ReferenceAddition::new("../../spec/spec-beta/spec.md")],
+            replace_locators: Vec::new(),
             persist: true,
         };
 
@@ -539,6 +787,7 @@ mod tests {
             path: spec_path.canonicalize().unwrap(),
             add_dependencies: vec!["http://example.com/spec.md".into()],
             add_references: Vec::new(),
+            replace_locators: Vec::new(),
             persist: false,
         };
 
@@ -546,6 +795,99 @@ mod tests {
         assert!(matches!(err, SpecmanError::Dependency(_)));
     }
 
+    #[test]
+    fn mutate_rejects_scratch_target_outside_work_type() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman/scratchpad/fix-core")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("impl/core-rust")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\n---\n# Core",
+        )
+        .unwrap();
+        fs::write(
+            root.join("impl/core-rust/impl.md"),
+            "---\nspec: ../../spec/core/spec.md\n---\n# Core in Rust",
+        )
+        .unwrap();
+        let scratch_path = root.join(".specman/scratchpad/fix-core/scratch.md");
+        fs::write(
+            &scratch_path,
+            "---\ntarget: impl/core-rust/impl.md\nwork_type:\n  fix: {}\n---\n# Fix core",
+        )
+        .unwrap();
+
+        let mutator = MetadataMutator::new(FilesystemWorkspaceLocator::new(&root));
+        let request = MetadataMutationRequest {
+            path: scratch_path.canonicalize().unwrap(),
+            replace_locators: vec![LocatorReplacement::new(
+                "impl/core-rust/impl.md",
+                "spec://core",
+            )],
+            persist: true,
+            ..Default::default()
+        };
+
+        let err = mutator
+            .mutate(request)
+            .expect_err("fix pads target implementations");
+        assert_eq!(err.code(), ErrorCode::ScratchTarget);
+        assert!(
+            err.to_string().contains("must target an implementation"),
+            "{err}"
+        );
+        assert!(
+            fs::read_to_string(&scratch_path)
+                .unwrap()
+                .contains("target: impl/core-rust/impl.md")
+        );
+    }
+
+    #[test]
+    fn mutate_rejects_dependency_query_handles() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::create_dir_all(root.join("spec/data-model")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\n---\n# Core",
+        )
+        .unwrap();
+        fs::write(
+            root.join("spec/data-model/spec.md"),
+            "---\nname: data-model\n---\n# Data Model",
+        )
+        .unwrap();
+
+        let mutator = MetadataMutator::new(FilesystemWorkspaceLocator::new(&root));
+        let mut through_query = MetadataMutationRequest::new("spec://core/dependencies");
+        through_query
+            .add_dependencies
+            .push("spec://data-model".into());
+        let err = mutator
+            .mutate(through_query)
+            .expect_err("query target rejected");
+        assert!(matches!(err, SpecmanError::ReadOnlyLocator(_)));
+
+        let mut query_dependency = MetadataMutationRequest::new("spec://core");
+        query_dependency
+            .add_dependencies
+            .push("spec://data-model/dependencies".into());
+        let err = mutator
+            .mutate(query_dependency)
+            .expect_err("query dependency rejected");
+        assert!(matches!(err, SpecmanError::ReadOnlyLocator(_)));
+
+        let mut by_handle = MetadataMutationRequest::new("spec://core");
+        by_handle.add_dependencies.push("spec://data-model".into());
+        let result = mutator.mutate(by_handle).expect("handle target accepted");
+        assert_eq!(result.artifact.name, "core");
+    }
+
     #[test]
     fn mutate_notifies_adapter_on_persist() {
         let temp = tempdir().unwrap();
@@ -591,6 +933,7 @@ mod tests {
             path: base.canonicalize().unwrap(),
             add_dependencies: vec!["../extra/spec.md".into()],
             add_references: Vec::new(),
+            replace_locators: Vec::new(),
             persist: true,
         };
 
@@ -600,4 +943,57 @@ mod tests {
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].name, "base");
     }
+
+    #[test]
+    fn mutate_waits_on_artifact_lock() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::write(
+            root.join("spec/core/spec.md"),
+            "---\nname: core\n---\n# Core",
+        )
+        .unwrap();
+
+        let locator = FilesystemWorkspaceLocator::new(&root);
+        let workspace = locator.workspace().unwrap();
+        let held = WorkspaceLockManager::new(&workspace)
+            .acquire(&ArtifactId {
+                kind: ArtifactKind::Specification,
+                name: "core".into(),
+            })
+            .unwrap();
+
+        let mutator = MetadataMutator::new(locator).lock_options(LockOptions::fail_fast());
+        let mut request = MetadataMutationRequest::new("spec://core").persist(true);
+        request.add_dependencies.push("../other/spec.md".into());
+        let err = mutator.mutate(request).expect_err("locked artifact");
+        assert!(matches!(err, SpecmanError::Lock(_)));
+
+        let mut preview = MetadataMutationRequest::new("spec://core").persist(false);
+        preview.add_dependencies.push("spec://core".into());
+        mutator.mutate(preview).expect("previews do not lock");
+
+        drop(held);
+        let mut request = MetadataMutationRequest::new("spec://core").persist(true);
+        request.add_dependencies.push("spec://core".into());
+        mutator.mutate(request).expect("lock released");
+    }
+
+    #[test]
+    fn persisting_outside_an_artifact_folder_is_rejected() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("docs/notes.md"), "---\nname: notes\n---\n# Notes").unwrap();
+
+        let mutator = MetadataMutator::new(FilesystemWorkspaceLocator::new(&root));
+        let mut request = MetadataMutationRequest::new(root.join("docs/notes.md")).persist(true);
+        request.add_dependencies.push("../spec/core/spec.md".into());
+        let err = mutator.mutate(request).expect_err("no lock key");
+        assert!(matches!(err, SpecmanError::Workspace(_)), "{err}");
+        assert!(!root.join(".specman/locks/spec-docs.lock").exists());
+    }
 }
@@ -2,10 +2,16 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use serde_json::json;
+
 use crate::adapter::DataModelAdapter;
+use crate::audit::{self, AuditLog, OperationEnvelope};
+use crate::capability::{LIFECYCLE_DELETE, LIFECYCLE_PERSIST};
 use crate::dependency_tree::{ArtifactId, ArtifactKind, DependencyInventory, DependencyTree};
-use crate::error::SpecmanError;
+use crate::error::{CodedError, ErrorCode, SpecmanError};
 use crate::front_matter::split_front_matter;
+use crate::locking::{LockOptions, WorkspaceLockManager};
+use crate::preview::{FileAction, FileChange, MutationPreview};
 use crate::template::{RenderedTemplate, TemplateProvenance};
 use crate::workspace::{WorkspaceLocator, WorkspacePaths};
 
@@ -15,6 +21,10 @@ pub struct PersistedArtifact {
     pub artifact: ArtifactId,
     pub path: PathBuf,
     pub workspace: WorkspacePaths,
+    /// Audit record appended to `.specman/audit/operations.ndjson` for this write.
+    pub envelope: OperationEnvelope,
+    /// Why `envelope` could not be appended to the audit log. The write itself succeeded.
+    pub audit_error: Option<String>,
 }
 
 /// Result of removing an artifact directory from the workspace filesystem.
@@ -23,6 +33,10 @@ pub struct RemovedArtifact {
     pub artifact: ArtifactId,
     pub directory: PathBuf,
     pub workspace: WorkspacePaths,
+    /// Audit record appended to `.specman/audit/operations.ndjson` for this removal.
+    pub envelope: OperationEnvelope,
+    /// Why `envelope` could not be appended to the audit log. The removal itself succeeded.
+    pub audit_error: Option<String>,
 }
 
 /// Writes rendered templates into canonical workspace locations.
@@ -30,6 +44,7 @@ pub struct WorkspacePersistence<L: WorkspaceLocator> {
     locator: L,
     dependency_inventory: Option<Arc<dyn DependencyInventory>>,
     data_adapter: Option<Arc<dyn DataModelAdapter>>,
+    lock_options: LockOptions,
 }
 
 impl<L: WorkspaceLocator> WorkspacePersistence<L> {
@@ -38,6 +53,7 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
             locator,
             dependency_inventory: None,
             data_adapter: None,
+            lock_options: LockOptions::default(),
         }
     }
 
@@ -46,6 +62,7 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
             locator,
             dependency_inventory: Some(dependency_inventory),
             data_adapter: None,
+            lock_options: LockOptions::default(),
         }
     }
 
@@ -54,6 +71,7 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
             locator,
             dependency_inventory: None,
             data_adapter: Some(data_adapter),
+            lock_options: LockOptions::default(),
         }
     }
 
@@ -66,9 +84,16 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
             locator,
             dependency_inventory: Some(dependency_inventory),
             data_adapter: Some(data_adapter),
+            lock_options: LockOptions::default(),
         }
     }
 
+    /// Overrides how long writes wait for, and when they reclaim, per-artifact locks.
+    pub fn lock_options(mut self, options: LockOptions) -> Self {
+        self.lock_options = options;
+        self
+    }
+
     /// Persists the rendered template and registers the accompanying dependency tree
     /// through the configured data model adapter when present.
     pub fn persist_with_dependency_tree(
@@ -92,21 +117,42 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
         ensure_safe_name(&artifact.name)?;
 
         let workspace = self.locator.workspace()?;
+        let _lock =
+            WorkspaceLockManager::with_options(&workspace, self.lock_options).acquire(artifact)?;
         let target_path = resolve_target_path(artifact, &workspace)?;
-        let output = if let Some(provenance) = &rendered.provenance {
-            inject_provenance(&rendered.body, provenance)?
-        } else {
-            rendered.body.clone()
-        };
+        let envelope = OperationEnvelope::begin(
+            LIFECYCLE_PERSIST,
+            json!({
+                "artifact": artifact,
+                "template": rendered
+                    .provenance
+                    .as_ref()
+                    .map(|provenance| audit::sanitize_locator(&provenance.locator)),
+                "body_bytes": rendered.body.len(),
+            }),
+        );
+        let existed = target_path.exists();
+        let output = render_output(rendered)?;
+        let audit_log = AuditLog::open(&workspace)?;
         write_body(&target_path, &output)?;
         if let Some(inventory) = &self.dependency_inventory {
             inventory.invalidate();
         }
 
+        let envelope = if existed {
+            envelope.updated(&workspace, &target_path)
+        } else {
+            envelope.created(&workspace, &target_path)
+        }
+        .complete();
+        let audit_error = audit_log.record(&envelope);
+
         Ok(PersistedArtifact {
             artifact: artifact.clone(),
             path: target_path,
             workspace,
+            envelope,
+            audit_error,
         })
     }
 
@@ -114,34 +160,94 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
     pub fn remove(&self, artifact: &ArtifactId) -> Result<RemovedArtifact, SpecmanError> {
         ensure_safe_name(&artifact.name)?;
         let workspace = self.locator.workspace()?;
-        let target_file = resolve_target_path(artifact, &workspace)?;
-        let directory = target_file.parent().ok_or_else(|| {
-            SpecmanError::Workspace(format!(
-                "unable to compute artifact directory for {}",
-                artifact.name
-            ))
-        })?;
-
-        if !directory.exists() {
-            return Err(SpecmanError::Workspace(format!(
-                "artifact directory does not exist: {}",
-                directory.display()
-            )));
-        }
+        let _lock =
+            WorkspaceLockManager::with_options(&workspace, self.lock_options).acquire(artifact)?;
+        let directory = existing_artifact_dir(artifact, &workspace)?;
+        let directory = directory.as_path();
 
+        let envelope = OperationEnvelope::begin(LIFECYCLE_DELETE, json!({ "artifact": artifact }));
+        let audit_log = AuditLog::open(&workspace)?;
         fs::remove_dir_all(directory)?;
         if let Some(inventory) = &self.dependency_inventory {
             inventory.invalidate();
         }
         self.invalidate_tree_in_adapter(artifact)?;
 
+        let envelope = envelope.deleted(&workspace, directory).complete();
+        let audit_error = audit_log.record(&envelope);
+
         Ok(RemovedArtifact {
             artifact: artifact.clone(),
             directory: directory.to_path_buf(),
             workspace,
+            envelope,
+            audit_error,
+        })
+    }
+
+    /// Dry-run variant of [`Self::persist`]: reports the file that would be written with a
+    /// unified diff against its current contents, plus the dependency tree the written
+    /// document would have when an inventory is attached, without touching the workspace.
+    pub fn persist_dry_run(
+        &self,
+        artifact: &ArtifactId,
+        rendered: &RenderedTemplate,
+    ) -> Result<MutationPreview, SpecmanError> {
+        ensure_rendered_tokens_resolved(&rendered.body)?;
+        ensure_safe_name(&artifact.name)?;
+
+        let workspace = self.locator.workspace()?;
+        let target_path = resolve_target_path(artifact, &workspace)?;
+        let output = render_output(rendered)?;
+        let current = if target_path.is_file() {
+            Some(fs::read_to_string(&target_path)?)
+        } else {
+            None
+        };
+        let change = FileChange::new(
+            workspace_relative(&workspace, &target_path),
+            current.as_deref(),
+            Some(&output),
+        );
+        let preview = MutationPreview::new(artifact.clone(), vec![change]);
+        let tree = match &self.dependency_inventory {
+            Some(inventory) => inventory.draft_tree(&target_path, &output)?,
+            None => None,
+        };
+        Ok(match tree {
+            Some(tree) => preview.with_dependencies(tree),
+            None => preview,
         })
     }
 
+    /// Dry-run variant of [`Self::remove`]: lists every file under the artifact directory
+    /// that would be deleted, without touching the workspace.
+    pub fn remove_dry_run(&self, artifact: &ArtifactId) -> Result<MutationPreview, SpecmanError> {
+        ensure_safe_name(&artifact.name)?;
+        let workspace = self.locator.workspace()?;
+        let directory = existing_artifact_dir(artifact, &workspace)?;
+
+        let mut files = Vec::new();
+        collect_files(&directory, &mut files)?;
+        files.sort();
+        let changes = files
+            .into_iter()
+            .map(|path| {
+                let bytes = fs::read(&path)?;
+                let relative = workspace_relative(&workspace, &path);
+                Ok(match String::from_utf8(bytes) {
+                    Ok(text) => FileChange::new(relative, Some(&text), None),
+                    Err(_) => FileChange {
+                        diff: format!("Binary file {} deleted\n", relative.display()),
+                        path: relative,
+                        action: FileAction::Delete,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, SpecmanError>>()?;
+        Ok(MutationPreview::new(artifact.clone(), changes))
+    }
+
     /// Saves the provided dependency tree via the configured data-model adapter.
     pub fn save_dependency_tree(
         &self,
@@ -175,12 +281,24 @@ impl<L: WorkspaceLocator> WorkspacePersistence<L> {
 /// Trait abstraction for components capable of removing artifact directories.
 pub trait ArtifactRemovalStore: Send + Sync {
     fn remove_artifact(&self, artifact: &ArtifactId) -> Result<RemovedArtifact, SpecmanError>;
+    /// Reports what [`Self::remove_artifact`] would delete without deleting anything. Stores
+    /// that cannot predict their removals report dry runs as unsupported.
+    fn preview_removal(&self, artifact: &ArtifactId) -> Result<MutationPreview, SpecmanError> {
+        Err(SpecmanError::Workspace(format!(
+            "this artifact store cannot preview removing {}",
+            artifact.name
+        )))
+    }
 }
 
 impl<L: WorkspaceLocator> ArtifactRemovalStore for WorkspacePersistence<L> {
     fn remove_artifact(&self, artifact: &ArtifactId) -> Result<RemovedArtifact, SpecmanError> {
         self.remove(artifact)
     }
+
+    fn preview_removal(&self, artifact: &ArtifactId) -> Result<MutationPreview, SpecmanError> {
+        self.remove_dry_run(artifact)
+    }
 }
 
 impl<S> ArtifactRemovalStore for Arc<S>
@@ -190,6 +308,10 @@ where
     fn remove_artifact(&self, artifact: &ArtifactId) -> Result<RemovedArtifact, SpecmanError> {
         (**self).remove_artifact(artifact)
     }
+
+    fn preview_removal(&self, artifact: &ArtifactId) -> Result<MutationPreview, SpecmanError> {
+        (**self).preview_removal(artifact)
+    }
 }
 
 fn resolve_target_path(
@@ -206,6 +328,53 @@ fn resolve_target_path(
     Ok(folder.join(file_name))
 }
 
+fn existing_artifact_dir(
+    artifact: &ArtifactId,
+    workspace: &WorkspacePaths,
+) -> Result<PathBuf, SpecmanError> {
+    let target_file = resolve_target_path(artifact, workspace)?;
+    let directory = target_file.parent().ok_or_else(|| {
+        SpecmanError::Workspace(format!(
+            "unable to compute artifact directory for {}",
+            artifact.name
+        ))
+    })?;
+
+    if !directory.exists() {
+        return Err(SpecmanError::Workspace(format!(
+            "artifact directory does not exist: {}",
+            directory.display()
+        )));
+    }
+    Ok(directory.to_path_buf())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), SpecmanError> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn workspace_relative(workspace: &WorkspacePaths, path: &Path) -> PathBuf {
+    path.strip_prefix(workspace.root())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn render_output(rendered: &RenderedTemplate) -> Result<String, SpecmanError> {
+    match &rendered.provenance {
+        Some(provenance) => inject_provenance(&rendered.body, provenance),
+        None => Ok(rendered.body.clone()),
+    }
+}
+
 fn write_body(path: &Path, body: &str) -> Result<(), SpecmanError> {
     if let Some(dir) = path.parent() {
         fs::create_dir_all(dir)?;
@@ -253,9 +422,11 @@ fn inject_provenance(body: &str, provenance: &TemplateProvenance) -> Result<Stri
 
 fn ensure_rendered_tokens_resolved(body: &str) -> Result<(), SpecmanError> {
     if body.contains("{{") {
-        return Err(SpecmanError::Template(
-            "rendered output still contains template tokens".into(),
-        ));
+        return Err(CodedError::new(
+            ErrorCode::TemplateMissingToken,
+            "rendered output still contains template tokens",
+        )
+        .into());
     }
     Ok(())
 }
@@ -409,7 +580,26 @@ mod tests {
         let rendered = rendered("value: {{missing}}");
 
         let err = persistence.persist(&target, &rendered).unwrap_err();
-        assert!(matches!(err, SpecmanError::Template(_)));
+        assert_eq!(err.code(), ErrorCode::TemplateMissingToken);
+    }
+
+    #[test]
+    fn persist_checks_the_audit_log_before_writing() {
+        let (_temp, root, persistence) = setup_workspace();
+        // A directory where the log file belongs cannot be opened for appending.
+        fs::create_dir_all(root.join(".specman/audit/operations.ndjson")).unwrap();
+        let target = artifact(ArtifactKind::Specification, "feature-one");
+
+        persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .expect_err("audit log cannot be opened");
+        assert!(!root.join("spec/feature-one/spec.md").exists());
+
+        fs::remove_dir(root.join(".specman/audit/operations.ndjson")).unwrap();
+        let persisted = persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .unwrap();
+        assert_eq!(persisted.audit_error, None);
     }
 
     #[test]
@@ -508,4 +698,123 @@ mod tests {
         assert_eq!(removed.artifact, target);
         assert_eq!(adapter.invalidated(), vec![target.clone()]);
     }
+
+    #[test]
+    fn persist_and_remove_append_operation_envelopes() {
+        let (_temp, root, persistence) = setup_workspace();
+        let target = artifact(ArtifactKind::Specification, "feature-one");
+
+        let created = persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .expect("create spec");
+        let updated = persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nrevised"))
+            .expect("update spec");
+        let removed = persistence.remove(&target).expect("remove spec");
+
+        assert_eq!(created.envelope.capability, LIFECYCLE_PERSIST);
+        assert_eq!(
+            created.envelope.created,
+            vec![PathBuf::from("spec/feature-one/spec.md")]
+        );
+        assert_eq!(
+            updated.envelope.updated,
+            vec![PathBuf::from("spec/feature-one/spec.md")]
+        );
+        assert_eq!(removed.envelope.capability, LIFECYCLE_DELETE);
+        assert_eq!(
+            removed.envelope.deleted,
+            vec![PathBuf::from("spec/feature-one")]
+        );
+        assert_eq!(created.envelope.inputs["artifact"]["name"], "feature-one");
+        assert!(created.envelope.inputs.get("body").is_none());
+
+        let workspace = WorkspacePaths::new(root.clone(), root.join(".specman"));
+        let logged = audit::read_envelopes(&workspace).unwrap();
+        assert_eq!(
+            logged,
+            vec![created.envelope, updated.envelope, removed.envelope]
+        );
+    }
+
+    #[test]
+    fn persist_and_remove_respect_artifact_locks() {
+        let (_temp, root, persistence) = setup_workspace();
+        let persistence = persistence.lock_options(LockOptions::fail_fast());
+        let target = artifact(ArtifactKind::Specification, "feature-one");
+        let workspace = WorkspacePaths::new(root.clone(), root.join(".specman"));
+        let held = WorkspaceLockManager::new(&workspace)
+            .acquire(&target)
+            .unwrap();
+
+        let err = persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .expect_err("persist blocked by lock");
+        assert!(matches!(err, SpecmanError::Lock(_)));
+        assert!(!root.join("spec/feature-one/spec.md").exists());
+
+        drop(held);
+        persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .expect("persist after release");
+        persistence.remove(&target).expect("remove after release");
+        assert!(!held_lock_path(&workspace, &target).exists());
+    }
+
+    #[test]
+    fn dry_runs_report_diffs_without_touching_workspace() {
+        let (_temp, root, persistence) = setup_workspace();
+        let target = artifact(ArtifactKind::Specification, "feature-one");
+        let spec_path = root.join("spec/feature-one/spec.md");
+
+        let preview = persistence
+            .persist_dry_run(&target, &rendered("---\nname: feature\n---\nbody"))
+            .expect("preview create");
+        assert!(preview.dry_run);
+        assert_eq!(preview.changes.len(), 1);
+        assert_eq!(preview.changes[0].action, FileAction::Create);
+        assert_eq!(
+            preview.changes[0].path,
+            PathBuf::from("spec/feature-one/spec.md")
+        );
+        assert!(preview.changes[0].diff.contains("+body\n"));
+        assert!(!spec_path.exists());
+
+        persistence
+            .persist(&target, &rendered("---\nname: feature\n---\nbody"))
+            .unwrap();
+        let preview = persistence
+            .persist_dry_run(&target, &rendered("---\nname: feature\n---\nrevised"))
+            .expect("preview update");
+        assert_eq!(preview.changes[0].action, FileAction::Update);
+        assert!(preview.changes[0].diff.contains("-body\n+revised\n"));
+        assert!(fs::read_to_string(&spec_path).unwrap().ends_with("body"));
+
+        fs::write(root.join("spec/feature-one/notes.md"), "extra\n").unwrap();
+        let preview = persistence
+            .remove_dry_run(&target)
+            .expect("preview removal");
+        let paths: Vec<_> = preview.changes.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("spec/feature-one/notes.md"),
+                PathBuf::from("spec/feature-one/spec.md"),
+            ]
+        );
+        assert!(
+            preview
+                .changes
+                .iter()
+                .all(|change| change.action == FileAction::Delete)
+        );
+        assert!(spec_path.exists());
+
+        let workspace = WorkspacePaths::new(root.clone(), root.join(".specman"));
+        assert_eq!(audit::read_envelopes(&workspace).unwrap().len(), 1);
+    }
+
+    fn held_lock_path(workspace: &WorkspacePaths, target: &ArtifactId) -> PathBuf {
+        WorkspaceLockManager::new(workspace).lock_path(target)
+    }
 }
@@ -0,0 +1,329 @@
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::dependency_tree::{ArtifactId, DependencyTree};
+
+const CONTEXT_LINES: usize = 3;
+/// Largest LCS table (in cells) a diff may allocate, about 32 MiB; see `diff_lines`.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// How a dry-run operation would touch a file.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// A single file a mutating operation would write or delete, with a unified diff of the
+/// change. Paths are workspace-relative.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub action: FileAction,
+    pub diff: String,
+}
+
+impl FileChange {
+    /// Builds the change for `path` going from `before` (absent when the file does not
+    /// exist) to `after` (absent when the file would be deleted).
+    pub fn new(path: PathBuf, before: Option<&str>, after: Option<&str>) -> Self {
+        let label = path.display().to_string();
+        let action = match (before, after) {
+            (None, _) => FileAction::Create,
+            (Some(_), None) => FileAction::Delete,
+            (Some(_), Some(_)) => FileAction::Update,
+        };
+        let old_label = if before.is_some() {
+            format!("a/{label}")
+        } else {
+            "/dev/null".to_string()
+        };
+        let new_label = if after.is_some() {
+            format!("b/{label}")
+        } else {
+            "/dev/null".to_string()
+        };
+        let diff = unified_diff(
+            &old_label,
+            &new_label,
+            before.unwrap_or_default(),
+            after.unwrap_or_default(),
+        );
+        Self { path, action, diff }
+    }
+}
+
+/// Result of a dry run: the files that would change and the dependency tree the guard
+/// rails consulted. Nothing on disk is modified while producing it.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MutationPreview {
+    pub artifact: ArtifactId,
+    /// Always `true`; lets serialized previews announce that nothing was persisted.
+    pub dry_run: bool,
+    pub changes: Vec<FileChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<DependencyTree>,
+}
+
+impl MutationPreview {
+    pub fn new(artifact: ArtifactId, changes: Vec<FileChange>) -> Self {
+        Self {
+            artifact,
+            dry_run: true,
+            changes,
+            dependencies: None,
+        }
+    }
+
+    pub fn with_dependencies(mut self, dependencies: DependencyTree) -> Self {
+        self.dependencies = Some(dependencies);
+        self
+    }
+}
+
+/// Renders a unified diff (three lines of context) between two texts. Returns an empty
+/// string when the texts are identical.
+pub fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut output = format!("--- {old_label}\n+++ {new_label}\n");
+    for hunk in hunks(&ops) {
+        let slice = &ops[hunk.start..hunk.end];
+        let old_start = slice.iter().find_map(|op| op.old_index());
+        let new_start = slice.iter().find_map(|op| op.new_index());
+        let old_count = slice.iter().filter(|op| op.old_index().is_some()).count();
+        let new_count = slice.iter().filter(|op| op.new_index().is_some()).count();
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count, hunk_anchor_old(&ops, hunk.start)),
+            hunk_range(new_start, new_count, hunk_anchor_new(&ops, hunk.start)),
+        ));
+        for op in slice {
+            let (marker, line) = match op {
+                DiffOp::Equal(index, _) => (' ', old_lines[*index]),
+                DiffOp::Delete(index) => ('-', old_lines[*index]),
+                DiffOp::Insert(index) => ('+', new_lines[*index]),
+            };
+            output.push(marker);
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+impl DiffOp {
+    fn old_index(&self) -> Option<usize> {
+        match self {
+            DiffOp::Equal(old, _) | DiffOp::Delete(old) => Some(*old),
+            DiffOp::Insert(_) => None,
+        }
+    }
+
+    fn new_index(&self) -> Option<usize> {
+        match self {
+            DiffOp::Equal(_, new) | DiffOp::Insert(new) => Some(*new),
+            DiffOp::Delete(_) => None,
+        }
+    }
+}
+
+/// Line-level edit script derived from the longest common subsequence. Unchanged leading
+/// and trailing lines are matched directly; when the differing middle would need an LCS table
+/// larger than [`MAX_LCS_CELLS`], it is reported as a wholesale replacement instead.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let (n, m) = (old.len() - suffix, new.len() - suffix);
+
+    let mut ops = Vec::with_capacity(n + m + suffix);
+    ops.extend((0..prefix).map(|index| DiffOp::Equal(index, index)));
+    if (n - prefix).saturating_mul(m - prefix) > MAX_LCS_CELLS {
+        ops.extend((prefix..n).map(DiffOp::Delete));
+        ops.extend((prefix..m).map(DiffOp::Insert));
+    } else {
+        lcs_ops(old, new, prefix, n, m, &mut ops);
+    }
+    ops.extend((0..suffix).map(|offset| DiffOp::Equal(n + offset, m + offset)));
+    ops
+}
+
+/// Appends the LCS edit script for `old[start..n]` against `new[start..m]`.
+fn lcs_ops(old: &[&str], new: &[&str], start: usize, n: usize, m: usize, ops: &mut Vec<DiffOp>) {
+    let (rows, cols) = (n - start, m - start);
+    let mut lcs = vec![vec![0usize; cols + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lcs[i][j] = if old[start + i] == new[start + j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if old[start + i] == new[start + j] {
+            ops.push(DiffOp::Equal(start + i, start + j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Delete(start + i));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(start + j));
+            j += 1;
+        }
+    }
+    ops.extend((start + i..n).map(DiffOp::Delete));
+    ops.extend((start + j..m).map(DiffOp::Insert));
+}
+
+struct Hunk {
+    start: usize,
+    end: usize,
+}
+
+/// Groups changed operations with surrounding context, merging hunks whose context overlaps.
+fn hunks(ops: &[DiffOp]) -> Vec<Hunk> {
+    let mut result: Vec<Hunk> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal(..)) {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + 1 + CONTEXT_LINES).min(ops.len());
+        match result.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => result.push(Hunk { start, end }),
+        }
+    }
+    result
+}
+
+/// Line a zero-length hunk side is anchored after (the count of lines preceding it).
+fn hunk_anchor_old(ops: &[DiffOp], start: usize) -> usize {
+    ops[..start]
+        .iter()
+        .filter(|op| op.old_index().is_some())
+        .count()
+}
+
+fn hunk_anchor_new(ops: &[DiffOp], start: usize) -> usize {
+    ops[..start]
+        .iter()
+        .filter(|op| op.new_index().is_some())
+        .count()
+}
+
+fn hunk_range(first: Option<usize>, count: usize, anchor: usize) -> String {
+    match (first, count) {
+        (Some(first), 1) => format!("{}", first + 1),
+        (Some(first), count) => format!("{},{count}", first + 1),
+        (None, _) => format!("{anchor},0"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_reports_changed_lines_with_context() {
+        let old = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\n";
+        let new = "one\ntwo\nthree\nfour\nFIVE\nsix\nseven\neight\nnine\nten\n";
+        let diff = unified_diff("a/doc.md", "b/doc.md", old, new);
+        assert_eq!(
+            diff,
+            "--- a/doc.md\n+++ b/doc.md\n\
+             @@ -2,8 +2,9 @@\n two\n three\n four\n-five\n+FIVE\n six\n seven\n eight\n nine\n+ten\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_separates_distant_hunks_and_handles_empty_sides() {
+        let old: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 19\n", "");
+        let diff = unified_diff("a/x", "b/x", &old, &new);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,5 @@"));
+        assert!(diff.contains("@@ -16,5 +16,4 @@"));
+
+        let created = unified_diff("/dev/null", "b/x", "", "alpha\nbeta\n");
+        assert!(created.contains("@@ -0,0 +1,2 @@\n+alpha\n+beta\n"));
+        let deleted = unified_diff("a/x", "/dev/null", "alpha\n", "");
+        assert!(deleted.contains("@@ -1 +0,0 @@\n-alpha\n"));
+        assert!(unified_diff("a/x", "b/x", "same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn oversized_diffs_fall_back_to_replacing_the_changed_middle() {
+        let old: Vec<String> = (0..3000).map(|n| format!("old {n}")).collect();
+        let new: Vec<String> = (0..3000).map(|n| format!("new {n}")).collect();
+        let old: Vec<&str> = std::iter::once("head")
+            .chain(old.iter().map(String::as_str))
+            .chain(std::iter::once("tail"))
+            .collect();
+        let new: Vec<&str> = std::iter::once("head")
+            .chain(new.iter().map(String::as_str))
+            .chain(std::iter::once("tail"))
+            .collect();
+
+        let ops = diff_lines(&old, &new);
+        assert_eq!(ops.len(), 6002);
+        assert_eq!(ops[0], DiffOp::Equal(0, 0));
+        assert_eq!(ops[1], DiffOp::Delete(1));
+        assert_eq!(ops[3001], DiffOp::Insert(1));
+        assert_eq!(ops[6001], DiffOp::Equal(3001, 3001));
+    }
+
+    #[test]
+    fn file_changes_classify_actions() {
+        let created = FileChange::new("spec/a/spec.md".into(), None, Some("new\n"));
+        assert_eq!(created.action, FileAction::Create);
+        assert!(
+            created
+                .diff
+                .starts_with("--- /dev/null\n+++ b/spec/a/spec.md\n")
+        );
+
+        let deleted = FileChange::new("spec/a/spec.md".into(), Some("old\n"), None);
+        assert_eq!(deleted.action, FileAction::Delete);
+        assert!(
+            deleted
+                .diff
+                .starts_with("--- a/spec/a/spec.md\n+++ /dev/null\n")
+        );
+
+        let updated = FileChange::new("spec/a/spec.md".into(), Some("old\n"), Some("new\n"));
+        assert_eq!(updated.action, FileAction::Update);
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::dependency_tree::{ArtifactId, ArtifactKind, DependencyRelation};
+
+/// Milestone reported while dependency traversals and inventory builds are running.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// An artifact document was loaded and parsed.
+    ArtifactVisited {
+        artifact: ArtifactId,
+        locator: String,
+    },
+    /// A remote document is about to be fetched.
+    FetchStarted { url: String },
+    /// A remote fetch completed, successfully or not.
+    FetchFinished { url: String, ok: bool },
+    /// A dependency edge was added to the tree being built.
+    EdgeDiscovered {
+        from: ArtifactId,
+        to: ArtifactId,
+        relation: DependencyRelation,
+    },
+}
+
+impl fmt::Display for ProgressEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressEvent::ArtifactVisited { artifact, .. } => {
+                write!(f, "visited {} {}", kind_label(artifact.kind), artifact.name)
+            }
+            ProgressEvent::FetchStarted { url } => write!(f, "fetching {url}"),
+            ProgressEvent::FetchFinished { url, ok: true } => write!(f, "fetched {url}"),
+            ProgressEvent::FetchFinished { url, ok: false } => write!(f, "failed to fetch {url}"),
+            ProgressEvent::EdgeDiscovered { from, to, .. } => {
+                write!(f, "found edge {} -> {}", from.name, to.name)
+            }
+        }
+    }
+}
+
+/// Receives [`ProgressEvent`]s from [`crate::DependencyGraphServices`]. Observers are
+/// called synchronously on the traversing thread, so implementations should stay cheap.
+pub trait ProgressObserver: Send + Sync {
+    fn on_progress(&self, event: &ProgressEvent);
+}
+
+/// Observer that discards every event; the default for graph services.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopProgress;
+
+impl ProgressObserver for NoopProgress {
+    fn on_progress(&self, _event: &ProgressEvent) {}
+}
+
+fn kind_label(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Specification => "spec",
+        ArtifactKind::Implementation => "impl",
+        ArtifactKind::ScratchPad => "scratch",
+    }
+}
@@ -0,0 +1,612 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::dependency_tree::{ArtifactKind, DependencyMapping, ResourceHandle};
+use crate::error::SpecmanError;
+use crate::scratchpad::ScratchPadProfileKind;
+use crate::template::{TemplateProvenance, TemplateTier};
+use crate::template_catalog::{TemplateCache, display_locator, read_pointer, workspace_relative};
+use crate::workspace::WorkspacePaths;
+
+const EMBEDDED_PROMPT_SPEC: &str = include_str!("../templates/prompts/spec.md");
+const EMBEDDED_PROMPT_IMPL: &str = include_str!("../templates/prompts/impl.md");
+const EMBEDDED_PROMPT_SCRATCH_FEAT: &str = include_str!("../templates/prompts/scratch-feat.md");
+const EMBEDDED_PROMPT_SCRATCH_FIX: &str = include_str!("../templates/prompts/scratch-fix.md");
+const EMBEDDED_PROMPT_SCRATCH_REF: &str = include_str!("../templates/prompts/scratch-ref.md");
+const EMBEDDED_PROMPT_SCRATCH_REVISION: &str =
+    include_str!("../templates/prompts/scratch-revision.md");
+
+/// Reminder attached to every rendered prompt, per the SpecMan MCP prompt contract.
+const HTML_COMMENT_REMINDER: &str =
+    "Keep every HTML comment directive in the template until its instruction is satisfied.";
+
+/// Stand-in for `{{context}}`, `{{dependencies}}`, and `{{arguments}}` when nothing was given.
+const NONE: &str = "(none)";
+
+/// Prompt blueprints shipped under `templates/prompts/`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+pub enum PromptKind {
+    #[serde(rename = "spec")]
+    Specification,
+    #[serde(rename = "impl")]
+    Implementation,
+    #[serde(rename = "scratch-feat")]
+    ScratchFeat,
+    #[serde(rename = "scratch-fix")]
+    ScratchFix,
+    #[serde(rename = "scratch-ref")]
+    ScratchRef,
+    #[serde(rename = "scratch-revision")]
+    ScratchRevision,
+}
+
+impl PromptKind {
+    pub const ALL: [PromptKind; 6] = [
+        PromptKind::Specification,
+        PromptKind::Implementation,
+        PromptKind::ScratchFeat,
+        PromptKind::ScratchFix,
+        PromptKind::ScratchRef,
+        PromptKind::ScratchRevision,
+    ];
+
+    /// File stem of the blueprint, also used for override file names.
+    pub fn slug(self) -> &'static str {
+        match self {
+            PromptKind::Specification => "spec",
+            PromptKind::Implementation => "impl",
+            PromptKind::ScratchFeat => "scratch-feat",
+            PromptKind::ScratchFix => "scratch-fix",
+            PromptKind::ScratchRef => "scratch-ref",
+            PromptKind::ScratchRevision => "scratch-revision",
+        }
+    }
+
+    /// Artifact template the prompt instructs the agent to copy.
+    pub fn template(self) -> &'static str {
+        match self {
+            PromptKind::Specification => "templates/spec/spec.md",
+            PromptKind::Implementation => "templates/impl/impl.md",
+            PromptKind::ScratchFix => "templates/scratch/fix.md",
+            PromptKind::ScratchFeat | PromptKind::ScratchRef | PromptKind::ScratchRevision => {
+                "templates/scratch/scratch.md"
+            }
+        }
+    }
+
+    /// Scratch pad work type declared by scratch prompts.
+    pub fn work_type(self) -> Option<ScratchPadProfileKind> {
+        match self {
+            PromptKind::Specification | PromptKind::Implementation => None,
+            PromptKind::ScratchFeat => Some(ScratchPadProfileKind::Feat),
+            PromptKind::ScratchFix => Some(ScratchPadProfileKind::Fix),
+            PromptKind::ScratchRef => Some(ScratchPadProfileKind::Ref),
+            PromptKind::ScratchRevision => Some(ScratchPadProfileKind::Revision),
+        }
+    }
+
+    /// Artifact kind a prompt target must have: specifications for spec, impl, and
+    /// revision prompts; implementations for the remaining scratch prompts.
+    pub fn target_kind(self) -> ArtifactKind {
+        match self {
+            PromptKind::Specification
+            | PromptKind::Implementation
+            | PromptKind::ScratchRevision => ArtifactKind::Specification,
+            PromptKind::ScratchFeat | PromptKind::ScratchFix | PromptKind::ScratchRef => {
+                ArtifactKind::Implementation
+            }
+        }
+    }
+
+    /// Token the prompt uses for the artifact name the agent is asked to infer.
+    fn name_token(self) -> &'static str {
+        match self {
+            PromptKind::Specification => "spec_name",
+            PromptKind::Implementation => "implementation_name",
+            _ => "scratch_name",
+        }
+    }
+
+    fn pointer_name(self) -> String {
+        format!("PROMPT_{}", self.slug().replace('-', "_").to_uppercase())
+    }
+
+    fn embedded(self) -> &'static str {
+        match self {
+            PromptKind::Specification => EMBEDDED_PROMPT_SPEC,
+            PromptKind::Implementation => EMBEDDED_PROMPT_IMPL,
+            PromptKind::ScratchFeat => EMBEDDED_PROMPT_SCRATCH_FEAT,
+            PromptKind::ScratchFix => EMBEDDED_PROMPT_SCRATCH_FIX,
+            PromptKind::ScratchRef => EMBEDDED_PROMPT_SCRATCH_REF,
+            PromptKind::ScratchRevision => EMBEDDED_PROMPT_SCRATCH_REVISION,
+        }
+    }
+}
+
+impl fmt::Display for PromptKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.slug())
+    }
+}
+
+impl FromStr for PromptKind {
+    type Err = SpecmanError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        PromptKind::ALL
+            .into_iter()
+            .find(|kind| kind.slug() == raw)
+            .ok_or_else(|| {
+                SpecmanError::Template(format!(
+                    "unknown prompt kind {raw} (expected one of {})",
+                    PromptKind::ALL.map(PromptKind::slug).join(", ")
+                ))
+            })
+    }
+}
+
+/// Inputs for rendering a prompt. Everything except `kind` is optional; tokens that
+/// cannot be filled stay in the body for the agent to infer.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PromptRequest {
+    pub kind: PromptKind,
+    /// Artifact the prompt works against; its dependency chain becomes `{{context}}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<ResourceHandle>,
+    /// Name for the artifact being created, when already decided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// User input inserted verbatim as `{{arguments}}`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub arguments: String,
+    /// Extra paths or URLs appended to `{{context}}`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+    /// Explicit token values; these win over anything derived from the workspace.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tokens: BTreeMap<String, String>,
+}
+
+impl PromptRequest {
+    pub fn new(kind: PromptKind) -> Self {
+        Self {
+            kind,
+            target: None,
+            name: None,
+            branch: None,
+            arguments: String::new(),
+            context: Vec::new(),
+            tokens: BTreeMap::new(),
+        }
+    }
+}
+
+/// Prompt body ready to hand to an agent, with the template it cites and the tokens
+/// that were left for the agent to fill in.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenderedPrompt {
+    pub kind: PromptKind,
+    pub body: String,
+    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_type: Option<ScratchPadProfileKind>,
+    pub reminder: String,
+    pub provenance: TemplateProvenance,
+    #[serde(default)]
+    pub unresolved_tokens: Vec<String>,
+}
+
+/// Resolves prompt blueprints through the same override → pointer → embedded tiers as
+/// [`crate::TemplateCatalog`]. Overrides live at `.specman/templates/prompts/{slug}.md`
+/// and pointers at `.specman/templates/PROMPT_{SLUG}`.
+pub struct PromptCatalog {
+    workspace: WorkspacePaths,
+}
+
+impl PromptCatalog {
+    pub fn new(workspace: WorkspacePaths) -> Self {
+        Self { workspace }
+    }
+
+    /// Locates the blueprint for `kind` and reports where it came from.
+    pub fn resolve(&self, kind: PromptKind) -> Result<(PathBuf, TemplateProvenance), SpecmanError> {
+        let root = self.workspace.root();
+        let override_path = self
+            .workspace
+            .dot_specman()
+            .join("templates")
+            .join("prompts")
+            .join(format!("{}.md", kind.slug()));
+        if override_path.is_file() {
+            let provenance = TemplateProvenance {
+                tier: TemplateTier::WorkspaceOverride,
+                locator: workspace_relative(root, &override_path),
+                pointer: None,
+                cache_path: None,
+                last_modified: None,
+            };
+            return Ok((override_path, provenance));
+        }
+
+        let pointer_name = kind.pointer_name();
+        if let Some(target) = read_pointer(&self.workspace, &pointer_name)? {
+            let provenance = TemplateProvenance {
+                tier: target.tier,
+                locator: target
+                    .locator
+                    .unwrap_or_else(|| workspace_relative(root, &target.path)),
+                pointer: Some(pointer_name),
+                cache_path: target.cache_path,
+                last_modified: target.last_modified,
+            };
+            return Ok((target.path, provenance));
+        }
+
+        let key = format!("prompt-{}", kind.slug());
+        let path = TemplateCache::new(&self.workspace).write_embedded(&key, kind.embedded())?;
+        let provenance = TemplateProvenance {
+            tier: TemplateTier::EmbeddedDefault,
+            locator: format!("embedded://{key}"),
+            pointer: None,
+            cache_path: Some(workspace_relative(root, &path)),
+            last_modified: None,
+        };
+        Ok((path, provenance))
+    }
+
+    /// Renders the prompt for `request`, deriving `{{context}}`, `{{dependencies}}`, and
+    /// the target path tokens from the target's dependency tree in `mapping`.
+    pub fn render(
+        &self,
+        request: &PromptRequest,
+        mapping: &dyn DependencyMapping,
+    ) -> Result<RenderedPrompt, SpecmanError> {
+        let kind = request.kind;
+        let (path, provenance) = self.resolve(kind)?;
+        let raw = fs::read_to_string(&path).map_err(|err| {
+            SpecmanError::Template(format!("failed to read prompt {}: {err}", path.display()))
+        })?;
+
+        let mut tokens = BTreeMap::new();
+        let mut context = Vec::new();
+        if let Some(target) = &request.target {
+            self.target_tokens(kind, target, mapping, &mut tokens, &mut context)?;
+        }
+        for entry in &request.context {
+            if !context.contains(entry) {
+                context.push(entry.clone());
+            }
+        }
+        // Context and arguments are always filled, so a bare prompt never leaks placeholders.
+        let list = if context.is_empty() {
+            NONE.to_string()
+        } else {
+            context
+                .iter()
+                .map(|entry| format!("- `{entry}`"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        tokens.insert("context".to_string(), list.clone());
+        tokens.insert("dependencies".to_string(), list);
+        let arguments = if request.arguments.is_empty() {
+            NONE
+        } else {
+            &request.arguments
+        };
+        tokens.insert("arguments".to_string(), arguments.to_string());
+        if let Some(name) = &request.name {
+            tokens.insert("output_name".to_string(), name.clone());
+            tokens.insert(kind.name_token().to_string(), name.clone());
+        }
+        if let Some(branch) = &request.branch {
+            tokens.insert("branch_name".to_string(), branch.clone());
+        }
+        tokens.extend(request.tokens.clone());
+
+        let body = fill_tokens(&raw, &tokens);
+        let unresolved_tokens = unresolved_tokens(&body);
+        Ok(RenderedPrompt {
+            kind,
+            body,
+            template: kind.template().to_string(),
+            work_type: kind.work_type(),
+            reminder: HTML_COMMENT_REMINDER.to_string(),
+            provenance,
+            unresolved_tokens,
+        })
+    }
+
+    fn target_tokens(
+        &self,
+        kind: PromptKind,
+        target: &ResourceHandle,
+        mapping: &dyn DependencyMapping,
+        tokens: &mut BTreeMap<String, String>,
+        context: &mut Vec<String>,
+    ) -> Result<(), SpecmanError> {
+        if target.kind() != kind.target_kind() {
+            return Err(SpecmanError::Template(format!(
+                "{kind} prompts target {} handles, not {target}",
+                match kind.target_kind() {
+                    ArtifactKind::Specification => "spec://",
+                    ArtifactKind::Implementation => "impl://",
+                    ArtifactKind::ScratchPad => "scratch://",
+                }
+            )));
+        }
+
+        let id = target.artifact_id();
+        let tree = mapping.dependency_tree(&id)?;
+        let root = self.workspace.root();
+        let target_path = display_locator(root, &target.to_path(&self.workspace).to_string_lossy());
+        match target.kind() {
+            ArtifactKind::Specification => {
+                tokens.insert("target_spec_path".to_string(), target_path.clone());
+            }
+            ArtifactKind::Implementation => {
+                tokens.insert("target_impl_path".to_string(), target_path.clone());
+                let governing = tree.upstream.iter().find(|edge| {
+                    edge.from.id == id && edge.to.id.kind == ArtifactKind::Specification
+                });
+                if let Some(locator) = governing.and_then(|edge| edge.to.metadata.get("locator")) {
+                    tokens.insert(
+                        "target_spec_path".to_string(),
+                        display_locator(root, locator),
+                    );
+                }
+            }
+            ArtifactKind::ScratchPad => {}
+        }
+
+        context.push(target_path);
+        for summary in tree.upstream_chain() {
+            if let Some(locator) = summary.metadata.get("locator") {
+                let entry = display_locator(root, locator);
+                if !context.contains(&entry) {
+                    context.push(entry);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces every known `{{token}}` in one pass over `raw`. Substituted values are never
+/// scanned again, so a value that itself contains `{{...}}` is inserted verbatim.
+fn fill_tokens(raw: &str, tokens: &BTreeMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after
+            .find("}}")
+            .and_then(|end| tokens.get(&after[..end]).map(|value| (end, value)))
+        {
+            Some((end, value)) => {
+                rendered.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Collects `{{token}}` placeholders still present in `body`, sorted and deduplicated.
+fn unresolved_tokens(body: &str) -> Vec<String> {
+    let mut found = BTreeSet::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = &after[..end];
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        {
+            found.insert(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    found.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_tree::FilesystemDependencyMapper;
+    use crate::workspace::FilesystemWorkspaceLocator;
+    use std::path::Path;
+
+    #[test]
+    fn resolves_embedded_prompts_and_caches_them() {
+        let (_tempdir, workspace) = workspace_fixture();
+        let catalog = PromptCatalog::new(workspace.clone());
+        let mapping = mapper(&workspace);
+
+        for kind in PromptKind::ALL {
+            let rendered = catalog
+                .render(&PromptRequest::new(kind), &mapping)
+                .expect("embedded prompt renders");
+            assert!(matches!(
+                rendered.provenance.tier,
+                TemplateTier::EmbeddedDefault
+            ));
+            assert!(rendered.body.contains(NONE));
+            assert!(!rendered.body.contains("{{context}}"));
+            assert!(!rendered.body.contains("{{arguments}}"));
+            assert_eq!(
+                rendered.work_type.is_some(),
+                kind.slug().starts_with("scratch")
+            );
+        }
+        assert!(
+            workspace
+                .dot_specman()
+                .join("cache/templates/embedded-prompt-scratch-feat.md")
+                .is_file()
+        );
+    }
+
+    #[test]
+    fn substituted_values_are_not_rescanned_for_tokens() {
+        let (_tempdir, workspace) = workspace_fixture();
+        let catalog = PromptCatalog::new(workspace.clone());
+        let mapping = mapper(&workspace);
+        let overrides = workspace.dot_specman().join("templates/prompts");
+        fs::create_dir_all(&overrides).unwrap();
+        fs::write(
+            overrides.join(format!("{}.md", PromptKind::Specification.slug())),
+            "context: {{context}}\nask: {{arguments}}\n",
+        )
+        .unwrap();
+
+        let mut request = PromptRequest::new(PromptKind::Specification);
+        request.arguments = "keep {{context}} literal".into();
+        let rendered = catalog.render(&request, &mapping).unwrap();
+        assert_eq!(
+            rendered.body,
+            "context: (none)\nask: keep {{context}} literal\n"
+        );
+    }
+
+    #[test]
+    fn override_and_pointer_tiers_take_precedence() {
+        let (_tempdir, workspace) = workspace_fixture();
+        let catalog = PromptCatalog::new(workspace.clone());
+        let mapping = mapper(&workspace);
+        fs::write(
+            workspace.root().join("fix-prompt.md"),
+            "pointer {{arguments}}",
+        )
+        .unwrap();
+        fs::write(
+            workspace.dot_specman().join("templates/PROMPT_SCRATCH_FIX"),
+            "fix-prompt.md\n",
+        )
+        .unwrap();
+
+        let mut request = PromptRequest::new(PromptKind::ScratchFix);
+        request.arguments = "crash on save".into();
+        let rendered = catalog.render(&request, &mapping).unwrap();
+        assert!(matches!(
+            rendered.provenance.tier,
+            TemplateTier::PointerFile
+        ));
+        assert_eq!(
+            rendered.provenance.pointer.as_deref(),
+            Some("PROMPT_SCRATCH_FIX")
+        );
+        assert_eq!(rendered.body, "pointer crash on save");
+        assert_eq!(rendered.template, "templates/scratch/fix.md");
+
+        let overrides = workspace.dot_specman().join("templates/prompts");
+        fs::create_dir_all(&overrides).unwrap();
+        fs::write(overrides.join("scratch-fix.md"), "override").unwrap();
+        let rendered = catalog.render(&request, &mapping).unwrap();
+        assert!(matches!(
+            rendered.provenance.tier,
+            TemplateTier::WorkspaceOverride
+        ));
+        assert_eq!(
+            rendered.provenance.locator,
+            ".specman/templates/prompts/scratch-fix.md"
+        );
+    }
+
+    #[test]
+    fn fills_context_and_target_tokens_from_the_dependency_tree() {
+        let (_tempdir, workspace) = workspace_fixture();
+        let root = workspace.root();
+        write(
+            &root.join("spec/base/spec.md"),
+            "---\nname: base\nversion: \"1.0.0\"\ndependencies: []\n---\nBase.\n",
+        );
+        write(
+            &root.join("spec/alpha/spec.md"),
+            "---\nname: alpha\nversion: \"1.0.0\"\ndependencies:\n  - ../base/spec.md\n---\nAlpha.\n",
+        );
+        write(
+            &root.join("impl/alpha-rust/impl.md"),
+            "---\nname: alpha-rust\nversion: \"0.1.0\"\nspec: ../../spec/alpha/spec.md\nprimary_language:\n  language: rust@1.0\n---\nImpl.\n",
+        );
+        let catalog = PromptCatalog::new(workspace.clone());
+        let mapping = mapper(&workspace);
+
+        let mut request = PromptRequest::new(PromptKind::ScratchFeat);
+        request.target = Some(ResourceHandle::parse("impl://alpha-rust").unwrap().unwrap());
+        request.name = Some("alpha-export".into());
+        request.arguments = "Add an export command.".into();
+        request.context = vec!["docs/notes.md".into()];
+        request
+            .tokens
+            .insert("objectives".into(), "Ship export.".into());
+        let rendered = catalog.render(&request, &mapping).unwrap();
+
+        assert!(
+            rendered
+                .body
+                .contains("targeting implementation impl/alpha-rust/impl.md")
+        );
+        assert!(rendered.body.contains(
+            "- `impl/alpha-rust/impl.md`\n- `spec/alpha/spec.md`\n- `spec/base/spec.md`\n- `docs/notes.md`"
+        ));
+        assert!(rendered.body.contains("Add an export command."));
+        assert!(
+            rendered
+                .body
+                .contains("`.specman/scratchpad/alpha-export/scratch.md`")
+        );
+        assert!(rendered.body.contains("Summarize Ship export."));
+        assert!(rendered.body.contains("{target_name}/feat/alpha-export"));
+        assert_eq!(
+            rendered.unresolved_tokens,
+            vec!["branch_name".to_string(), "task_outline".to_string()]
+        );
+        assert_eq!(rendered.work_type, Some(ScratchPadProfileKind::Feat));
+
+        let mut mismatched = PromptRequest::new(PromptKind::Specification);
+        mismatched.target = request.target.clone();
+        let err = catalog.render(&mismatched, &mapping).unwrap_err();
+        assert!(err.to_string().contains("spec prompts target spec://"));
+    }
+
+    fn mapper(
+        workspace: &WorkspacePaths,
+    ) -> FilesystemDependencyMapper<FilesystemWorkspaceLocator> {
+        FilesystemDependencyMapper::new(FilesystemWorkspaceLocator::new(workspace.root()))
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn workspace_fixture() -> (tempfile::TempDir, WorkspacePaths) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path().to_path_buf();
+        let dot_specman = root.join(".specman");
+        fs::create_dir_all(dot_specman.join("templates")).unwrap();
+        fs::create_dir_all(root.join("spec")).unwrap();
+        fs::create_dir_all(root.join("impl")).unwrap();
+        (tempdir, WorkspacePaths::new(root, dot_specman))
+    }
+}
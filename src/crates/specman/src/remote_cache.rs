@@ -0,0 +1,326 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::dependency_tree::ContentFetcher;
+use crate::error::SpecmanError;
+use crate::template_catalog::hash_url;
+use crate::workspace::WorkspacePaths;
+
+/// How a [`CachingContentFetcher`] uses the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FetchMode {
+    /// Revalidate cached documents with their origin, serving the cached copy when the origin
+    /// is unreachable.
+    #[default]
+    Online,
+    /// Serve documents from the cache only. A document that was never cached is an error.
+    Offline,
+    /// Always revalidate with the origin and fail when it is unreachable. The cached copy is
+    /// left untouched on failure. Used to warm the cache.
+    Refresh,
+}
+
+/// Validators and provenance stored next to every cached document.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RemoteCacheMetadata {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// A document read back from the remote cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedDocument {
+    pub body: String,
+    pub metadata: RemoteCacheMetadata,
+}
+
+/// Content fetcher that mirrors HTTPS documents under `.specman/cache/remote/`. Each
+/// document is stored as `{sha256(url)}.md` with a `{sha256(url)}.json` sidecar holding the
+/// ETag and Last-Modified validators, which later fetches send back as conditional requests.
+pub struct CachingContentFetcher {
+    root: PathBuf,
+    mode: FetchMode,
+}
+
+impl CachingContentFetcher {
+    pub fn new(workspace: &WorkspacePaths, mode: FetchMode) -> Self {
+        Self {
+            root: workspace.dot_specman().join("cache").join("remote"),
+            mode,
+        }
+    }
+
+    pub fn mode(&self) -> FetchMode {
+        self.mode
+    }
+
+    /// Directory holding cached documents and their metadata.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Cached copy of `url`, if any, without touching the network.
+    pub fn cached(&self, url: &Url) -> Result<Option<CachedDocument>, SpecmanError> {
+        let (body_path, meta_path) = self.paths(url);
+        if !body_path.is_file() {
+            return Ok(None);
+        }
+        let body = fs::read_to_string(&body_path)?;
+        let metadata = if meta_path.is_file() {
+            serde_json::from_str(&fs::read_to_string(&meta_path)?).map_err(|err| {
+                SpecmanError::Serialization(format!(
+                    "invalid remote cache metadata {}: {err}",
+                    meta_path.display()
+                ))
+            })?
+        } else {
+            RemoteCacheMetadata {
+                url: url.to_string(),
+                etag: None,
+                last_modified: None,
+            }
+        };
+        Ok(Some(CachedDocument { body, metadata }))
+    }
+
+    /// Downloads `url`, sending the cached validators so an unchanged document costs a
+    /// `304 Not Modified`.
+    fn revalidate(
+        &self,
+        url: &Url,
+        cached: Option<&CachedDocument>,
+    ) -> Result<String, SpecmanError> {
+        let mut request = ureq::get(url.as_str());
+        if let Some(cached) = cached {
+            if let Some(etag) = &cached.metadata.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &cached.metadata.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = request
+            .call()
+            .map_err(|err| SpecmanError::Dependency(format!("failed to fetch {}: {}", url, err)))?;
+        if response.status() == 304
+            && let Some(cached) = cached
+        {
+            return Ok(cached.body.clone());
+        }
+        if !(200..300).contains(&response.status()) {
+            return Err(SpecmanError::Dependency(format!(
+                "received {} from {}",
+                response.status(),
+                url
+            )));
+        }
+
+        let metadata = RemoteCacheMetadata {
+            url: url.to_string(),
+            etag: response.header("ETag").map(str::to_string),
+            last_modified: response.header("Last-Modified").map(str::to_string),
+        };
+        let body = response.into_string().map_err(|err| {
+            SpecmanError::Dependency(format!("failed reading body from {}: {}", url, err))
+        })?;
+        self.store(url, &body, &metadata)?;
+        Ok(body)
+    }
+
+    fn store(
+        &self,
+        url: &Url,
+        body: &str,
+        metadata: &RemoteCacheMetadata,
+    ) -> Result<(), SpecmanError> {
+        fs::create_dir_all(&self.root)?;
+        let (body_path, meta_path) = self.paths(url);
+        let metadata = serde_json::to_string_pretty(metadata)?;
+        // Drop the old validators before the new body lands so an interrupted store leaves a
+        // body without metadata (fetched unconditionally next time) rather than a body paired
+        // with another version's ETag.
+        match fs::remove_file(&meta_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        write_atomic(&body_path, body.as_bytes())?;
+        write_atomic(&meta_path, metadata.as_bytes())?;
+        Ok(())
+    }
+
+    fn paths(&self, url: &Url) -> (PathBuf, PathBuf) {
+        let key = hash_url(url);
+        (
+            self.root.join(format!("{key}.md")),
+            self.root.join(format!("{key}.json")),
+        )
+    }
+}
+
+/// Writes `contents` to a uniquely named sibling of `path` and renames it into place, so
+/// concurrent readers never observe a partially written file.
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), SpecmanError> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(
+        ".{file_name}.{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(err) = fs::write(&tmp_path, contents) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err.into());
+    }
+    fs::rename(&tmp_path, path).map_err(|err| {
+        let _ = fs::remove_file(&tmp_path);
+        err.into()
+    })
+}
+
+impl ContentFetcher for CachingContentFetcher {
+    fn fetch(&self, url: &Url) -> Result<String, SpecmanError> {
+        let cached = self.cached(url)?;
+        match self.mode {
+            FetchMode::Offline => cached.map(|document| document.body).ok_or_else(|| {
+                SpecmanError::Dependency(format!(
+                    "{} is not in the remote cache ({}) and network access is disabled",
+                    url,
+                    self.root.display()
+                ))
+            }),
+            FetchMode::Refresh => self.revalidate(url, cached.as_ref()),
+            FetchMode::Online => match self.revalidate(url, cached.as_ref()) {
+                Ok(body) => Ok(body),
+                Err(err) => cached.map(|document| document.body).ok_or(err),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Serves one canned response per connection and records each request's headers.
+    fn serve(responses: Vec<&'static str>) -> (Url, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/spec.md",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                recorded.lock().unwrap().push(request.to_lowercase());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn workspace() -> (tempfile::TempDir, WorkspacePaths) {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join(".specman")).unwrap();
+        let paths = FilesystemWorkspaceLocator::new(temp.path())
+            .workspace()
+            .unwrap();
+        (temp, paths)
+    }
+
+    #[test]
+    fn caches_documents_and_revalidates_with_their_etag() {
+        let (_temp, workspace) = workspace();
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 7\r\nConnection: close\r\n\r\n# Spec\n",
+            "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let fetcher = CachingContentFetcher::new(&workspace, FetchMode::Online);
+
+        assert_eq!(fetcher.fetch(&url).unwrap(), "# Spec\n");
+        let cached = fetcher.cached(&url).unwrap().unwrap();
+        assert_eq!(cached.metadata.etag.as_deref(), Some("\"v1\""));
+        assert!(fetcher.root().ends_with(".specman/cache/remote"));
+        let mut entries: Vec<String> = fs::read_dir(fetcher.root())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        let key = hash_url(&url);
+        assert_eq!(entries, [format!("{key}.json"), format!("{key}.md")]);
+
+        assert_eq!(fetcher.fetch(&url).unwrap(), "# Spec\n");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn offline_and_unreachable_fetches_fall_back_to_the_cache() {
+        let (_temp, workspace) = workspace();
+        // Nothing listens on this port once the listener is dropped.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = Url::parse(&format!("http://127.0.0.1:{port}/spec.md")).unwrap();
+
+        let offline = CachingContentFetcher::new(&workspace, FetchMode::Offline);
+        let err = offline.fetch(&url).unwrap_err();
+        assert!(err.to_string().contains("network access is disabled"));
+
+        offline
+            .store(
+                &url,
+                "# Cached\n",
+                &RemoteCacheMetadata {
+                    url: url.to_string(),
+                    etag: None,
+                    last_modified: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(offline.fetch(&url).unwrap(), "# Cached\n");
+
+        let online = CachingContentFetcher::new(&workspace, FetchMode::Online);
+        assert_eq!(online.fetch(&url).unwrap(), "# Cached\n");
+
+        let refresh = CachingContentFetcher::new(&workspace, FetchMode::Refresh);
+        assert!(matches!(
+            refresh.fetch(&url),
+            Err(SpecmanError::Dependency(_))
+        ));
+        assert_eq!(offline.fetch(&url).unwrap(), "# Cached\n");
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde_json::Value;
+
+use crate::error::SpecmanError;
+use crate::front_matter::{
+    FrontMatterKind, ImplementationFrontMatter, ScratchFrontMatter, SpecificationFrontMatter,
+};
+use crate::lint::DATA_MODEL_SPEC;
+use crate::workspace::WorkspacePaths;
+
+/// Every front matter kind, in the order `specman schema all` emits them.
+pub const SCHEMA_KINDS: [FrontMatterKind; 3] = [
+    FrontMatterKind::Specification,
+    FrontMatterKind::Implementation,
+    FrontMatterKind::ScratchPad,
+];
+
+/// Short name used for a kind on the command line and in schema file names.
+pub fn schema_name(kind: FrontMatterKind) -> &'static str {
+    match kind {
+        FrontMatterKind::Specification => "spec",
+        FrontMatterKind::Implementation => "impl",
+        FrontMatterKind::ScratchPad => "scratch",
+    }
+}
+
+/// Draft-07 JSON Schema for one kind's front matter, with shared types such as
+/// `ScratchWorkType` and `DependencyEntry` under `definitions`.
+pub fn front_matter_schema(kind: FrontMatterKind) -> Value {
+    let (mut schema, label, heading) = match kind {
+        FrontMatterKind::Specification => (
+            root_schema::<SpecificationFrontMatter>(),
+            "specification",
+            "specification-metadata",
+        ),
+        FrontMatterKind::Implementation => (
+            root_schema::<ImplementationFrontMatter>(),
+            "implementation",
+            "implementation-metadata",
+        ),
+        FrontMatterKind::ScratchPad => (
+            root_schema::<ScratchFrontMatter>(),
+            "scratch pad",
+            "scratch-pad-metadata",
+        ),
+    };
+    schema["title"] = Value::String(format!("SpecMan {label} front matter"));
+    schema["description"] = Value::String(format!(
+        "YAML front matter of a SpecMan {label}, as defined in {DATA_MODEL_SPEC}#{heading}."
+    ));
+    schema
+}
+
+/// Writes `.specman/schemas/{spec,impl,scratch}.schema.json` for `kinds` so editors can
+/// validate front matter, replacing any earlier copies. Returns the written paths.
+pub fn write_front_matter_schemas(
+    workspace: &WorkspacePaths,
+    kinds: &[FrontMatterKind],
+) -> Result<Vec<PathBuf>, SpecmanError> {
+    let dir = workspace.dot_specman().join("schemas");
+    fs::create_dir_all(&dir)?;
+    let mut written = Vec::new();
+    for kind in kinds {
+        let path = dir.join(format!("{}.schema.json", schema_name(*kind)));
+        let mut document = serde_json::to_string_pretty(&front_matter_schema(*kind))?;
+        document.push('\n');
+        fs::write(&path, document)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn root_schema<T: JsonSchema>() -> Value {
+    let schema = SchemaSettings::draft07()
+        .into_generator()
+        .into_root_schema_for::<T>();
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator};
+
+    #[test]
+    fn scratch_schema_covers_every_work_type() {
+        let schema = front_matter_schema(FrontMatterKind::ScratchPad);
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(schema["title"], "SpecMan scratch pad front matter");
+        for field in [
+            "name",
+            "version",
+            "target",
+            "branch",
+            "work_type",
+            "dependencies",
+        ] {
+            assert!(
+                schema["properties"][field].is_object(),
+                "missing property {field}"
+            );
+        }
+
+        let work_type = &schema["definitions"]["ScratchWorkType"];
+        let keys: Vec<&str> = work_type["anyOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["required"][0].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, ["draft", "revision", "feat", "ref", "fix"]);
+        let revision = &work_type["anyOf"][1];
+        assert_eq!(revision["additionalProperties"], false);
+        let revision_ref = revision["properties"]["revision"]["$ref"].as_str().unwrap();
+        let revision_def = revision_ref.trim_start_matches("#/definitions/");
+        assert!(schema["definitions"][revision_def]["properties"]["revised_headings"].is_object());
+    }
+
+    #[test]
+    fn writes_schemas_under_dot_specman() {
+        let tempdir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tempdir.path().join(".specman")).unwrap();
+        let workspace = FilesystemWorkspaceLocator::new(tempdir.path())
+            .workspace()
+            .unwrap();
+
+        let written = write_front_matter_schemas(&workspace, &SCHEMA_KINDS).unwrap();
+        let names: Vec<String> = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "spec.schema.json",
+                "impl.schema.json",
+                "scratch.schema.json"
+            ]
+        );
+        let spec: Value = serde_json::from_str(&fs::read_to_string(&written[0]).unwrap()).unwrap();
+        assert_eq!(spec, front_matter_schema(FrontMatterKind::Specification));
+        assert!(spec["properties"]["dependencies"].is_object());
+    }
+}
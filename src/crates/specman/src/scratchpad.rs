@@ -1,9 +1,19 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::dependency_tree::{ArtifactKind, local_reference_path};
+use crate::error::{CodedError, ErrorCode, SpecmanError};
+use crate::front_matter::{
+    self, ArtifactFrontMatter, FrontMatterKind, ScratchWorkType, ScratchWorkTypeKind,
+};
+use crate::headings;
+use crate::impact::artifact_for_path;
 use crate::template::{TemplateDescriptor, TemplateProvenance, TokenMap};
+use crate::workspace::WorkspacePaths;
 
 /// Standard scratch pad profiles aligned with SpecMan work types.
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
@@ -75,3 +85,288 @@ impl ScratchPadProfile {
             .collect()
     }
 }
+
+/// Checks a scratch pad's work type against its target, per the Work Type section of the
+/// SpecMan Data Model: `draft` and `revision` target a local specification, `feat` and `ref`
+/// an implementation, and `fix` a local implementation. `revised_headings` must be headings of
+/// the target specification, `refactored_headings` of the implemented specification, and
+/// `fixed_headings` of any specification the implementation references.
+///
+/// Relative targets resolve from the workspace root, then from `scratch_dir`. Returns one
+/// message per violation.
+pub fn work_type_violations(
+    work_type: &ScratchWorkType,
+    target: &str,
+    scratch_dir: &Path,
+    workspace: &WorkspacePaths,
+) -> Vec<String> {
+    let kind = work_type.kind();
+    let label = kind.as_str();
+    let (target, _) = headings::split_fragment(target.trim());
+    let expected = match kind {
+        ScratchWorkTypeKind::Draft | ScratchWorkTypeKind::Revision => ArtifactKind::Specification,
+        _ => ArtifactKind::Implementation,
+    };
+
+    let Some(path) = target_path(target, scratch_dir, workspace) else {
+        // The kind of a remote artifact is unknown, so only the work types that must stay
+        // local can reject it.
+        return match kind {
+            ScratchWorkTypeKind::Feat | ScratchWorkTypeKind::Refactor => Vec::new(),
+            _ => vec![format!(
+                "`{label}` scratch pads must target a local {}, not {target}",
+                kind_label(expected)
+            )],
+        };
+    };
+    match artifact_for_path(workspace, &path) {
+        Some(id) if id.kind == expected => {}
+        Some(id) => {
+            return vec![format!(
+                "`{label}` scratch pads must target {}, but {target} is {}",
+                with_article(expected),
+                with_article(id.kind)
+            )];
+        }
+        None => {
+            return vec![format!(
+                "target {target} is not a specification or implementation in this workspace"
+            )];
+        }
+    }
+
+    let (field, fragments, documents) = match work_type {
+        ScratchWorkType::Revision(meta) => ("revised_headings", &meta.revised_headings, vec![path]),
+        ScratchWorkType::Refactor(meta) => (
+            "refactored_headings",
+            &meta.refactored_headings,
+            implemented_specifications(&path, workspace, false),
+        ),
+        ScratchWorkType::Fix(meta) => (
+            "fixed_headings",
+            &meta.fixed_headings,
+            implemented_specifications(&path, workspace, true),
+        ),
+        ScratchWorkType::Draft(_) | ScratchWorkType::Feat(_) => return Vec::new(),
+    };
+    if fragments.is_empty() {
+        return Vec::new();
+    }
+
+    let mut anchors = BTreeSet::new();
+    let mut searched = Vec::new();
+    for document in &documents {
+        if let Ok(content) = fs::read_to_string(document) {
+            anchors.extend(headings::heading_anchors(&content));
+            searched.push(relative_label(document, workspace));
+        }
+    }
+    if searched.is_empty() {
+        return vec![format!(
+            "cannot check `{field}`: no local specification found for {target}"
+        )];
+    }
+    fragments
+        .iter()
+        .map(|fragment| fragment.trim().trim_start_matches('#'))
+        .filter(|anchor| !anchors.contains(*anchor))
+        .map(|anchor| {
+            format!(
+                "`{field}` entry #{anchor} is not a heading in {}",
+                searched.join(", ")
+            )
+        })
+        .collect()
+}
+
+/// Fails with the violations [`work_type_violations`] reports, if any.
+pub fn validate_work_type(
+    work_type: &ScratchWorkType,
+    target: &str,
+    scratch_dir: &Path,
+    workspace: &WorkspacePaths,
+) -> Result<(), SpecmanError> {
+    let violations = work_type_violations(work_type, target, scratch_dir, workspace);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(
+            CodedError::new(ErrorCode::ScratchTarget, violations.join("; "))
+                .with_locator(target)
+                .into(),
+        )
+    }
+}
+
+fn target_path(target: &str, scratch_dir: &Path, workspace: &WorkspacePaths) -> Option<PathBuf> {
+    let primary = local_reference_path(target, workspace.root(), workspace)?;
+    if primary.exists() {
+        return Some(primary);
+    }
+    local_reference_path(target, scratch_dir, workspace)
+        .filter(|fallback| fallback.exists())
+        .or(Some(primary))
+}
+
+/// Local specification documents an implementation declares in `spec`, plus (when
+/// `include_references` is set) its `type: specification` references.
+fn implemented_specifications(
+    implementation: &Path,
+    workspace: &WorkspacePaths,
+    include_references: bool,
+) -> Vec<PathBuf> {
+    let Some(dir) = implementation.parent() else {
+        return Vec::new();
+    };
+    let front = fs::read_to_string(implementation).ok().and_then(|content| {
+        let split = front_matter::split_front_matter(&content).ok()?;
+        ArtifactFrontMatter::from_yaml_str_for_kind(split.yaml, FrontMatterKind::Implementation)
+            .ok()
+    });
+    let Some(ArtifactFrontMatter::Implementation(front)) = front else {
+        return Vec::new();
+    };
+
+    let mut locators: Vec<&str> = front.spec.as_deref().into_iter().collect();
+    if include_references {
+        locators.extend(
+            front
+                .references
+                .iter()
+                .filter(|reference| reference.reference_type.as_deref() == Some("specification"))
+                .map(|reference| reference.reference.as_str()),
+        );
+    }
+    locators
+        .into_iter()
+        .filter_map(|locator| {
+            let (locator, _) = headings::split_fragment(locator);
+            local_reference_path(locator, dir, workspace)
+        })
+        .collect()
+}
+
+fn kind_label(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Specification => "specification",
+        ArtifactKind::Implementation => "implementation",
+        ArtifactKind::ScratchPad => "scratch pad",
+    }
+}
+
+fn with_article(kind: ArtifactKind) -> String {
+    match kind {
+        ArtifactKind::Implementation => format!("an {}", kind_label(kind)),
+        _ => format!("a {}", kind_label(kind)),
+    }
+}
+
+fn relative_label(path: &Path, workspace: &WorkspacePaths) -> String {
+    path.strip_prefix(workspace.root())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::front_matter::{ScratchFixMetadata, ScratchRevisionMetadata, ScratchWorkloadExtras};
+    use crate::workspace::{FilesystemWorkspaceLocator, WorkspaceLocator};
+
+    #[test]
+    fn work_types_require_matching_target_kinds() {
+        let (_tempdir, workspace) = workspace();
+        let scratch_dir = workspace.scratchpad_dir().join("notes");
+        let check = |work_type: ScratchWorkType, target: &str| {
+            work_type_violations(&work_type, target, &scratch_dir, &workspace)
+        };
+        let draft = || ScratchWorkType::Draft(ScratchWorkloadExtras::default());
+        let fix = || ScratchWorkType::Fix(ScratchFixMetadata::default());
+
+        // Drafts may name a specification that does not exist yet.
+        assert!(check(draft(), "spec/upcoming/spec.md").is_empty());
+        assert!(check(draft(), "spec://core").is_empty());
+        assert_eq!(
+            check(draft(), "impl/core-rust/impl.md"),
+            vec![
+                "`draft` scratch pads must target a specification, but impl/core-rust/impl.md is an implementation"
+            ]
+        );
+        assert_eq!(
+            check(draft(), "https://example.com/spec.md"),
+            vec![
+                "`draft` scratch pads must target a local specification, not https://example.com/spec.md"
+            ]
+        );
+        assert!(check(fix(), "impl://core-rust").is_empty());
+        assert_eq!(check(fix(), "spec/core/spec.md").len(), 1);
+        assert_eq!(check(fix(), "https://example.com/impl.md").len(), 1);
+        assert!(
+            check(
+                ScratchWorkType::Feat(ScratchWorkloadExtras::default()),
+                "https://example.com/impl.md"
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            check(fix(), "README.md"),
+            vec!["target README.md is not a specification or implementation in this workspace"]
+        );
+    }
+
+    #[test]
+    fn heading_lists_must_name_existing_headings() {
+        let (_tempdir, workspace) = workspace();
+        let scratch_dir = workspace.scratchpad_dir().join("notes");
+
+        let revision = ScratchWorkType::Revision(ScratchRevisionMetadata {
+            revised_headings: vec!["#concepts".into(), "#missing".into()],
+            ..Default::default()
+        });
+        assert_eq!(
+            work_type_violations(&revision, "spec/core/spec.md", &scratch_dir, &workspace),
+            vec!["`revised_headings` entry #missing is not a heading in spec/core/spec.md"]
+        );
+
+        // Fixes may cite headings from any referenced specification, not just `spec`.
+        let fix = ScratchWorkType::Fix(ScratchFixMetadata {
+            fixed_headings: vec!["concepts".into(), "#widgets".into()],
+            ..Default::default()
+        });
+        assert!(
+            work_type_violations(&fix, "impl/core-rust/impl.md", &scratch_dir, &workspace)
+                .is_empty()
+        );
+        let err = validate_work_type(&revision, "spec/core/spec.md", &scratch_dir, &workspace)
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::ScratchTarget);
+        assert!(err.to_string().contains("#missing"), "{err}");
+    }
+
+    fn workspace() -> (tempfile::TempDir, WorkspacePaths) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        for (path, content) in [
+            (
+                "spec/core/spec.md",
+                "---\nname: core\n---\n# Core\n\n## Concepts\n",
+            ),
+            (
+                "spec/widgets/spec.md",
+                "---\nname: widgets\n---\n# Widgets\n",
+            ),
+            (
+                "impl/core-rust/impl.md",
+                "---\nspec: ../../spec/core/spec.md\nreferences:\n  - ref: spec://widgets\n    type: specification\n---\n# Core in Rust\n",
+            ),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        fs::create_dir_all(root.join(".specman/scratchpad")).unwrap();
+        let workspace = FilesystemWorkspaceLocator::new(root).workspace().unwrap();
+        (tempdir, workspace)
+    }
+}
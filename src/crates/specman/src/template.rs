@@ -6,7 +6,8 @@ use std::sync::Arc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::error::SpecmanError;
+use crate::error::{CodedError, ErrorCode, SpecmanError};
+use crate::template_catalog::embedded_template;
 
 pub type TokenMap = BTreeMap<String, serde_json::Value>;
 
@@ -58,6 +59,8 @@ impl Default for TemplateScenario {
 pub enum TemplateLocator {
     FilePath(PathBuf),
     Url(String),
+    /// Template compiled into SpecMan, by key (`spec`, `impl`, or `scratch`).
+    Embedded(String),
 }
 
 impl Default for TemplateLocator {
@@ -102,22 +105,25 @@ impl TemplateEngine for MarkdownTemplateEngine {
         descriptor: &TemplateDescriptor,
         tokens: &TokenMap,
     ) -> Result<RenderedTemplate, SpecmanError> {
-        match &descriptor.locator {
-            TemplateLocator::FilePath(path) => {
-                let raw = fs::read_to_string(path)?;
-                let body = apply_tokens(&raw, descriptor, tokens)?;
-                // Leverage the `markdown` crate to parse as a validation step.
-                let _ = markdown::to_html(&body);
-                Ok(RenderedTemplate {
-                    body,
-                    metadata: descriptor.clone(),
-                    provenance: None,
-                })
+        let raw = match &descriptor.locator {
+            TemplateLocator::FilePath(path) => fs::read_to_string(path)?,
+            TemplateLocator::Url(url) => {
+                return Err(SpecmanError::Template(format!(
+                    "remote templates are not yet supported: {url}"
+                )));
             }
-            TemplateLocator::Url(url) => Err(SpecmanError::Template(format!(
-                "remote templates are not yet supported: {url}"
-            ))),
-        }
+            TemplateLocator::Embedded(key) => embedded_template(key)
+                .ok_or_else(|| SpecmanError::Template(format!("no embedded template {key}")))?
+                .to_string(),
+        };
+        let body = apply_tokens(&raw, descriptor, tokens)?;
+        // Leverage the `markdown` crate to parse as a validation step.
+        let _ = markdown::to_html(&body);
+        Ok(RenderedTemplate {
+            body,
+            metadata: descriptor.clone(),
+            provenance: None,
+        })
     }
 }
 
@@ -141,7 +147,16 @@ fn apply_tokens(
 ) -> Result<String, SpecmanError> {
     for key in &descriptor.required_tokens {
         if !tokens.contains_key(key) {
-            return Err(SpecmanError::Template(format!("missing token: {key}")));
+            return Err(CodedError::new(
+                ErrorCode::TemplateMissingToken,
+                format!("missing token: {key}"),
+            )
+            .with_locator(match &descriptor.locator {
+                TemplateLocator::FilePath(path) => path.display().to_string(),
+                TemplateLocator::Url(url) => url.clone(),
+                TemplateLocator::Embedded(key) => format!("embedded://{key}"),
+            })
+            .into());
         }
     }
 
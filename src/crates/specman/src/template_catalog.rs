@@ -4,11 +4,12 @@ use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use url::Url;
 
-use crate::error::SpecmanError;
+use crate::error::{CodedError, ErrorCode, SpecmanError};
 use crate::scratchpad::{ScratchPadProfile, ScratchPadProfileKind};
 use crate::template::{
     TemplateDescriptor, TemplateLocator, TemplateProvenance, TemplateScenario, TemplateTier,
@@ -23,10 +24,11 @@ const EMBEDDED_SCRATCH: &str = include_str!("../templates/scratch/scratch.md");
 /// pointer files, remote caches, and embedded defaults.
 pub struct TemplateCatalog {
     workspace: WorkspacePaths,
+    cache_embedded: bool,
 }
 
 /// Result of resolving a template with provenance metadata for persistence.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ResolvedTemplate {
     pub descriptor: TemplateDescriptor,
     pub provenance: TemplateProvenance,
@@ -34,7 +36,17 @@ pub struct ResolvedTemplate {
 
 impl TemplateCatalog {
     pub fn new(workspace: WorkspacePaths) -> Self {
-        Self { workspace }
+        Self {
+            workspace,
+            cache_embedded: true,
+        }
+    }
+
+    /// Resolves embedded defaults in memory instead of refreshing their copies under
+    /// `.specman/cache/templates`, so dry runs leave the workspace untouched.
+    pub fn without_cache_writes(mut self) -> Self {
+        self.cache_embedded = false;
+        self
     }
 
     /// Resolves a template descriptor for the given scenario following the
@@ -171,10 +183,15 @@ impl TemplateCatalog {
         };
 
         if !resolved.starts_with(self.workspace.root()) {
-            return Err(SpecmanError::Template(format!(
-                "pointer locator escapes the workspace: {}",
-                resolved.display()
-            )));
+            return Err(CodedError::new(
+                ErrorCode::WorkspaceEscape,
+                format!(
+                    "pointer locator escapes the workspace: {}",
+                    resolved.display()
+                ),
+            )
+            .with_locator(trimmed)
+            .into());
         }
 
         if !resolved.is_file() {
@@ -261,61 +278,17 @@ impl TemplateCatalog {
         scenario: &TemplateScenario,
     ) -> Result<Option<ResolvedTemplate>, SpecmanError> {
         let pointer_name = pointer_name(scenario);
-        let pointer_path = self.templates_dir().join(pointer_name);
-        if !pointer_path.is_file() {
-            return Ok(None);
-        }
-
-        let contents = fs::read_to_string(&pointer_path).map_err(|err| {
-            SpecmanError::Template(format!(
-                "failed to read template pointer {}: {err}",
-                pointer_path.display()
-            ))
-        })?;
-        let trimmed = contents.trim();
-        if trimmed.is_empty() {
-            return Err(SpecmanError::Template(format!(
-                "template pointer {} has no content",
-                pointer_path.display()
-            )));
-        }
-
-        if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
-            let url = Url::parse(trimmed).map_err(|err| {
-                SpecmanError::Template(format!("invalid template pointer URL {}: {err}", trimmed))
-            })?;
-            let cache = TemplateCache::new(&self.workspace);
-            match cache.fetch_url(&url) {
-                Ok(hit) => {
-                    let cache_path = workspace_relative(self.workspace.root(), &hit.path);
-                    return Ok(Some(self.resolved_from_path(
-                        scenario,
-                        hit.path,
-                        TemplateTier::PointerUrl,
-                        Some(pointer_name.to_string()),
-                        Some(url.to_string()),
-                        Some(cache_path),
-                        hit.last_modified,
-                    )));
-                }
-                Err(_err) => {
-                    // Spec requires falling back to embedded defaults when the remote
-                    // pointer cannot be refreshed and no cache exists.
-                    return Ok(None);
-                }
-            }
-        }
-
-        let file_path = self.resolve_pointer_path(trimmed, pointer_name)?;
-        Ok(Some(self.resolved_from_path(
-            scenario,
-            file_path,
-            TemplateTier::PointerFile,
-            Some(pointer_name.to_string()),
-            None,
-            None,
-            None,
-        )))
+        Ok(read_pointer(&self.workspace, pointer_name)?.map(|target| {
+            self.resolved_from_path(
+                scenario,
+                target.path,
+                target.tier,
+                Some(pointer_name.to_string()),
+                target.locator,
+                target.cache_path,
+                target.last_modified,
+            )
+        }))
     }
 
     fn embedded_default(
@@ -323,8 +296,26 @@ impl TemplateCatalog {
         scenario: &TemplateScenario,
     ) -> Result<ResolvedTemplate, SpecmanError> {
         let (key, body) = embedded_assets(scenario);
-
         let cache = TemplateCache::new(&self.workspace);
+        if !self.cache_embedded {
+            // Report the cache copy a real run would write so previews match it exactly.
+            let cache_path = workspace_relative(self.workspace.root(), &cache.embedded_path(key));
+            return Ok(ResolvedTemplate {
+                descriptor: TemplateDescriptor {
+                    locator: TemplateLocator::Embedded(key.to_string()),
+                    scenario: scenario.clone(),
+                    required_tokens: Vec::new(),
+                },
+                provenance: TemplateProvenance {
+                    tier: TemplateTier::EmbeddedDefault,
+                    locator: format!("embedded://{key}"),
+                    pointer: None,
+                    cache_path: Some(cache_path),
+                    last_modified: None,
+                },
+            });
+        }
+
         let path = cache.write_embedded(key, body)?;
         let cache_path = workspace_relative(self.workspace.root(), &path);
         Ok(self.resolved_from_path(
@@ -355,33 +346,6 @@ impl TemplateCatalog {
         }
     }
 
-    fn resolve_pointer_path(&self, raw: &str, pointer_name: &str) -> Result<PathBuf, SpecmanError> {
-        let candidate = PathBuf::from(raw);
-        let resolved = if candidate.is_absolute() {
-            candidate
-        } else {
-            self.workspace.root().join(candidate)
-        };
-
-        if !resolved.starts_with(self.workspace.root()) {
-            return Err(SpecmanError::Template(format!(
-                "pointer {} resolved outside the workspace: {}",
-                pointer_name,
-                resolved.display()
-            )));
-        }
-
-        if !resolved.is_file() {
-            return Err(SpecmanError::Template(format!(
-                "pointer {} references missing file: {}",
-                pointer_name,
-                resolved.display()
-            )));
-        }
-
-        Ok(resolved)
-    }
-
     fn resolved_from_path(
         &self,
         scenario: &TemplateScenario,
@@ -430,6 +394,111 @@ fn embedded_assets(scenario: &TemplateScenario) -> (&'static str, &'static str)
     }
 }
 
+/// Body of the embedded template stored under `key`.
+pub(crate) fn embedded_template(key: &str) -> Option<&'static str> {
+    match key {
+        "spec" => Some(EMBEDDED_SPEC),
+        "impl" => Some(EMBEDDED_IMPL),
+        "scratch" => Some(EMBEDDED_SCRATCH),
+        _ => None,
+    }
+}
+
+/// File a pointer under `.specman/templates/` currently resolves to.
+pub(crate) struct PointerTarget {
+    pub(crate) path: PathBuf,
+    pub(crate) tier: TemplateTier,
+    pub(crate) locator: Option<String>,
+    pub(crate) cache_path: Option<String>,
+    pub(crate) last_modified: Option<String>,
+}
+
+/// Follows the named pointer file, downloading (or reusing the cache for) remote
+/// locators. Returns `Ok(None)` when the pointer is absent or its remote target is
+/// unreachable without a cached copy, so callers fall through to embedded defaults.
+pub(crate) fn read_pointer(
+    workspace: &WorkspacePaths,
+    pointer_name: &str,
+) -> Result<Option<PointerTarget>, SpecmanError> {
+    let pointer_path = workspace.dot_specman().join("templates").join(pointer_name);
+    if !pointer_path.is_file() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(&pointer_path).map_err(|err| {
+        SpecmanError::Template(format!(
+            "failed to read template pointer {}: {err}",
+            pointer_path.display()
+        ))
+    })?;
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        return Err(SpecmanError::Template(format!(
+            "template pointer {} has no content",
+            pointer_path.display()
+        )));
+    }
+
+    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+        let url = Url::parse(trimmed).map_err(|err| {
+            SpecmanError::Template(format!("invalid template pointer URL {}: {err}", trimmed))
+        })?;
+        let cache = TemplateCache::new(workspace);
+        return match cache.fetch_url(&url) {
+            Ok(hit) => Ok(Some(PointerTarget {
+                cache_path: Some(workspace_relative(workspace.root(), &hit.path)),
+                path: hit.path,
+                tier: TemplateTier::PointerUrl,
+                locator: Some(url.to_string()),
+                last_modified: hit.last_modified,
+            })),
+            // Spec requires falling back to embedded defaults when the remote
+            // pointer cannot be refreshed and no cache exists.
+            Err(_err) => Ok(None),
+        };
+    }
+
+    let path = resolve_pointer_path(workspace.root(), trimmed, pointer_name)?;
+    Ok(Some(PointerTarget {
+        path,
+        tier: TemplateTier::PointerFile,
+        locator: None,
+        cache_path: None,
+        last_modified: None,
+    }))
+}
+
+fn resolve_pointer_path(
+    root: &Path,
+    raw: &str,
+    pointer_name: &str,
+) -> Result<PathBuf, SpecmanError> {
+    let candidate = PathBuf::from(raw);
+    let resolved = if candidate.is_absolute() {
+        candidate
+    } else {
+        root.join(candidate)
+    };
+
+    if !resolved.starts_with(root) {
+        return Err(SpecmanError::Template(format!(
+            "pointer {} resolved outside the workspace: {}",
+            pointer_name,
+            resolved.display()
+        )));
+    }
+
+    if !resolved.is_file() {
+        return Err(SpecmanError::Template(format!(
+            "pointer {} references missing file: {}",
+            pointer_name,
+            resolved.display()
+        )));
+    }
+
+    Ok(resolved)
+}
+
 fn sanitize_key(raw: &str) -> String {
     raw.chars()
         .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-' || *ch == '_')
@@ -437,18 +506,34 @@ fn sanitize_key(raw: &str) -> String {
         .to_lowercase()
 }
 
-fn workspace_relative(root: &Path, path: &Path) -> String {
+pub(crate) fn workspace_relative(root: &Path, path: &Path) -> String {
     path.strip_prefix(root)
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|_| path.to_string_lossy().to_string())
 }
 
-struct TemplateCache {
+/// Shortens absolute workspace file locators to workspace-relative paths; URLs and
+/// relative paths pass through unchanged.
+pub(crate) fn display_locator(root: &Path, locator: &str) -> String {
+    let path = Path::new(locator);
+    if !path.is_absolute() {
+        return locator.to_string();
+    }
+    if path.starts_with(root) {
+        return workspace_relative(root, path);
+    }
+    match root.canonicalize() {
+        Ok(canonical) => workspace_relative(&canonical, path),
+        Err(_) => locator.to_string(),
+    }
+}
+
+pub(crate) struct TemplateCache {
     root: PathBuf,
 }
 
 impl TemplateCache {
-    fn new(workspace: &WorkspacePaths) -> Self {
+    pub(crate) fn new(workspace: &WorkspacePaths) -> Self {
         Self {
             root: workspace.dot_specman().join("cache").join("templates"),
         }
@@ -458,13 +543,22 @@ impl TemplateCache {
         fs::create_dir_all(&self.root).map_err(SpecmanError::from)
     }
 
-    fn write_embedded(&self, key: &str, contents: &str) -> Result<PathBuf, SpecmanError> {
+    pub(crate) fn write_embedded(
+        &self,
+        key: &str,
+        contents: &str,
+    ) -> Result<PathBuf, SpecmanError> {
         self.ensure_root()?;
-        let path = self.root.join(format!("embedded-{key}.md"));
+        let path = self.embedded_path(key);
         fs::write(&path, contents)?;
         Ok(path)
     }
 
+    /// Where [`Self::write_embedded`] stores the embedded template `key`.
+    pub(crate) fn embedded_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("embedded-{key}.md"))
+    }
+
     fn fetch_url(&self, url: &Url) -> Result<CacheHit, SpecmanError> {
         self.ensure_root()?;
         let key = hash_url(url);
@@ -554,7 +648,7 @@ fn read_metadata(path: &Path) -> Result<Option<TemplateCacheMetadata>, SpecmanEr
     Ok(Some(metadata))
 }
 
-fn hash_url(url: &Url) -> String {
+pub(crate) fn hash_url(url: &Url) -> String {
     let mut hasher = Sha256::new();
     hasher.update(url.as_str().as_bytes());
     let digest = hasher.finalize();
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::dependency_tree::{ArtifactKind, DependencyGraphServices};
+use crate::error::SpecmanError;
+use crate::template::TokenMap;
+use crate::template_catalog::display_locator;
+use crate::workspace::WorkspaceLocator;
+
+/// Token names defined by the SpecMan Templates Template Token Contract.
+pub const OUTPUT_NAME: &str = "output_name";
+pub const CONTEXT: &str = "context";
+pub const DEPENDENCIES: &str = "dependencies";
+pub const ARGUMENTS: &str = "arguments";
+
+/// Inputs the contract tokens are derived from for an artifact that is about to be created.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenRequest {
+    pub kind: ArtifactKind,
+    pub name: String,
+    /// Locators the new artifact declares: a specification's `dependencies`, an
+    /// implementation's `spec` plus `references`, or a scratch pad's `target`.
+    #[serde(default)]
+    pub declared: Vec<String>,
+    /// Caller-supplied instructions, passed through verbatim.
+    #[serde(default)]
+    pub arguments: String,
+}
+
+impl TokenRequest {
+    pub fn new(kind: ArtifactKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            declared: Vec::new(),
+            arguments: String::new(),
+        }
+    }
+}
+
+/// Computes template tokens for artifact creation.
+pub trait TokenProvider: Send + Sync {
+    fn tokens(&self, request: &TokenRequest) -> Result<TokenMap, SpecmanError>;
+}
+
+/// Fills `{{output_name}}`, `{{context}}`, `{{dependencies}}`, and `{{arguments}}`.
+///
+/// Specifications and implementations expand `context` and `dependencies` to their declared
+/// locators. Scratch pads expand both to the target plus its upstream chain from
+/// [`DependencyGraphServices`]. `dependencies` is a JSON array so it drops into YAML front
+/// matter as a flow sequence; `context` is a Markdown bullet list.
+pub struct ContractTokenProvider<L: WorkspaceLocator> {
+    graph: Arc<DependencyGraphServices<L>>,
+}
+
+impl<L: WorkspaceLocator> ContractTokenProvider<L> {
+    pub fn new(graph: Arc<DependencyGraphServices<L>>) -> Self {
+        Self { graph }
+    }
+
+    fn scratch_chain(&self, targets: &[String]) -> Result<Vec<String>, SpecmanError> {
+        let workspace = self.graph.workspace_paths()?;
+        let mut chain = Vec::new();
+        for target in targets {
+            let tree = self.graph.dependency_tree_from_locator(target)?;
+            let entries = std::iter::once(target.clone()).chain(
+                tree.upstream_chain()
+                    .into_iter()
+                    .filter_map(|summary| summary.metadata.get("locator"))
+                    .map(|locator| display_locator(workspace.root(), locator)),
+            );
+            for entry in entries {
+                if !chain.contains(&entry) {
+                    chain.push(entry);
+                }
+            }
+        }
+        Ok(chain)
+    }
+}
+
+impl<L: WorkspaceLocator> TokenProvider for ContractTokenProvider<L> {
+    fn tokens(&self, request: &TokenRequest) -> Result<TokenMap, SpecmanError> {
+        let upstream = match request.kind {
+            ArtifactKind::ScratchPad => self.scratch_chain(&request.declared)?,
+            ArtifactKind::Specification | ArtifactKind::Implementation => request.declared.clone(),
+        };
+
+        let context = upstream
+            .iter()
+            .map(|locator| format!("- `{locator}`"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut tokens = TokenMap::new();
+        tokens.insert(OUTPUT_NAME.into(), Value::String(request.name.clone()));
+        tokens.insert(CONTEXT.into(), Value::String(context));
+        tokens.insert(
+            DEPENDENCIES.into(),
+            Value::Array(upstream.into_iter().map(Value::String).collect()),
+        );
+        tokens.insert(ARGUMENTS.into(), Value::String(request.arguments.clone()));
+        Ok(tokens)
+    }
+}
+
+impl<T> TokenProvider for Arc<T>
+where
+    T: TokenProvider,
+{
+    fn tokens(&self, request: &TokenRequest) -> Result<TokenMap, SpecmanError> {
+        (**self).tokens(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::{
+        MarkdownTemplateEngine, TemplateDescriptor, TemplateEngine, TemplateLocator,
+        TemplateScenario,
+    };
+    use crate::workspace::FilesystemWorkspaceLocator;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn specifications_expand_declared_dependencies() {
+        let (_tempdir, provider) = fixture();
+        let mut request = TokenRequest::new(ArtifactKind::Specification, "gamma");
+        request.declared = vec!["spec/alpha/spec.md".into()];
+        request.arguments = "Keep it short.".into();
+
+        let tokens = provider.tokens(&request).unwrap();
+        assert_eq!(tokens[OUTPUT_NAME], Value::from("gamma"));
+        assert_eq!(tokens[CONTEXT], Value::from("- `spec/alpha/spec.md`"));
+        assert_eq!(
+            tokens[DEPENDENCIES],
+            Value::from(vec!["spec/alpha/spec.md"])
+        );
+        assert_eq!(tokens[ARGUMENTS], Value::from("Keep it short."));
+    }
+
+    #[test]
+    fn scratch_pads_expand_the_target_dependency_chain() {
+        let (tempdir, provider) = fixture();
+        let mut request = TokenRequest::new(ArtifactKind::ScratchPad, "alpha-fix");
+        request.declared = vec!["impl/alpha-rust/impl.md".into()];
+        let tokens = provider.tokens(&request).unwrap();
+
+        assert_eq!(
+            tokens[DEPENDENCIES],
+            Value::from(vec![
+                "impl/alpha-rust/impl.md",
+                "spec/alpha/spec.md",
+                "spec/base/spec.md",
+            ])
+        );
+
+        let template = tempdir.path().join("scratch-template.md");
+        fs::write(
+            &template,
+            "---\nname: {{output_name}}\ndependencies: {{dependencies}}\n---\n{{context}}\n{{arguments}}\n",
+        )
+        .unwrap();
+        let descriptor = TemplateDescriptor {
+            locator: TemplateLocator::FilePath(template),
+            scenario: TemplateScenario::ScratchPad,
+            required_tokens: Vec::new(),
+        };
+        let rendered = MarkdownTemplateEngine.render(&descriptor, &tokens).unwrap();
+        assert!(rendered.body.starts_with(
+            "---\nname: alpha-fix\ndependencies: [\"impl/alpha-rust/impl.md\",\"spec/alpha/spec.md\",\"spec/base/spec.md\"]\n---\n- `impl/alpha-rust/impl.md`\n"
+        ));
+    }
+
+    fn fixture() -> (
+        tempfile::TempDir,
+        ContractTokenProvider<FilesystemWorkspaceLocator>,
+    ) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        write(
+            &root.join("spec/base/spec.md"),
+            "---\nname: base\nversion: \"1.0.0\"\ndependencies: []\n---\nBase.\n",
+        );
+        write(
+            &root.join("spec/alpha/spec.md"),
+            "---\nname: alpha\nversion: \"1.0.0\"\ndependencies:\n  - ../base/spec.md\n---\nAlpha.\n",
+        );
+        write(
+            &root.join("impl/alpha-rust/impl.md"),
+            "---\nname: alpha-rust\nversion: \"0.1.0\"\nspec: ../../spec/alpha/spec.md\nprimary_language:\n  language: rust@1.0\n---\nImpl.\n",
+        );
+        let graph = Arc::new(DependencyGraphServices::new(
+            FilesystemWorkspaceLocator::new(root),
+        ));
+        (tempdir, ContractTokenProvider::new(graph))
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
@@ -0,0 +1,623 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_yaml::{Mapping, Value as YamlValue};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::audit::{self, AuditLog, OperationEnvelope};
+use crate::capability::DEPENDENCY_VENDOR;
+use crate::dependency_tree::{
+    ArtifactId, ArtifactKind, ContentFetcher, ResourceHandle, gather_workspace_artifacts,
+};
+use crate::error::SpecmanError;
+use crate::front_matter::{
+    self, ArtifactFrontMatter, DependencyEntry, FrontMatterKind, ReferenceEntry,
+};
+use crate::locking::{LockOptions, WorkspaceLockManager};
+use crate::metadata::{LocatorReplacement, MetadataMutationRequest, MetadataMutator};
+use crate::workspace::{WorkspaceLocator, WorkspacePaths};
+
+/// Provenance of a remote specification copied into the workspace.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VendoredArtifact {
+    pub artifact: ArtifactId,
+    /// Workspace-relative path of the vendored copy.
+    pub path: String,
+    pub source: String,
+    /// Hex-encoded SHA-256 of the document as downloaded.
+    pub sha256: String,
+    /// Hex-encoded SHA-256 of the copy written to the workspace, when vendoring rewrote its
+    /// front matter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_sha256: Option<String>,
+    /// RFC 3339 UTC time of the download.
+    pub fetched_at: String,
+}
+
+/// Provenance records kept in `.specman/vendor.json`, ordered by artifact name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VendorManifest {
+    #[serde(default)]
+    pub artifacts: Vec<VendoredArtifact>,
+}
+
+impl VendorManifest {
+    pub fn path(workspace: &WorkspacePaths) -> PathBuf {
+        workspace.dot_specman().join("vendor.json")
+    }
+
+    /// Reads the manifest, treating a missing file as an empty one.
+    pub fn load(workspace: &WorkspacePaths) -> Result<Self, SpecmanError> {
+        let path = Self::path(workspace);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|err| {
+            SpecmanError::Serialization(format!(
+                "invalid vendor manifest {}: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    pub fn get(&self, artifact: &ArtifactId) -> Option<&VendoredArtifact> {
+        self.artifacts
+            .iter()
+            .find(|entry| &entry.artifact == artifact)
+    }
+
+    fn upsert(&mut self, entry: VendoredArtifact) {
+        self.artifacts
+            .retain(|existing| existing.artifact != entry.artifact);
+        self.artifacts.push(entry);
+        self.artifacts
+            .sort_by(|a, b| a.artifact.name.cmp(&b.artifact.name));
+    }
+
+    fn save(&self, workspace: &WorkspacePaths) -> Result<(), SpecmanError> {
+        fs::write(
+            Self::path(workspace),
+            serde_json::to_string_pretty(self)? + "\n",
+        )?;
+        Ok(())
+    }
+}
+
+/// Result of vendoring a remote specification.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VendorOutcome {
+    pub vendored: VendoredArtifact,
+    /// Artifacts whose front matter now points at the vendored copy instead of the URL.
+    pub rewritten: Vec<ArtifactId>,
+    /// Why the download could not be appended to the audit log. The copy was still written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_error: Option<String>,
+}
+
+/// Comparison of one vendored specification with its upstream source and local copy.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VendorCheck {
+    pub vendored: VendoredArtifact,
+    /// Hash of the upstream document, or `None` when it could not be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_sha256: Option<String>,
+    /// Hash of the vendored file, or `None` when it is missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl VendorCheck {
+    /// The upstream document no longer matches what was vendored.
+    pub fn upstream_changed(&self) -> bool {
+        self.upstream_sha256
+            .as_ref()
+            .is_some_and(|hash| hash != &self.vendored.sha256)
+    }
+
+    /// The vendored file was edited or removed since it was written.
+    pub fn locally_modified(&self) -> bool {
+        let written = self
+            .vendored
+            .written_sha256
+            .as_ref()
+            .unwrap_or(&self.vendored.sha256);
+        self.local_sha256.as_ref() != Some(written)
+    }
+}
+
+/// Copies remote specifications into `spec/{name}/spec.md`, records their provenance, and
+/// repoints front matter that referenced the URL at the vendored `spec://` handle.
+pub struct Vendorer<L: WorkspaceLocator + Clone> {
+    workspace: L,
+    fetcher: Arc<dyn ContentFetcher>,
+    lock_options: LockOptions,
+}
+
+impl<L: WorkspaceLocator + Clone> Vendorer<L> {
+    pub fn new(workspace: L, fetcher: Arc<dyn ContentFetcher>) -> Self {
+        Self {
+            workspace,
+            fetcher,
+            lock_options: LockOptions::default(),
+        }
+    }
+
+    /// Overrides how long vendoring waits for, and when it reclaims, per-artifact locks.
+    pub fn lock_options(mut self, options: LockOptions) -> Self {
+        self.lock_options = options;
+        self
+    }
+
+    /// Downloads `url` and stores it as a workspace specification. The name comes from
+    /// `name`, the document's front matter, or the URL, in that order; an explicit `name` is
+    /// also written into the copy's front matter. Relative `dependencies` and `references`
+    /// are rebased onto `url` so they keep resolving from the workspace. Vendoring the same
+    /// URL again refreshes the copy; any other existing specification of that name is an
+    /// error.
+    pub fn vendor(&self, url: &str, name: Option<&str>) -> Result<VendorOutcome, SpecmanError> {
+        let workspace = self.workspace.workspace()?;
+        let source = parse_source(url)?;
+        let body = self.fetcher.fetch(&source)?;
+
+        let front = match front_matter::split_front_matter(&body) {
+            Ok(split) => split.parse().map_err(|err| err.in_file(&source))?,
+            Err(_) => {
+                return Err(SpecmanError::Dependency(format!(
+                    "{source} has no front matter; only specifications can be vendored"
+                )));
+            }
+        };
+        if front.kind() != FrontMatterKind::Specification {
+            return Err(SpecmanError::Dependency(format!(
+                "{source} is not a specification; only specifications can be vendored"
+            )));
+        }
+
+        let raw_name = name
+            .map(str::to_string)
+            .or_else(|| front.name().map(str::to_string))
+            .or_else(|| name_from_url(&source))
+            .ok_or_else(|| {
+                SpecmanError::Dependency(format!(
+                    "cannot infer a specification name from {source}; pass one explicitly"
+                ))
+            })?;
+        let handle = ResourceHandle::new(ArtifactKind::Specification, &raw_name)?;
+        let artifact = handle.artifact_id();
+        let path = workspace.spec_dir().join(handle.slug()).join("spec.md");
+        let relative = workspace_relative(&workspace, &path);
+        let written = localize_front_matter(&body, &source, name.map(|_| handle.slug()))?;
+
+        let (vendored, audit_error) = {
+            let _lock = WorkspaceLockManager::with_options(&workspace, self.lock_options)
+                .acquire(&artifact)?;
+            let mut manifest = VendorManifest::load(&workspace)?;
+            let refresh = manifest
+                .get(&artifact)
+                .is_some_and(|entry| entry.source == source.as_str());
+            if path.exists() && !refresh {
+                return Err(SpecmanError::Workspace(format!(
+                    "{relative} already exists and was not vendored from {source}"
+                )));
+            }
+
+            let envelope = OperationEnvelope::begin(
+                DEPENDENCY_VENDOR,
+                json!({
+                    "url": audit::sanitize_locator(source.as_str()),
+                    "name": handle.slug(),
+                }),
+            );
+            let existed = path.exists();
+            let audit_log = AuditLog::open(&workspace)?;
+            fs::create_dir_all(path.parent().expect("spec path has a parent"))?;
+            fs::write(&path, &written)?;
+            let envelope = if existed {
+                envelope.updated(&workspace, &path)
+            } else {
+                envelope.created(&workspace, &path)
+            };
+            let audit_error = audit_log.record(&envelope.complete());
+
+            let entry = VendoredArtifact {
+                artifact: artifact.clone(),
+                path: relative,
+                source: source.to_string(),
+                sha256: sha256_hex(body.as_bytes()),
+                written_sha256: (written != body).then(|| sha256_hex(written.as_bytes())),
+                fetched_at: audit::timestamp(SystemTime::now()),
+            };
+            manifest.upsert(entry.clone());
+            manifest.save(&workspace)?;
+            (entry, audit_error)
+        };
+
+        let rewritten = self.rewrite_references(&workspace, &source, &handle, &path)?;
+        Ok(VendorOutcome {
+            vendored,
+            rewritten,
+            audit_error,
+        })
+    }
+
+    /// Re-downloads every vendored specification and hashes it alongside the local copy.
+    /// Fetch failures are recorded per artifact rather than aborting the check.
+    pub fn check(&self) -> Result<Vec<VendorCheck>, SpecmanError> {
+        let workspace = self.workspace.workspace()?;
+        let manifest = VendorManifest::load(&workspace)?;
+        let mut checks = Vec::with_capacity(manifest.artifacts.len());
+        for vendored in manifest.artifacts {
+            let local = workspace.root().join(&vendored.path);
+            let local_sha256 = fs::read(&local).ok().map(|bytes| sha256_hex(&bytes));
+            let (upstream_sha256, error) = match parse_source(&vendored.source)
+                .and_then(|source| self.fetcher.fetch(&source))
+            {
+                Ok(body) => (Some(sha256_hex(body.as_bytes())), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            checks.push(VendorCheck {
+                vendored,
+                upstream_sha256,
+                local_sha256,
+                error,
+            });
+        }
+        Ok(checks)
+    }
+
+    /// Points every front-matter entry whose locator is `source`, with or without a heading
+    /// fragment, at the vendored handle, keeping the fragment.
+    fn rewrite_references(
+        &self,
+        workspace: &WorkspacePaths,
+        source: &Url,
+        handle: &ResourceHandle,
+        vendored_path: &Path,
+    ) -> Result<Vec<ArtifactId>, SpecmanError> {
+        let replacement = LocatorReplacement::new(
+            source.as_str(),
+            format!("{}://{}", handle.scheme(), handle.slug()),
+        );
+        let mutator = MetadataMutator::new(self.workspace.clone()).lock_options(self.lock_options);
+
+        let mut files = gather_workspace_artifacts(workspace)?;
+        files.sort();
+        let mut rewritten = Vec::new();
+        for file in files {
+            if file == vendored_path {
+                continue;
+            }
+            let raw = fs::read_to_string(&file)?;
+            let mentions_source = front_matter::split_front_matter(&raw)
+                .and_then(|split| split.parse())
+                .is_ok_and(|front| {
+                    front_matter_locators(&front).any(|locator| names_source(locator, source))
+                });
+            if !mentions_source {
+                continue;
+            }
+
+            let mut request = MetadataMutationRequest::new(&file).persist(true);
+            request.replace_locators.push(replacement.clone());
+            if let Some(persisted) = mutator.mutate(request)?.persisted {
+                rewritten.push(persisted.artifact);
+            }
+        }
+        Ok(rewritten)
+    }
+}
+
+fn parse_source(url: &str) -> Result<Url, SpecmanError> {
+    let source = Url::parse(url)
+        .map_err(|err| SpecmanError::Dependency(format!("invalid url {url}: {err}")))?;
+    if source.scheme() != "https" {
+        return Err(SpecmanError::Dependency(format!(
+            "unsupported url scheme in {url}; use https"
+        )));
+    }
+    Ok(source)
+}
+
+/// Rebases relative `dependencies` and `references` onto `source` and, when `name` is given,
+/// sets the front matter `name`. Returns `body` untouched when nothing needs rewriting.
+fn localize_front_matter(
+    body: &str,
+    source: &Url,
+    name: Option<&str>,
+) -> Result<String, SpecmanError> {
+    let split = front_matter::split_front_matter(body)?;
+    let mapping: Mapping = serde_yaml::from_str(split.yaml)
+        .map_err(|err| SpecmanError::Serialization(format!("{source}: {err}")))?;
+    let renamed = name.is_some_and(|name| {
+        mapping
+            .get(YamlValue::from("name"))
+            .and_then(YamlValue::as_str)
+            != Some(name)
+    });
+    let relative = ["dependencies", "references"].iter().any(|field| {
+        mapping
+            .get(YamlValue::from(*field))
+            .and_then(YamlValue::as_sequence)
+            .is_some_and(|entries| {
+                entries
+                    .iter()
+                    .any(|entry| entry_locator(entry).is_some_and(is_relative_locator))
+            })
+    });
+    if !renamed && !relative {
+        return Ok(body.to_string());
+    }
+
+    front_matter::rewrite_front_matter(body, |doc| {
+        if let Some(name) = name {
+            doc.insert(YamlValue::from("name"), YamlValue::from(name));
+        }
+        for field in ["dependencies", "references"] {
+            let Some(YamlValue::Sequence(entries)) = doc.get_mut(YamlValue::from(field)) else {
+                continue;
+            };
+            for entry in entries {
+                let slot = match entry {
+                    YamlValue::Mapping(map) => match map.get_mut(YamlValue::from("ref")) {
+                        Some(slot) => slot,
+                        None => continue,
+                    },
+                    other => other,
+                };
+                let rebased = slot
+                    .as_str()
+                    .filter(|locator| is_relative_locator(locator))
+                    .and_then(|locator| source.join(locator).ok());
+                if let Some(rebased) = rebased {
+                    *slot = YamlValue::from(rebased.to_string());
+                }
+            }
+        }
+    })
+}
+
+/// The locator of a plain or `ref:` front-matter entry.
+fn entry_locator(entry: &YamlValue) -> Option<&str> {
+    match entry {
+        YamlValue::Mapping(map) => map.get(YamlValue::from("ref")).and_then(YamlValue::as_str),
+        other => other.as_str(),
+    }
+}
+
+/// Paths such as `../core/spec.md`, which only resolve next to the document. URLs,
+/// `spec://` handles, and same-document `#fragment` anchors are left alone.
+fn is_relative_locator(locator: &str) -> bool {
+    !locator.is_empty()
+        && !locator.starts_with('#')
+        && matches!(
+            Url::parse(locator),
+            Err(url::ParseError::RelativeUrlWithoutBase)
+        )
+}
+
+/// Every locator in `front`: its `spec` or `target`, `dependencies`, and `references`.
+fn front_matter_locators(front: &ArtifactFrontMatter) -> impl Iterator<Item = &str> {
+    let (single, dependencies, references): (
+        Option<&String>,
+        &[DependencyEntry],
+        &[ReferenceEntry],
+    ) = match front {
+        ArtifactFrontMatter::Specification(spec) => (None, &spec.dependencies, &[]),
+        ArtifactFrontMatter::Implementation(implementation) => (
+            implementation.spec.as_ref(),
+            &implementation.dependencies,
+            &implementation.references,
+        ),
+        ArtifactFrontMatter::Scratch(scratch) => {
+            (scratch.target.as_ref(), &scratch.dependencies, &[])
+        }
+    };
+    single
+        .map(String::as_str)
+        .into_iter()
+        .chain(dependencies.iter().map(|entry| match entry {
+            DependencyEntry::Simple(reference) => reference.as_str(),
+            DependencyEntry::Detailed(object) => object.reference.as_str(),
+        }))
+        .chain(references.iter().map(|entry| entry.reference.as_str()))
+}
+
+/// `locator` is `source`, optionally followed by a heading fragment.
+fn names_source(locator: &str, source: &Url) -> bool {
+    locator
+        .strip_prefix(source.as_str())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('#'))
+}
+
+/// `.../core/spec.md` names `core`; `.../core.md` names `core`.
+fn name_from_url(url: &Url) -> Option<String> {
+    let mut segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let last = segments.pop()?;
+    let stem = if last == "spec.md" {
+        segments.pop()?
+    } else {
+        last.strip_suffix(".md").unwrap_or(last)
+    };
+    Some(stem.to_string())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn workspace_relative(workspace: &WorkspacePaths, path: &Path) -> String {
+    path.strip_prefix(workspace.root())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::FilesystemWorkspaceLocator;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    const URL: &str = "https://example.com/specs/core/spec.md";
+
+    /// Serves canned bodies that tests can swap out between calls.
+    #[derive(Default)]
+    struct StubFetcher {
+        responses: Mutex<HashMap<String, String>>,
+    }
+
+    impl StubFetcher {
+        fn serve(&self, url: &str, body: &str) {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(url.to_string(), body.to_string());
+        }
+    }
+
+    impl ContentFetcher for StubFetcher {
+        fn fetch(&self, url: &Url) -> Result<String, SpecmanError> {
+            self.responses
+                .lock()
+                .unwrap()
+                .get(url.as_str())
+                .cloned()
+                .ok_or_else(|| SpecmanError::Dependency(format!("failed to fetch {url}")))
+        }
+    }
+
+    fn workspace() -> (tempfile::TempDir, PathBuf) {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("workspace");
+        fs::create_dir_all(root.join(".specman")).unwrap();
+        fs::create_dir_all(root.join("impl/consumer")).unwrap();
+        fs::write(
+            root.join("impl/consumer/impl.md"),
+            format!(
+                "---\nname: consumer\nspec: {URL}\nreferences:\n  - ref: {URL}#dependencies\n    type: specification\n---\n# Consumer\n"
+            ),
+        )
+        .unwrap();
+        (temp, root)
+    }
+
+    #[test]
+    fn vendor_copies_the_document_and_repoints_references() {
+        let (_temp, root) = workspace();
+        let fetcher = Arc::new(StubFetcher::default());
+        let upstream = "---\nname: core\nversion: \"1.0.0\"\n---\n# Core\n\n## Dependencies\n";
+        fetcher.serve(URL, upstream);
+        let vendorer = Vendorer::new(
+            Arc::new(FilesystemWorkspaceLocator::new(&root)),
+            fetcher.clone(),
+        );
+
+        let outcome = vendorer.vendor(URL, None).unwrap();
+        assert_eq!(outcome.vendored.path, "spec/core/spec.md");
+        assert_eq!(outcome.vendored.sha256, sha256_hex(upstream.as_bytes()));
+        assert_eq!(
+            fs::read_to_string(root.join("spec/core/spec.md")).unwrap(),
+            upstream
+        );
+        assert_eq!(
+            outcome.rewritten,
+            vec![ArtifactId {
+                kind: ArtifactKind::Implementation,
+                name: "consumer".into(),
+            }]
+        );
+        let consumer = fs::read_to_string(root.join("impl/consumer/impl.md")).unwrap();
+        assert!(consumer.contains("spec: spec://core\n"));
+        assert!(consumer.contains("ref: spec://core#dependencies"));
+        assert!(!consumer.contains(URL));
+
+        let workspace = FilesystemWorkspaceLocator::new(&root).workspace().unwrap();
+        let manifest = VendorManifest::load(&workspace).unwrap();
+        assert_eq!(manifest.artifacts, vec![outcome.vendored.clone()]);
+
+        let checks = vendorer.check().unwrap();
+        assert!(!checks[0].upstream_changed() && !checks[0].locally_modified());
+
+        fetcher.serve(URL, "---\nname: core\nversion: \"1.1.0\"\n---\n# Core\n");
+        let checks = vendorer.check().unwrap();
+        assert!(checks[0].upstream_changed());
+        assert!(!checks[0].locally_modified());
+    }
+
+    #[test]
+    fn vendor_refuses_to_overwrite_workspace_specifications() {
+        let (_temp, root) = workspace();
+        fs::create_dir_all(root.join("spec/core")).unwrap();
+        fs::write(root.join("spec/core/spec.md"), "---\nname: core\n---\n").unwrap();
+        let fetcher = Arc::new(StubFetcher::default());
+        fetcher.serve(URL, "---\nname: core\n---\n# Core\n\n## Dependencies\n");
+        let vendorer = Vendorer::new(Arc::new(FilesystemWorkspaceLocator::new(&root)), fetcher);
+
+        let err = vendorer.vendor(URL, None).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+
+        let outcome = vendorer.vendor(URL, Some("upstream-core")).unwrap();
+        assert_eq!(outcome.vendored.path, "spec/upstream-core/spec.md");
+        let copy = fs::read_to_string(root.join("spec/upstream-core/spec.md")).unwrap();
+        assert!(copy.contains("name: upstream-core\n"), "{copy}");
+        assert!(copy.ends_with("# Core\n\n## Dependencies\n"));
+        let check = &vendorer.check().unwrap()[0];
+        assert!(!check.upstream_changed() && !check.locally_modified());
+    }
+
+    #[test]
+    fn vendor_rebases_relative_locators_onto_the_source_url() {
+        let (_temp, root) = workspace();
+        let fetcher = Arc::new(StubFetcher::default());
+        fetcher.serve(
+            URL,
+            "---\nname: core\ndependencies:\n  - ../data-model/spec.md#entities\n  - ref: ./extras.md\n    optional: true\n  - https://example.org/other.md\n  - spec://shared\n---\n# Core\n\n## Dependencies\n",
+        );
+        let vendorer = Vendorer::new(Arc::new(FilesystemWorkspaceLocator::new(&root)), fetcher);
+
+        let outcome = vendorer.vendor(URL, None).unwrap();
+        let copy = fs::read_to_string(root.join("spec/core/spec.md")).unwrap();
+        assert!(copy.contains("- https://example.com/specs/data-model/spec.md#entities\n"));
+        assert!(copy.contains("ref: https://example.com/specs/core/extras.md\n"));
+        assert!(copy.contains("- https://example.org/other.md\n"));
+        assert!(copy.contains("- spec://shared\n"));
+        assert_eq!(
+            outcome.vendored.written_sha256,
+            Some(sha256_hex(copy.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn vendor_only_rewrites_entries_that_name_the_source() {
+        let (_temp, root) = workspace();
+        fs::create_dir_all(root.join("impl/neighbor")).unwrap();
+        let neighbor =
+            format!("---\nname: neighbor\nspec: {URL}.bak\nlocation: {URL}\n---\n# Neighbor\n");
+        fs::write(root.join("impl/neighbor/impl.md"), &neighbor).unwrap();
+        let fetcher = Arc::new(StubFetcher::default());
+        fetcher.serve(URL, "---\nname: core\n---\n# Core\n\n## Dependencies\n");
+        let vendorer = Vendorer::new(Arc::new(FilesystemWorkspaceLocator::new(&root)), fetcher);
+
+        let outcome = vendorer.vendor(URL, None).unwrap();
+        let names: Vec<&str> = outcome
+            .rewritten
+            .iter()
+            .map(|artifact| artifact.name.as_str())
+            .collect();
+        assert_eq!(names, ["consumer"]);
+        assert_eq!(
+            fs::read_to_string(root.join("impl/neighbor/impl.md")).unwrap(),
+            neighbor
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use crate::error::SpecmanError;
+use crate::error::{CodedError, ErrorCode, SpecmanError};
 
 /// Canonical paths for a SpecMan workspace.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -105,10 +105,15 @@ pub fn discover(start: impl AsRef<Path>) -> Result<WorkspacePaths, SpecmanError>
         }
     }
 
-    Err(SpecmanError::Workspace(format!(
-        "no .specman directory found from {}",
-        canonical_start.display()
-    )))
+    Err(CodedError::new(
+        ErrorCode::WorkspaceNotFound,
+        format!(
+            "no .specman directory found from {}",
+            canonical_start.display()
+        ),
+    )
+    .with_locator(canonical_start.display().to_string())
+    .into())
 }
 
 fn normalize_start(start: &Path) -> Result<PathBuf, SpecmanError> {
@@ -172,7 +177,7 @@ mod tests {
         fs::create_dir_all(&root).unwrap();
 
         let err = discover(&root).expect_err("expected workspace error");
-        assert!(matches!(err, SpecmanError::Workspace(_)));
+        assert_eq!(err.code(), ErrorCode::WorkspaceNotFound);
     }
 
     #[test]
@@ -191,6 +196,6 @@ mod tests {
         fs::remove_dir_all(first.dot_specman()).unwrap();
 
         let err = locator.workspace().expect_err("should error after removal");
-        assert!(matches!(err, SpecmanError::Workspace(_)));
+        assert_eq!(err.code(), ErrorCode::WorkspaceNotFound);
     }
 }